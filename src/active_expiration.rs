@@ -1,71 +1,277 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
 
+use crate::stats::update_expired_stale_perc;
 use crate::store::Store;
+use crate::timeseries::retention::enforce_retention;
 
 /// Implement redis active expiration
 /// https://redis.io/commands/expire
-/// Define cronInterval in which you randomly select 20 keys with expiration set
-/// if they have expiration set and expiration is in the past, delete the key
-/// While doing this increase a counter and check whether we have deleted more than 25% of the keys
-/// if so, repeat the process
+/// see https://github.com/redis/redis/blob/unstable/src/expire.c
+///
+/// Every cycle samples random keys with an expiration of each database and deletes the expired ones.
+/// As long as more than the acceptable share of the sampled keys was expired, the database is sampled again.
+/// A cycle stops when its time budget is used, so a large number of expired keys does not block the event loop:
+///
+/// slow cycle   runs 10 times per second and may use 25% of the CPU time, i.e. 25ms
+/// fast cycle   runs before polling for events if the last cycle ran out of time or many sampled keys were expired,
+///              it is limited to 1ms and runs at most every 2ms
+///
+/// active-expire-effort (1-10) increases the sampled keys, the time budgets and lowers the acceptable share of expired keys.
+/// The next cycle continues with the database after the last visited one.
+///
+/// With active-expire-index enabled, the keys are not sampled but taken from the expiry index of the store in the order of
+/// their expiration, so every cycle deletes exactly the keys which are due (see expiry_index). The time budgets still apply,
+/// a fast cycle runs if the last cycle ran out of time.
+///
+/// The same manager also removes samples of time series which are older than their retention period, every 3 seconds.
+/// Every logical database is checked in each cycle, but only its keys holding a time series are visited
+
+pub const DEFAULT_ACTIVE_EXPIRE_EFFORT: u32 = 1;
+pub const MAX_ACTIVE_EXPIRE_EFFORT: u32 = 10;
+
+// keys sampled per database and loop
+const KEYS_PER_LOOP: usize = 20;
+const FAST_CYCLE_DURATION_US: u64 = 1000;
+// share of the CPU time of the slow cycle in percent
+const SLOW_CYCLE_TIME_PERC: u64 = 25;
+// percentage of expired keys among the sampled keys which stops sampling a database
+const ACCEPTABLE_STALE_PERC: u64 = 10;
+// the slow cycle runs 10 times per second, like serverCron with the default hz of Redis
+const SLOW_CYCLE_INTERVAL: Duration = Duration::from_millis(100);
+const RETENTION_INTERVAL: Duration = Duration::from_millis(3000);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExpireCycle {
+    Fast,
+    Slow,
+}
+
+/// Limits of a cycle derived from active-expire-effort
+struct CycleConfiguration {
+    keys_per_loop: usize,
+    time_limit: Duration,
+    acceptable_stale_perc: u64,
+}
+
+impl CycleConfiguration {
+    fn new(cycle: ExpireCycle, effort: u32) -> Self {
+        // effort 1 is the default, every additional level adds 25% of the defaults
+        let effort = effort.clamp(DEFAULT_ACTIVE_EXPIRE_EFFORT, MAX_ACTIVE_EXPIRE_EFFORT) as u64 - 1;
+        let time_limit = match cycle {
+            ExpireCycle::Fast => Duration::from_micros(get_fast_cycle_duration_us(effort)),
+            ExpireCycle::Slow => SLOW_CYCLE_INTERVAL * (SLOW_CYCLE_TIME_PERC + 2 * effort) as u32 / 100,
+        };
+
+        CycleConfiguration {
+            keys_per_loop: KEYS_PER_LOOP + KEYS_PER_LOOP / 4 * effort as usize,
+            time_limit,
+            acceptable_stale_perc: ACCEPTABLE_STALE_PERC - effort,
+        }
+    }
+}
+
+fn get_fast_cycle_duration_us(effort: u64) -> u64 {
+    FAST_CYCLE_DURATION_US + FAST_CYCLE_DURATION_US / 4 * effort
+}
 
 pub struct ActiveExpirationManager {
-    cron_interval_ms: u64,
-    last_run: u64,
-    deleted_keys: u64,
-    total_keys: u64,
+    last_slow_cycle: Option<Instant>,
+    last_fast_cycle: Option<Instant>,
+    last_retention: Option<Instant>,
+    // database where the next cycle starts
+    next_db: usize,
+    // whether the last cycle stopped because its time budget was used
+    timed_out: bool,
+    // moving average of the share of expired keys among the sampled keys
+    stale_perc: f64,
 }
 
 impl ActiveExpirationManager {
-    pub fn new(cron_interval: u64) -> Self {
+    pub fn new() -> Self {
         Self {
-            cron_interval_ms: cron_interval,
-            last_run: 0,
-            deleted_keys: 0,
-            total_keys: 0,
+            last_slow_cycle: None,
+            last_fast_cycle: None,
+            last_retention: None,
+            next_db: 0,
+            timed_out: false,
+            stale_perc: 0.0,
         }
     }
 
-    pub fn run_loop(&mut self, store: &mut Store) {
-        loop {
-            if self.run(store) {
-                println!("Expiration Manager: deleted {} keys out of {} total keys", self.deleted_keys, self.total_keys);
-            } else {
-                break;
+    /// Called by the event loop before polling for events, runs a slow cycle every 100ms and a fast cycle in between if needed
+    pub fn run(&mut self, store: &mut Store) {
+        let now = Instant::now();
+        if self.last_slow_cycle.is_none_or(|last_run| now.duration_since(last_run) >= SLOW_CYCLE_INTERVAL) {
+            self.last_slow_cycle = Some(now);
+            self.run_cycle(ExpireCycle::Slow, store);
+        } else if self.needs_fast_cycle(now, store.get_active_expire_effort()) {
+            self.last_fast_cycle = Some(now);
+            self.run_cycle(ExpireCycle::Fast, store);
+        }
+
+        if self.last_retention.is_none_or(|last_run| now.duration_since(last_run) >= RETENTION_INTERVAL) {
+            self.last_retention = Some(now);
+            let removed_samples = run_retention(store);
+            if removed_samples > 0 {
+                println!("Expiration Manager: removed {} time series samples older than their retention", removed_samples);
             }
         }
     }
 
-    fn run(&mut self, store: &mut Store) -> bool {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
-        if (now - self.last_run) < self.cron_interval_ms {
-            // Cron interval not reached yet
+    /// A fast cycle only makes sense if there are probably many expired keys left
+    fn needs_fast_cycle(&self, now: Instant, effort: u32) -> bool {
+        let config = CycleConfiguration::new(ExpireCycle::Fast, effort);
+        if !self.timed_out && self.stale_perc * 100.0 < config.acceptable_stale_perc as f64 {
             return false;
         }
+        self.last_fast_cycle.is_none_or(|last_run| now.duration_since(last_run) >= config.time_limit * 2)
+    }
+
+    /// Samples the keys with an expiration of all databases until few sampled keys are expired or the time budget is used.
+    /// Returns the number of deleted keys
+    fn run_cycle(&mut self, cycle: ExpireCycle, store: &mut Store) -> u64 {
+        let config = CycleConfiguration::new(cycle, store.get_active_expire_effort());
+        let start = Instant::now();
+        let mut sampled_keys = 0;
+        let mut deleted_keys = 0;
+        self.timed_out = false;
+
+        // the database selected by the last client is restored afterwards
+        let selected_db = store.get_selected_db();
+        let db_count = store.get_db_count();
+        'databases: for _ in 0..db_count {
+            store.select_db(self.next_db % db_count).expect("Database does not exist");
+            self.next_db = (self.next_db + 1) % db_count;
+
+            let with_expiry_index = store.has_expiry_index();
+            loop {
+                let keys: Vec<String> = match with_expiry_index {
+                    true => store.get_expired_keys(config.keys_per_loop),
+                    false => store.get_expiration_data().sample(config.keys_per_loop)
+                        .into_iter()
+                        .map(|(key, _)| key.clone())
+                        .collect(),
+                };
+                if keys.is_empty() {
+                    break;
+                }
+
+                let expired = keys.iter().filter(|key| store.expire_if_needed(key)).count() as u64;
+                deleted_keys += expired;
+                // the index only returns expired keys, they do not tell how many expired keys are left
+                if !with_expiry_index {
+                    sampled_keys += keys.len() as u64;
+                }
 
-        let mut num_keys = 0;
-        let mut num_deleted_keys = 0;
-        let mut keys_to_delete = Vec::new();
-        let max_keys_with_expiration_to_visit = 20;
-
-        // iterate in a random order over the data of the store until we saw 20 keys with expiration and delete expired keys
-        for (key, expires_at) in store.get_expiration_data().iter().take(max_keys_with_expiration_to_visit) {
-            num_keys += 1;
-            if *expires_at < now as i64 {
-                num_deleted_keys += 1;
-                keys_to_delete.push(key.clone());
+                if start.elapsed() >= config.time_limit {
+                    self.timed_out = true;
+                    break 'databases;
+                }
+                if !with_expiry_index && expired * 100 <= keys.len() as u64 * config.acceptable_stale_perc {
+                    break;
+                }
             }
         }
+        store.select_db(selected_db).expect("Database does not exist");
 
-        for key in keys_to_delete {
-            store.remove(&key);
+        let stale_perc = if sampled_keys > 0 { deleted_keys as f64 / sampled_keys as f64 } else { 0.0 };
+        self.stale_perc = update_expired_stale_perc(stale_perc);
+        if deleted_keys > 0 {
+            println!("Expiration Manager: {:?} cycle deleted {} of {} sampled keys in {:?}", cycle, deleted_keys, sampled_keys, start.elapsed());
+        }
+        deleted_keys
+    }
+}
+
+fn run_retention(store: &mut Store) -> usize {
+    let selected_db = store.get_selected_db();
+    let mut removed_samples = 0;
+    for db in 0..store.get_db_count() {
+        store.select_db(db).expect("Database does not exist");
+        removed_samples += enforce_retention(store);
+    }
+    store.select_db(selected_db).expect("Database does not exist");
+    removed_samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_type_encoding::{OBJ_ENCODING_EMBSTR, OBJ_TYPE_STRING};
+    use crate::store::ObjectValue;
+    use crate::string_value::StringValue;
+
+    #[test]
+    fn test_cycle_configuration() {
+        // given
+        let default = CycleConfiguration::new(ExpireCycle::Slow, DEFAULT_ACTIVE_EXPIRE_EFFORT);
+        let max_effort = CycleConfiguration::new(ExpireCycle::Slow, MAX_ACTIVE_EXPIRE_EFFORT);
+        let fast = CycleConfiguration::new(ExpireCycle::Fast, DEFAULT_ACTIVE_EXPIRE_EFFORT);
+
+        // then
+        assert_eq!(default.keys_per_loop, 20);
+        assert_eq!(default.time_limit, Duration::from_millis(25));
+        assert_eq!(default.acceptable_stale_perc, 10);
+        assert_eq!(max_effort.keys_per_loop, 65);
+        assert_eq!(max_effort.time_limit, Duration::from_millis(43));
+        assert_eq!(max_effort.acceptable_stale_perc, 1);
+        assert_eq!(fast.time_limit, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_run_cycle_deletes_expired_keys() {
+        // given
+        let mut store = Store::new(2);
+        let mut manager = ActiveExpirationManager::new();
+        for i in 0..100 {
+            store.put(&format!("expired:{}", i), ObjectValue::String(StringValue::new(String::from("value"))), 1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+            store.put(&format!("volatile:{}", i), ObjectValue::String(StringValue::new(String::from("value"))), 100000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+        }
+        store.select_db(1).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+
+        // when
+        let first_cycle_deleted_keys = manager.run_cycle(ExpireCycle::Slow, &mut store);
+        // sampling stops once few sampled keys are expired, so the remaining keys are deleted by the next cycles
+        let mut deleted_keys = first_cycle_deleted_keys;
+        for _ in 0..100 {
+            if deleted_keys == 100 {
+                break;
+            }
+            deleted_keys += manager.run_cycle(ExpireCycle::Slow, &mut store);
         }
 
-        self.total_keys = store.get_data().len() as u64;
-        self.last_run = now;
-        self.deleted_keys = num_deleted_keys;
+        // then
+        assert!(first_cycle_deleted_keys > 0);
+        assert_eq!(deleted_keys, 100);
+        assert_eq!(store.get_selected_db(), 1);
+        store.select_db(0).unwrap();
+        assert_eq!(store.get_data().len(), 100);
+        assert!(store.get_keys("volatile:*").len() == 100);
+    }
+
+    #[test]
+    fn test_run_cycle_with_expiry_index() {
+        // given
+        let mut store = Store::new(1);
+        store.set_expiry_index(true);
+        let mut manager = ActiveExpirationManager::new();
+        for i in 0..1000u32 {
+            let expiration_ms = if i.is_multiple_of(10) { 1 } else { 100000 };
+            store.put(&format!("key:{}", i), ObjectValue::String(StringValue::new(String::from("value"))), expiration_ms, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+        }
+        std::thread::sleep(Duration::from_millis(5));
+
+        // when
+        let mut deleted_keys = 0;
+        for _ in 0..10 {
+            deleted_keys += manager.run_cycle(ExpireCycle::Slow, &mut store);
+        }
 
-        let exp_keys_ratio = num_deleted_keys as f64 / num_keys as f64;
-        return exp_keys_ratio > 0.25;
+        // then
+        // all expired keys are deleted, although they are only 10% of the keys
+        assert_eq!(deleted_keys, 100);
+        assert_eq!(store.get_data().len(), 900);
+        assert!(store.get_expired_keys(10).is_empty());
     }
 }
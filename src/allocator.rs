@@ -0,0 +1,92 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts the bytes allocated by the process, like zmalloc in Redis, see used_memory in INFO
+///
+/// The counter includes everything allocated by the server (keys, values, hash tables, client buffers),
+/// so it can be compared directly against maxmemory. Allocations are passed to the system allocator.
+
+pub struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static STARTUP_ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            add_allocated_bytes(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            add_allocated_bytes(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                add_allocated_bytes((new_size - layout.size()) as u64);
+            } else {
+                ALLOCATED_BYTES.fetch_sub((layout.size() - new_size) as u64, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+fn add_allocated_bytes(bytes: u64) {
+    let allocated = ALLOCATED_BYTES.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK_ALLOCATED_BYTES.fetch_max(allocated, Ordering::Relaxed);
+}
+
+/// Bytes currently allocated by the process
+pub fn get_allocated_bytes() -> u64 {
+    ALLOCATED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Highest number of bytes allocated since the start of the process
+pub fn get_peak_allocated_bytes() -> u64 {
+    PEAK_ALLOCATED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Remembers the bytes allocated by the initialized server before any key is loaded, see startup.allocated in MEMORY STATS
+pub fn record_startup_allocated_bytes() {
+    STARTUP_ALLOCATED_BYTES.store(get_allocated_bytes(), Ordering::Relaxed);
+}
+
+pub fn get_startup_allocated_bytes() -> u64 {
+    STARTUP_ALLOCATED_BYTES.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_allocations() {
+        // when
+        let mut buffer: Vec<u8> = Vec::with_capacity(1024 * 1024);
+        buffer.reserve(2 * 1024 * 1024);
+
+        // then
+        // other tests allocate concurrently, the live buffer is a lower bound of the counters
+        assert!(get_allocated_bytes() >= buffer.capacity() as u64);
+        assert!(get_peak_allocated_bytes() >= buffer.capacity() as u64);
+    }
+}
@@ -1,29 +1,52 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
 use std::net::{IpAddr, Ipv4Addr, TcpListener};
 use std::os::fd::{AsRawFd, RawFd};
+use std::time::Duration;
 
 use libc::{timespec};
 
 use crate::active_expiration::ActiveExpirationManager;
 use crate::client::ClientConnection;
+use crate::cmd::cmd_bgrewriteaof::AOF_FILE_NAME;
 use crate::cmd::command::SimpleCommand::BGREWRITEAOF;
 use crate::cmd::handler::CommandHandler;
 use crate::io_multiplexer::darwin_io_multiplexer::DarwinIOMultiplexer;
+use crate::config::Config;
 use crate::io_multiplexer::io_multiplexer::{Event, IOMultiplexer};
+use crate::resp::{DataType, RESPParser};
 use crate::signal::listen_for_shutdown_signals;
+use crate::allocator::record_startup_allocated_bytes;
+use crate::shared_integers::create_shared_integers;
 use crate::store::Store;
 
 const PORT: i16 = 9977;
 const ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
 const MAX_CLIENT_CONNECTIONS: usize = 1024;
+// time per event loop iteration spent on moving keys of resized hash tables, like activerehashing in Redis
+const ACTIVE_REHASHING_BUDGET: Duration = Duration::from_millis(1);
 
 pub fn setup_server() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = Config::parse(&args).expect("Invalid configuration");
+
     let (listener, listener_fd) = setup_tcp_listener();
-    let mut store = Store::new();
-    start_event_loop(listener, listener_fd, &mut store);
+    create_shared_integers();
+    let mut store = Store::new(config.databases);
+    let mut command_handler = CommandHandler::new();
+    record_startup_allocated_bytes();
+    load_aof_file(&mut store, &mut command_handler);
+
+    // like Redis, the limit applies after loading the AOF file, so a restart does not lose keys
+    store.set_maxmemory(config.maxmemory);
+    store.set_eviction_policy(config.maxmemory_policy);
+    store.set_active_expire_effort(config.active_expire_effort);
+    store.set_expiry_index(config.active_expire_index);
+    start_event_loop(listener, listener_fd, &mut store, &mut command_handler);
 }
 
-fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store) {
+fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store, command_handler: &mut CommandHandler) {
     // listen to process signals
     let signal_receiver = listen_for_shutdown_signals().expect("Can not listen for process signals");
 
@@ -37,22 +60,25 @@ fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store
     // if the client connection goes out of scope, the connection will be closed. Because of this we need to store the connections
     let mut client_connections = HashMap::new();
 
-    let mut command_handler = CommandHandler::new();
-
-    let mut active_expiration_manager = ActiveExpirationManager::new(3000);
+    let mut active_expiration_manager = ActiveExpirationManager::new();
 
     // event loop
     loop {
         // check for shutdown signals
         match signal_receiver.try_recv() {
             Ok(_) => {
-                cleanup(&mut io_multiplexer, store, &mut command_handler);
+                cleanup(&mut io_multiplexer, store, command_handler);
                 std::process::exit(0);
             }
             Err(_) => {}
         }
 
-        active_expiration_manager.run_loop(store);
+        // like Redis, keys do not expire while clients are paused, so the dataset does not change
+        if store.get_clients().get_pause_mode().is_none() {
+            active_expiration_manager.run(store);
+            resume_paused_clients(&mut client_connections, store, command_handler);
+        }
+        store.rehash_databases(ACTIVE_REHASHING_BUDGET);
 
         let events = io_multiplexer.poll(timespec { tv_sec: 0, tv_nsec: 0 });
 
@@ -69,7 +95,8 @@ fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store
                         let event = Event::new(stream_fd, libc::EVFILT_READ);
                         match io_multiplexer.register(event) {
                             Ok(_) => {
-                                client_connections.insert(stream_fd, ClientConnection::new(stream));
+                                let id = store.get_clients_mut().register(&stream);
+                                client_connections.insert(stream_fd, ClientConnection::new(stream, id));
                             }
                             Err(e) => {
                                 println!("{}", e);
@@ -85,12 +112,15 @@ fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store
 
                         if event.connection_closed {
                             println!("Connection got closed by client");
-                            client_connections.remove(&event.fd);
+                            if let Some(client_connection) = client_connections.remove(&event.fd) {
+                                store.get_clients_mut().unregister(client_connection.id);
+                            }
                             continue;
                         }
                     }
 
                 }
+                close_killed_clients(&mut client_connections, store);
             }
             Err(e) => {
                 println!("{}", e);
@@ -99,6 +129,43 @@ fn start_event_loop(listener: TcpListener, listener_fd: RawFd, store: &mut Store
     }
 }
 
+/// Executes the requests which waited for the end of a client pause
+fn resume_paused_clients(client_connections: &mut HashMap<RawFd, ClientConnection>, store: &mut Store, command_handler: &mut CommandHandler) {
+    for client_connection in client_connections.values_mut().filter(|connection| !connection.pending_requests.is_empty()) {
+        command_handler.handle_pending_requests(client_connection, store);
+    }
+    close_killed_clients(client_connections, store);
+}
+
+/// Closes the connections of the clients killed by CLIENT KILL, dropping the stream closes the socket
+fn close_killed_clients(client_connections: &mut HashMap<RawFd, ClientConnection>, store: &mut Store) {
+    for fd in store.get_clients().get_killed_fds() {
+        if let Some(client_connection) = client_connections.remove(&fd) {
+            println!("Closing killed client connection {}", client_connection.id);
+            store.get_clients_mut().unregister(client_connection.id);
+        }
+    }
+}
+
+/// Replays the commands of the AOF file written on the last shutdown. SELECT markers in the file switch the database
+fn load_aof_file(store: &mut Store, command_handler: &mut CommandHandler) {
+    let file = match File::open(AOF_FILE_NAME) {
+        Ok(file) => file,
+        Err(_) => return,
+    };
+
+    println!("Loading AOF file...");
+    let mut parser = RESPParser::new();
+    let commands = parser.decode_next_bulk(&mut BufReader::new(file)).expect("Can not decode AOF file");
+    for command in commands {
+        if let DataType::Error(err) = command_handler.handle_simple_command_request(command, store) {
+            eprintln!("Error while loading AOF file: {}", err);
+        }
+    }
+
+    store.select_db(0).expect("Database does not exist");
+}
+
 fn cleanup(io_multiplexer: &mut DarwinIOMultiplexer, store: &mut Store, cmd_handler: &mut CommandHandler) {
     io_multiplexer.close();
     cmd_handler.execute_simple_command(&BGREWRITEAOF, &mut Vec::new(), store);
@@ -1,18 +1,302 @@
-use std::net::{TcpStream};
+use std::collections::{BTreeMap, VecDeque};
+use std::net::{SocketAddr, TcpStream};
+use std::os::fd::AsRawFd;
+use std::time::{Duration, Instant};
 use crate::resp::DataType;
 
+/// Clients are the connections of the event loop. Their state shown by CLIENT LIST and CLIENT INFO is kept in the
+/// ClientRegistry of the store, so commands can inspect and kill other clients without access to the event loop.
+/// Like the selected database, the store knows which client the current command belongs to.
+///
+/// Killed clients are only marked, the event loop closes their connections after the reply of the current request
+/// was written. While clients are paused (see CLIENT PAUSE) their requests are kept in `pending_requests`.
+
 pub struct ClientConnection {
     pub stream: TcpStream,
     pub is_transaction_active: bool,
     pub cmd_queue: Vec<DataType>,
+    // logical database used by the commands of this connection, see SELECT
+    pub selected_db: usize,
+    // unique id of the client in the ClientRegistry
+    pub id: u64,
+    // requests which were read but not executed yet, because clients are paused
+    pub pending_requests: VecDeque<DataType>,
+    // encoded size of the pending requests in bytes, shown as qbuf by CLIENT LIST
+    pub pending_requests_size: usize,
 }
 
 impl ClientConnection {
-    pub fn new(stream: TcpStream) -> Self {
+    pub fn new(stream: TcpStream, id: u64) -> Self {
         ClientConnection {
             stream,
             is_transaction_active: false,
             cmd_queue: Vec::new(),
+            selected_db: 0,
+            id,
+            pending_requests: VecDeque::new(),
+            pending_requests_size: 0,
+        }
+    }
+}
+
+/// State of a client as shown by CLIENT LIST
+pub struct ClientInfo {
+    pub id: u64,
+    // set by CLIENT SETNAME, empty if the client has no name
+    pub name: String,
+    pub address: Option<SocketAddr>,
+    pub local_address: Option<SocketAddr>,
+    pub fd: i32,
+    pub created_at: Instant,
+    pub last_interaction: Instant,
+    pub db: usize,
+    // number of queued commands if a transaction is active
+    pub multi: Option<usize>,
+    // the client sent commands which wait for the end of a pause
+    pub blocked: bool,
+    // set by CLIENT NO-EVICT, there is no client eviction yet, so the flag is only reported
+    pub no_evict: bool,
+    // the client was killed and is closed after the current request
+    pub killed: bool,
+    // bytes of the requests waiting for the end of a pause. Replies are written right away, there is no output buffer
+    pub query_buffer_size: usize,
+    // bytes written to the client
+    pub total_net_out: u64,
+    pub last_command: String,
+}
+
+impl ClientInfo {
+    fn new(id: u64, stream: &TcpStream) -> Self {
+        let now = Instant::now();
+        ClientInfo {
+            id,
+            name: String::new(),
+            address: stream.peer_addr().ok(),
+            local_address: stream.local_addr().ok(),
+            fd: stream.as_raw_fd(),
+            created_at: now,
+            last_interaction: now,
+            db: 0,
+            multi: None,
+            blocked: false,
+            no_evict: false,
+            killed: false,
+            query_buffer_size: 0,
+            total_net_out: 0,
+            last_command: String::from("NULL"),
+        }
+    }
+
+    pub fn get_address(&self) -> String {
+        self.address.map_or(String::new(), |address| address.to_string())
+    }
+
+    pub fn get_local_address(&self) -> String {
+        self.local_address.map_or(String::new(), |address| address.to_string())
+    }
+
+    /// Flags as shown by CLIENT LIST: x = in a transaction, b = blocked, e = no-evict, A = closed as soon as possible,
+    /// N = no flags
+    pub fn get_flags(&self) -> String {
+        let mut flags = String::new();
+        if self.multi.is_some() {
+            flags.push('x');
+        }
+        if self.blocked {
+            flags.push('b');
+        }
+        if self.no_evict {
+            flags.push('e');
+        }
+        if self.killed {
+            flags.push('A');
+        }
+        if flags.is_empty() {
+            flags.push('N');
+        }
+        flags
+    }
+
+    /// One line of CLIENT LIST, see https://redis.io/commands/client-list/
+    pub fn to_info_line(&self) -> String {
+        let now = Instant::now();
+        format!("id={} addr={} laddr={} fd={} name={} age={} idle={} flags={} db={} multi={} qbuf={} obl=0 tot-net-out={} cmd={} user=default",
+                self.id,
+                self.get_address(),
+                self.get_local_address(),
+                self.fd,
+                self.name,
+                now.duration_since(self.created_at).as_secs(),
+                now.duration_since(self.last_interaction).as_secs(),
+                self.get_flags(),
+                self.db,
+                self.multi.map_or(-1, |queued| queued as i64),
+                self.query_buffer_size,
+                self.total_net_out,
+                self.last_command)
+    }
+}
+
+/// Commands which are delayed by CLIENT PAUSE, ALL is stricter than WRITE
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PauseMode {
+    Write,
+    All,
+}
+
+struct ClientPause {
+    mode: PauseMode,
+    until: Instant,
+}
+
+pub struct ClientRegistry {
+    // ordered by id, so CLIENT LIST returns the oldest client first
+    clients: BTreeMap<u64, ClientInfo>,
+    next_id: u64,
+    // client whose command is executed, None for commands which are not sent by a client (e.g. when loading the AOF file)
+    current_client: Option<u64>,
+    pause: Option<ClientPause>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        ClientRegistry {
+            clients: BTreeMap::new(),
+            next_id: 1,
+            current_client: None,
+            pause: None,
+        }
+    }
+
+    /// Adds a new connection and returns its id, ids are never reused
+    pub fn register(&mut self, stream: &TcpStream) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.clients.insert(id, ClientInfo::new(id, stream));
+        id
+    }
+
+    pub fn unregister(&mut self, id: u64) {
+        self.clients.remove(&id);
+        if self.current_client == Some(id) {
+            self.current_client = None;
+        }
+    }
+
+    pub fn get(&self, id: u64) -> Option<&ClientInfo> {
+        self.clients.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut ClientInfo> {
+        self.clients.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ClientInfo> {
+        self.clients.values()
+    }
+
+    pub fn set_current_client(&mut self, id: Option<u64>) {
+        self.current_client = id;
+    }
+
+    pub fn get_current_client_id(&self) -> Option<u64> {
+        self.current_client
+    }
+
+    pub fn get_current_client_mut(&mut self) -> Option<&mut ClientInfo> {
+        self.current_client.and_then(|id| self.clients.get_mut(&id))
+    }
+
+    /// Marks the client to be closed, returns false if the client does not exist or was already killed
+    pub fn kill(&mut self, id: u64) -> bool {
+        match self.clients.get_mut(&id) {
+            Some(client) if !client.killed => {
+                client.killed = true;
+                true
+            }
+            _ => false,
         }
     }
+
+    /// File descriptors of the killed clients, their connections are closed by the event loop
+    pub fn get_killed_fds(&self) -> Vec<i32> {
+        self.clients.values()
+            .filter(|client| client.killed)
+            .map(|client| client.fd)
+            .collect()
+    }
+
+    /// Pauses the clients until the timeout is over. If clients are already paused, the longer pause and the stricter mode apply
+    pub fn pause(&mut self, mode: PauseMode, timeout: Duration) {
+        let until = Instant::now() + timeout;
+        self.pause = Some(match self.pause.take() {
+            Some(pause) if pause.until > Instant::now() => ClientPause { mode: mode.max(pause.mode), until: until.max(pause.until) },
+            _ => ClientPause { mode, until },
+        });
+    }
+
+    pub fn unpause(&mut self) {
+        self.pause = None;
+    }
+
+    /// Mode of the current pause, None if the clients are not paused or the pause is over
+    pub fn get_pause_mode(&self) -> Option<PauseMode> {
+        self.pause.as_ref()
+            .filter(|pause| pause.until > Instant::now())
+            .map(|pause| pause.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use super::*;
+
+    fn connect(listener: &TcpListener) -> TcpStream {
+        TcpStream::connect(listener.local_addr().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_register_and_kill() {
+        // given
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let (first, second) = (connect(&listener), connect(&listener));
+        let mut registry = ClientRegistry::new();
+
+        // when
+        let first_id = registry.register(&first);
+        let second_id = registry.register(&second);
+        let killed = registry.kill(second_id);
+
+        // then
+        assert_eq!((first_id, second_id), (1, 2));
+        assert!(killed && !registry.kill(second_id) && !registry.kill(3));
+        assert_eq!(registry.get_killed_fds(), vec![second.as_raw_fd()]);
+        assert_eq!(registry.get(second_id).unwrap().get_flags(), "A");
+        assert_eq!(registry.get(first_id).unwrap().get_address(), first.peer_addr().unwrap().to_string());
+
+        registry.unregister(second_id);
+        assert_eq!(registry.iter().map(|client| client.id).collect::<Vec<u64>>(), vec![first_id]);
+    }
+
+    #[test]
+    fn test_pause() {
+        // given
+        let mut registry = ClientRegistry::new();
+
+        // when
+        registry.pause(PauseMode::All, Duration::from_millis(10));
+        registry.pause(PauseMode::Write, Duration::from_secs(10));
+
+        // then
+        // the stricter mode and the longer pause apply
+        assert_eq!(registry.get_pause_mode(), Some(PauseMode::All));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(registry.get_pause_mode(), Some(PauseMode::All));
+        registry.unpause();
+        assert_eq!(registry.get_pause_mode(), None);
+
+        registry.pause(PauseMode::Write, Duration::from_millis(0));
+        assert_eq!(registry.get_pause_mode(), None);
+    }
 }
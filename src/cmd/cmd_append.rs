@@ -0,0 +1,34 @@
+use crate::cmd::command::{Command, WRONG_TYPE_ERROR};
+use crate::object_type_encoding::OBJ_TYPE_STRING;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::{ObjectValue, Store};
+use crate::string_value::StringValue;
+
+/// see https://redis.io/commands/append/
+///
+/// Appending converts int and embstr values to raw strings
+pub struct AppendCommand;
+
+impl Command for AppendCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        return match store.get_mut(&args[0]) {
+            Some(store_object) => {
+                let string = match store_object.get_value_mut() {
+                    ObjectValue::String(string) => string,
+                    _ => return Error(String::from(WRONG_TYPE_ERROR)),
+                };
+                let length = string.append(&args[1]);
+                store_object.type_encoding = OBJ_TYPE_STRING | string.get_encoding();
+                Integer(length as i64)
+            }
+            None => {
+                let value = StringValue::new(args[1].clone());
+                let length = value.get_length();
+                let string_encoding = value.get_encoding();
+                store.put(&args[0], ObjectValue::String(value), -1, OBJ_TYPE_STRING | string_encoding);
+                Integer(length as i64)
+            }
+        };
+    }
+}
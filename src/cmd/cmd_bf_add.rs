@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::get_or_create_bloom_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/bf.add/
+pub struct BfAddCommand;
+
+impl Command for BfAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let filter = match get_or_create_bloom_filter(store, &args[0]) {
+            Ok(filter) => filter,
+            Err(err) => return err,
+        };
+
+        match filter.add(&args[1]) {
+            Ok(added) => Integer(added as i64),
+            Err(err) => Error(err),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::get_bloom_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/bf.exists/
+pub struct BfExistsCommand;
+
+impl Command for BfExistsCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_bloom_filter(store, &args[0]) {
+            Ok(Some(filter)) => Integer(filter.contains(&args[1]) as i64),
+            Ok(None) => Integer(0),
+            Err(err) => err,
+        }
+    }
+}
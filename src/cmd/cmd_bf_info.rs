@@ -0,0 +1,55 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_bloom_filter, NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer, Null, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/bf.info/
+pub struct BfInfoCommand;
+
+impl Command for BfInfoCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'bf.info' command"));
+        }
+
+        let filter = match get_bloom_filter(store, &args[0]) {
+            Ok(Some(filter)) => filter,
+            Ok(None) => return Error(String::from(NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let expansion = match filter.get_expansion() {
+            Some(expansion) => Integer(expansion as i64),
+            None => Null,
+        };
+        let fields = [
+            ("Capacity", Integer(filter.get_capacity() as i64)),
+            ("Size", Integer(filter.get_size() as i64)),
+            ("Number of filters", Integer(filter.get_number_of_filters() as i64)),
+            ("Number of items inserted", Integer(filter.get_number_of_items() as i64)),
+            ("Expansion rate", expansion),
+        ];
+
+        // a single field can be requested, e.g. BF.INFO key CAPACITY
+        if let Some(field) = args.get(1) {
+            let index = match field.to_uppercase().as_str() {
+                "CAPACITY" => 0,
+                "SIZE" => 1,
+                "FILTERS" => 2,
+                "ITEMS" => 3,
+                "EXPANSION" => 4,
+                _ => return Error(String::from("ERR Invalid information value")),
+            };
+            let (_, value) = fields.into_iter().nth(index).unwrap();
+            return Array(vec![value]);
+        }
+
+        let mut response = Vec::new();
+        for (name, value) in fields {
+            response.push(SimpleString(name.to_string()));
+            response.push(value);
+        }
+        Array(response)
+    }
+}
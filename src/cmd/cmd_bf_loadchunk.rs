@@ -0,0 +1,31 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_BLOOM};
+use crate::probabilistic::bloom_filter::BloomFilter;
+use crate::probabilistic::probabilistic_helper::get_bloom_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/bf.loadchunk/
+/// Restores a filter dumped with BF.SCANDUMP. Also used to persist bloom filters in the AOF file
+pub struct BfLoadChunkCommand;
+
+impl Command for BfLoadChunkCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args[1].parse::<i64>().is_err() {
+            return Error(String::from("ERR invalid iterator"));
+        }
+
+        if let Err(err) = get_bloom_filter(store, &args[0]) {
+            return err;
+        }
+
+        let filter = match BloomFilter::restore(&args[2]) {
+            Ok(filter) => filter,
+            Err(_) => return Error(String::from("ERR received bad data")),
+        };
+
+        store.put(&args[0], ObjectValue::BloomFilter(filter), -1, OBJ_TYPE_BLOOM | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
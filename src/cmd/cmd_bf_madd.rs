@@ -0,0 +1,27 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::get_or_create_bloom_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/bf.madd/
+pub struct BfMAddCommand;
+
+impl Command for BfMAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let filter = match get_or_create_bloom_filter(store, &args[0]) {
+            Ok(filter) => filter,
+            Err(err) => return err,
+        };
+
+        // a full non scaling filter only fails the items which do not fit anymore
+        let results = args[1..].iter()
+            .map(|item| match filter.add(item) {
+                Ok(added) => Integer(added as i64),
+                Err(err) => Error(err),
+            })
+            .collect();
+
+        Array(results)
+    }
+}
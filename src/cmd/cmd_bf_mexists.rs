@@ -0,0 +1,23 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::get_bloom_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/bf.mexists/
+pub struct BfMExistsCommand;
+
+impl Command for BfMExistsCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let filter = match get_bloom_filter(store, &args[0]) {
+            Ok(filter) => filter,
+            Err(err) => return err,
+        };
+
+        let results = args[1..].iter()
+            .map(|item| Integer(filter.as_ref().is_some_and(|filter| filter.contains(item)) as i64))
+            .collect();
+
+        Array(results)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_BLOOM};
+use crate::probabilistic::bloom_filter::{BloomFilter, DEFAULT_EXPANSION};
+use crate::probabilistic::probabilistic_helper::{get_bloom_filter, parse_integer, ITEM_EXISTS_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/bf.reserve/
+pub struct BfReserveCommand;
+
+impl Command for BfReserveCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let error_rate = match args[1].parse::<f64>() {
+            Ok(error_rate) if error_rate > 0.0 && error_rate < 1.0 => error_rate,
+            _ => return Error(String::from("ERR (0 < error rate range < 1)")),
+        };
+        let capacity = match parse_integer(&args[2], "ERR (capacity should be larger than 0)") {
+            Ok(capacity) if capacity > 0 => capacity,
+            Ok(_) => return Error(String::from("ERR (capacity should be larger than 0)")),
+            Err(err) => return err,
+        };
+
+        let mut expansion = DEFAULT_EXPANSION;
+        let mut non_scaling = false;
+        let mut i = 3;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "NONSCALING" => non_scaling = true,
+                "EXPANSION" if i + 1 < args.len() => {
+                    expansion = match parse_integer(&args[i + 1], "ERR bad expansion") {
+                        Ok(expansion) if expansion > 0 => expansion,
+                        Ok(_) => return Error(String::from("ERR expansion should be greater or equal to 1")),
+                        Err(err) => return err,
+                    };
+                    i += 1;
+                }
+                _ => return Error(String::from("ERR syntax error")),
+            }
+            i += 1;
+        }
+
+        match get_bloom_filter(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(ITEM_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        let filter = BloomFilter::new(capacity, error_rate, expansion, non_scaling);
+        store.put(&args[0], ObjectValue::BloomFilter(filter), -1, OBJ_TYPE_BLOOM | OBJ_ENCODING_RAW);
+
+        SimpleString(String::from("OK"))
+    }
+}
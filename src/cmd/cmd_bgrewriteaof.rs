@@ -3,31 +3,265 @@ use std::io::Write;
 use crate::cmd::command::Command;
 
 use crate::resp::{DataType, RESPParser};
-use crate::store::{Store, store_object_to_datatype};
+use crate::store::{ObjectValue, Store, StoreObject, store_object_to_datatype};
+use crate::timeseries::time_series::TimeSeries;
 
 /// see: https://redis.io/commands/bgrewriteaof
 pub struct BgRewriteAofCommand;
 
-const AOF_FILE_NAME: &str = "kataradb.aof";
+pub const AOF_FILE_NAME: &str = "kataradb.aof";
+const AOF_TIME_SERIES_BATCH_SIZE: usize = 1000;
 
 // todo: run aof rewrite in background process instead of doing it synchronously
 impl Command for BgRewriteAofCommand {
     fn execute(&self, _: &mut Vec<String>, store: &mut Store) -> DataType {
         println!("Rewriting AOF file...");
 
-        let mut parser = RESPParser::new();
         let mut aof_file = File::create(AOF_FILE_NAME).expect("Can not create AOF file");
+        write_aof(store, &mut aof_file);
 
+        return DataType::SimpleString("OK".to_string());
+    }
+}
+
+/// Writes the commands which recreate all databases
+fn write_aof<W: Write>(store: &mut Store, aof_file: &mut W) {
+    let mut parser = RESPParser::new();
+    let now = chrono::Utc::now().timestamp_millis();
+
+    // every database starts with a SELECT marker, so the keys are loaded into the same database again
+    let selected_db = store.get_selected_db();
+    for db in 0..store.get_db_count() {
+        store.select_db(db).expect("Database does not exist");
+        if store.get_data().is_empty() && store.get_search_index_definitions().is_empty() {
+            continue;
+        }
+
+        let command = DataType::Array(vec![DataType::BulkString(String::from("SELECT")), DataType::BulkString(db.to_string())]);
+        let encoded = parser.encode(command);
+        aof_file.write_all(encoded.as_bytes()).expect("Can not write to AOF file");
+
+        // keys which have already expired are not written, the expiration of volatile keys follows their value
+        let has_expired = |key: &str| store.get_expiry(key).is_some_and(|expires_at| expires_at <= now);
         for (key, value) in store.get_data().iter() {
-            let command = DataType::Array(vec![
+            if has_expired(key) {
+                continue;
+            }
+
+            let mut commands = get_rewrite_commands(key, value);
+            if let Some(expires_at) = store.get_expiry(key) {
+                commands.push(DataType::Array(vec![
+                    DataType::BulkString(String::from("PEXPIREAT")),
+                    DataType::BulkString(key.to_string()),
+                    DataType::BulkString(expires_at.to_string()),
+                ]));
+            }
+            for command in commands {
+                let encoded = parser.encode(command);
+                aof_file.write_all(encoded.as_bytes()).expect("Can not write to AOF file");
+            }
+        }
+
+        // compaction rules need the source and the destination series, so they are written after all keys
+        for (key, value) in store.get_data().iter() {
+            if let ObjectValue::TimeSeries(series) = value.value.as_ref() {
+                for rule in series.rules.iter() {
+                    if has_expired(key) || has_expired(&rule.destination_key) {
+                        continue;
+                    }
+
+                    let command = DataType::Array(vec![
+                        DataType::BulkString(String::from("TS.CREATERULE")),
+                        DataType::BulkString(key.to_string()),
+                        DataType::BulkString(rule.destination_key.clone()),
+                        DataType::BulkString(String::from("AGGREGATION")),
+                        DataType::BulkString(rule.aggregation.name().to_string()),
+                        DataType::BulkString(rule.bucket_duration.to_string()),
+                    ]);
+                    let encoded = parser.encode(command);
+                    aof_file.write_all(encoded.as_bytes()).expect("Can not write to AOF file");
+                }
+            }
+        }
+
+        // search indexes are created after all keys, FT.CREATE indexes the existing keys
+        for definition in store.get_search_index_definitions() {
+            let mut command = vec![DataType::BulkString(String::from("FT.CREATE"))];
+            command.extend(definition.arguments.iter().map(|argument| DataType::BulkString(argument.clone())));
+            let encoded = parser.encode(DataType::Array(command));
+            aof_file.write_all(encoded.as_bytes()).expect("Can not write to AOF file");
+        }
+    }
+    store.select_db(selected_db).expect("Database does not exist");
+}
+
+/// Returns the commands which recreate the object when replaying the AOF file
+fn get_rewrite_commands(key: &str, value: &StoreObject) -> Vec<DataType> {
+    let command = match value.value.as_ref() {
+        ObjectValue::SortedSet(sorted_set) => {
+            let mut command = vec![
+                DataType::BulkString(String::from("ZADD")),
+                DataType::BulkString(key.to_string()),
+            ];
+            for (member, score) in sorted_set.iter() {
+                command.push(DataType::BulkString(score.to_string()));
+                command.push(DataType::BulkString(member.to_string()));
+            }
+            DataType::Array(command)
+        }
+        ObjectValue::Json(document) => {
+            DataType::Array(vec![
+                DataType::BulkString(String::from("JSON.SET")),
+                DataType::BulkString(key.to_string()),
+                DataType::BulkString(String::from("$")),
+                DataType::BulkString(document.to_json_string()),
+            ])
+        }
+        ObjectValue::BloomFilter(filter) => {
+            DataType::Array(vec![
+                DataType::BulkString(String::from("BF.LOADCHUNK")),
+                DataType::BulkString(key.to_string()),
+                DataType::BulkString(String::from("1")),
+                DataType::BulkString(filter.dump()),
+            ])
+        }
+        ObjectValue::CuckooFilter(filter) => {
+            DataType::Array(vec![
+                DataType::BulkString(String::from("CF.LOADCHUNK")),
+                DataType::BulkString(key.to_string()),
+                DataType::BulkString(String::from("1")),
+                DataType::BulkString(filter.dump()),
+            ])
+        }
+        ObjectValue::CountMinSketch(sketch) => {
+            DataType::Array(vec![
+                DataType::BulkString(String::from("CMS.LOADCHUNK")),
+                DataType::BulkString(key.to_string()),
+                DataType::BulkString(String::from("1")),
+                DataType::BulkString(sketch.dump()),
+            ])
+        }
+        ObjectValue::TopK(top_k) => {
+            DataType::Array(vec![
+                DataType::BulkString(String::from("TOPK.LOADCHUNK")),
+                DataType::BulkString(key.to_string()),
+                DataType::BulkString(String::from("1")),
+                DataType::BulkString(top_k.dump()),
+            ])
+        }
+        ObjectValue::TimeSeries(series) => {
+            return get_time_series_rewrite_commands(key, series);
+        }
+        ObjectValue::Hash(hash) => {
+            let mut command = vec![
+                DataType::BulkString(String::from("HSET")),
+                DataType::BulkString(key.to_string()),
+            ];
+            for (field, value) in hash.iter() {
+                command.push(DataType::BulkString(field.clone()));
+                command.push(DataType::BulkString(value.clone()));
+            }
+            DataType::Array(command)
+        }
+        _ => {
+            DataType::Array(vec![
                 DataType::BulkString(String::from("SET")),
                 DataType::BulkString(key.to_string()),
                 store_object_to_datatype(value),
-            ]);
-            let encoded = parser.encode(command);
-            aof_file.write_all(encoded.as_bytes()).expect("Can not write to AOF file");
+            ])
         }
+    };
 
-        return DataType::SimpleString("OK".to_string());
+    vec![command]
+}
+
+/// The series is created with TS.CREATE and the samples are added with TS.MADD in batches
+fn get_time_series_rewrite_commands(key: &str, series: &TimeSeries) -> Vec<DataType> {
+    let mut create_command = vec![
+        DataType::BulkString(String::from("TS.CREATE")),
+        DataType::BulkString(key.to_string()),
+        DataType::BulkString(String::from("RETENTION")),
+        DataType::BulkString(series.retention_ms.to_string()),
+        DataType::BulkString(String::from("DUPLICATE_POLICY")),
+        DataType::BulkString(series.duplicate_policy.name().to_string()),
+    ];
+    if !series.labels.is_empty() {
+        create_command.push(DataType::BulkString(String::from("LABELS")));
+        for (label, value) in series.labels.iter() {
+            create_command.push(DataType::BulkString(label.clone()));
+            create_command.push(DataType::BulkString(value.clone()));
+        }
+    }
+
+    let mut commands = vec![DataType::Array(create_command)];
+    let samples: Vec<(&i64, &f64)> = series.range(i64::MIN..=i64::MAX).collect();
+    for batch in samples.chunks(AOF_TIME_SERIES_BATCH_SIZE) {
+        let mut add_command = vec![DataType::BulkString(String::from("TS.MADD"))];
+        for (timestamp, value) in batch {
+            add_command.push(DataType::BulkString(key.to_string()));
+            add_command.push(DataType::BulkString(timestamp.to_string()));
+            add_command.push(DataType::BulkString(value.to_string()));
+        }
+        commands.push(DataType::Array(add_command));
+    }
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use super::*;
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+
+    /// Rewrites the AOF of the store and replays it into a new store
+    fn rewrite_and_load(handler: &mut CommandHandler, store: &mut Store) -> Store {
+        let mut aof = Vec::new();
+        write_aof(store, &mut aof);
+        let mut loaded_store = Store::new(1);
+        let commands = RESPParser::new().decode_next_bulk(&mut BufReader::new(aof.as_slice())).unwrap();
+        for command in commands {
+            assert!(!matches!(handler.handle_simple_command_request(command, &mut loaded_store), DataType::Error(_)));
+        }
+        loaded_store
+    }
+
+    #[test]
+    fn test_rewrite_and_load_geo_key() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "GEOADD places 13.361389 38.115556 Palermo 15.087269 37.502669 Catania");
+        let positions = execute(&mut handler, &mut store, "GEOPOS places Palermo Catania");
+
+        // when
+        let mut loaded_store = rewrite_and_load(&mut handler, &mut store);
+
+        // then
+        assert_eq!(execute(&mut handler, &mut loaded_store, "GEOPOS places Palermo Catania"), positions);
+        assert_eq!(execute(&mut handler, &mut loaded_store, "TYPE places"), DataType::SimpleString(String::from("zset")));
+    }
+
+    #[test]
+    fn test_rewrite_and_load_expirations() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET volatile value PX 100000");
+        execute(&mut handler, &mut store, "SET persistent value");
+        execute(&mut handler, &mut store, "SET expired value PX 1");
+        let expires_at = store.get_expiry("volatile");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        // when
+        let mut loaded_store = rewrite_and_load(&mut handler, &mut store);
+
+        // then
+        assert_eq!(loaded_store.get_expiry("volatile"), expires_at);
+        assert!(matches!(execute(&mut handler, &mut loaded_store, "PTTL volatile"), DataType::Integer(ttl) if ttl > 0 && ttl <= 100000));
+        assert_eq!(execute(&mut handler, &mut loaded_store, "PTTL persistent"), DataType::Integer(-1));
+        assert_eq!(execute(&mut handler, &mut loaded_store, "EXISTS expired"), DataType::Integer(0));
+        assert_eq!(loaded_store.get_data().len(), 2);
     }
 }
@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::get_or_create_cuckoo_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cf.add/
+pub struct CfAddCommand;
+
+impl Command for CfAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let filter = match get_or_create_cuckoo_filter(store, &args[0]) {
+            Ok(filter) => filter,
+            Err(err) => return err,
+        };
+
+        match filter.add(&args[1]) {
+            Ok(_) => Integer(1),
+            Err(err) => Error(err),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_cuckoo_filter, NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cf.del/
+pub struct CfDelCommand;
+
+impl Command for CfDelCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_cuckoo_filter(store, &args[0]) {
+            Ok(Some(filter)) => Integer(filter.delete(&args[1]) as i64),
+            Ok(None) => Error(String::from(NOT_FOUND_ERROR)),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_cuckoo_filter, NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cf.info/
+pub struct CfInfoCommand;
+
+impl Command for CfInfoCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let filter = match get_cuckoo_filter(store, &args[0]) {
+            Ok(Some(filter)) => filter,
+            Ok(None) => return Error(String::from(NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let fields = [
+            ("Size", filter.get_size()),
+            ("Number of buckets", filter.get_number_of_buckets()),
+            ("Number of filters", filter.get_number_of_filters()),
+            ("Number of items inserted", filter.get_number_of_items()),
+            ("Number of items deleted", filter.get_number_of_deletes()),
+            ("Bucket size", filter.get_bucket_size()),
+            ("Expansion rate", filter.get_expansion()),
+            ("Max iterations", filter.get_max_iterations()),
+        ];
+
+        let mut response = Vec::new();
+        for (name, value) in fields {
+            response.push(SimpleString(name.to_string()));
+            response.push(Integer(value as i64));
+        }
+        Array(response)
+    }
+}
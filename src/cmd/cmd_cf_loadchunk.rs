@@ -0,0 +1,31 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_CUCKOO};
+use crate::probabilistic::cuckoo_filter::CuckooFilter;
+use crate::probabilistic::probabilistic_helper::get_cuckoo_filter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/cf.loadchunk/
+/// Restores a filter dumped with CF.SCANDUMP. Also used to persist cuckoo filters in the AOF file
+pub struct CfLoadChunkCommand;
+
+impl Command for CfLoadChunkCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args[1].parse::<i64>().is_err() {
+            return Error(String::from("ERR invalid iterator"));
+        }
+
+        if let Err(err) = get_cuckoo_filter(store, &args[0]) {
+            return err;
+        }
+
+        let filter = match CuckooFilter::restore(&args[2]) {
+            Ok(filter) => filter,
+            Err(_) => return Error(String::from("ERR received bad data")),
+        };
+
+        store.put(&args[0], ObjectValue::CuckooFilter(Box::new(filter)), -1, OBJ_TYPE_CUCKOO | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
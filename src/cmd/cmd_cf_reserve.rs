@@ -0,0 +1,61 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_CUCKOO};
+use crate::probabilistic::cuckoo_filter::{CuckooFilter, DEFAULT_BUCKET_SIZE, DEFAULT_EXPANSION, DEFAULT_MAX_ITERATIONS};
+use crate::probabilistic::probabilistic_helper::{get_cuckoo_filter, parse_integer, ITEM_EXISTS_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/cf.reserve/
+pub struct CfReserveCommand;
+
+impl Command for CfReserveCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 2 || args.len() % 2 != 0 {
+            return Error(String::from("ERR wrong number of arguments for 'cf.reserve' command"));
+        }
+
+        let capacity = match parse_integer(&args[1], "ERR Bad capacity") {
+            Ok(capacity) if capacity > 0 => capacity,
+            Ok(_) => return Error(String::from("ERR Bad capacity")),
+            Err(err) => return err,
+        };
+
+        let mut bucket_size = DEFAULT_BUCKET_SIZE;
+        let mut max_iterations = DEFAULT_MAX_ITERATIONS;
+        let mut expansion = DEFAULT_EXPANSION;
+        for option in args[2..].chunks(2) {
+            let result = match option[0].to_uppercase().as_str() {
+                "BUCKETSIZE" => parse_integer(&option[1], "ERR Bad bucket size").map(|value| bucket_size = value),
+                "MAXITERATIONS" => parse_integer(&option[1], "ERR Bad max iterations").map(|value| max_iterations = value),
+                "EXPANSION" => parse_integer(&option[1], "ERR Bad expansion").map(|value| expansion = value),
+                _ => return Error(String::from("ERR syntax error")),
+            };
+            if let Err(err) = result {
+                return err;
+            }
+        }
+
+        // fingerprints are stored in single bytes per slot, so the bucket size is limited like in redis
+        if bucket_size == 0 || bucket_size > 255 {
+            return Error(String::from("ERR Bad bucket size"));
+        }
+        if max_iterations == 0 || max_iterations > 65535 {
+            return Error(String::from("ERR Bad max iterations"));
+        }
+        if expansion > 32768 {
+            return Error(String::from("ERR Bad expansion"));
+        }
+
+        match get_cuckoo_filter(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(ITEM_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        let filter = CuckooFilter::new(capacity, bucket_size, max_iterations, expansion);
+        store.put(&args[0], ObjectValue::CuckooFilter(Box::new(filter)), -1, OBJ_TYPE_CUCKOO | OBJ_ENCODING_RAW);
+
+        SimpleString(String::from("OK"))
+    }
+}
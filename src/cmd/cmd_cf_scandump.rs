@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_cuckoo_filter, scan_dump_response, NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::Store;
+
+/// see https://redis.io/commands/cf.scandump/
+pub struct CfScanDumpCommand;
+
+impl Command for CfScanDumpCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_cuckoo_filter(store, &args[0]) {
+            Ok(Some(filter)) => scan_dump_response(&args[1], || filter.dump()),
+            Ok(None) => Error(String::from(NOT_FOUND_ERROR)),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::{Duration, Instant};
+use crate::client::{ClientInfo, PauseMode};
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error, Integer, Null, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/client/
+///
+/// CLIENT ID | GETNAME | SETNAME name | INFO | LIST [TYPE type] [ID id [id ...]]
+/// CLIENT KILL addr:port | KILL [ID id] [ADDR addr:port] [LADDR addr:port] [USER username] [SKIPME yes|no] [MAXAGE seconds]
+/// CLIENT PAUSE timeout [WRITE | ALL] | UNPAUSE | NO-EVICT ON|OFF
+///
+/// There are no ACL users, every client is authenticated as the default user.
+/// Killed clients are closed after the reply of their current request, see client
+pub struct ClientCommand;
+
+impl Command for ClientCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let subcommand = args[0].to_uppercase();
+        let wrong_number_of_arguments = || Error(format!("ERR wrong number of arguments for 'client|{}' command", subcommand.to_lowercase()));
+        match (subcommand.as_str(), args.len()) {
+            ("ID", 1) => match store.get_clients().get_current_client_id() {
+                Some(id) => Integer(id as i64),
+                None => Null,
+            },
+            ("GETNAME", 1) => match store.get_clients_mut().get_current_client_mut() {
+                Some(client) if !client.name.is_empty() => BulkString(client.name.clone()),
+                _ => Null,
+            },
+            ("SETNAME", 2) => set_name(&args[1], store),
+            ("INFO", 1) => match store.get_clients_mut().get_current_client_mut() {
+                Some(client) => BulkString(format!("{}\n", client.to_info_line())),
+                None => Null,
+            },
+            ("LIST", _) => list(&args[1..], store),
+            ("KILL", 2) => kill_by_address(&args[1], store),
+            ("KILL", _) if args.len() > 2 => kill(&args[1..], store),
+            ("PAUSE", 2 | 3) => pause(&args[1..], store),
+            ("UNPAUSE", 1) => {
+                store.get_clients_mut().unpause();
+                SimpleString(String::from("OK"))
+            }
+            ("NO-EVICT", 2) => match args[1].to_uppercase().as_str() {
+                mode @ ("ON" | "OFF") => {
+                    if let Some(client) = store.get_clients_mut().get_current_client_mut() {
+                        client.no_evict = mode == "ON";
+                    }
+                    SimpleString(String::from("OK"))
+                }
+                _ => Error(String::from("ERR syntax error")),
+            },
+            ("ID" | "GETNAME" | "SETNAME" | "INFO" | "KILL" | "PAUSE" | "UNPAUSE" | "NO-EVICT", _) => wrong_number_of_arguments(),
+            _ => Error(format!("ERR unknown subcommand '{}'. Try CLIENT HELP.", args[0])),
+        }
+    }
+}
+
+/// An empty name removes the name of the client
+fn set_name(name: &str, store: &mut Store) -> DataType {
+    // names are shown in CLIENT LIST, which separates fields by spaces and clients by newlines
+    if name.chars().any(|c| !('!'..='~').contains(&c)) {
+        return Error(String::from("ERR Client names cannot contain spaces, newlines or special characters."));
+    }
+
+    if let Some(client) = store.get_clients_mut().get_current_client_mut() {
+        client.name = name.to_string();
+    }
+    SimpleString(String::from("OK"))
+}
+
+fn list(args: &[String], store: &Store) -> DataType {
+    let mut ids = None;
+    let mut only_normal_clients = true;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].to_uppercase().as_str() {
+            "TYPE" if i + 1 < args.len() => {
+                // all clients are normal clients, there are no replicas or pub/sub clients
+                only_normal_clients = match args[i + 1].to_lowercase().as_str() {
+                    "normal" => true,
+                    "master" | "replica" | "slave" | "pubsub" => false,
+                    _ => return Error(format!("ERR Unknown client type '{}'", args[i + 1])),
+                };
+                i += 2;
+            }
+            "ID" if i + 1 < args.len() => {
+                let parsed_ids: Result<Vec<u64>, _> = args[i + 1..].iter().map(|id| id.parse::<u64>()).collect();
+                match parsed_ids {
+                    Ok(parsed_ids) if parsed_ids.iter().all(|id| *id > 0) => ids = Some(parsed_ids),
+                    _ => return Error(String::from("ERR Invalid client ID")),
+                }
+                i = args.len();
+            }
+            _ => return Error(String::from("ERR syntax error")),
+        }
+    }
+
+    let clients = store.get_clients().iter()
+        .filter(|client| only_normal_clients && ids.as_ref().is_none_or(|ids| ids.contains(&client.id)));
+    BulkString(clients.map(|client| format!("{}\n", client.to_info_line())).collect())
+}
+
+/// Old form of CLIENT KILL, which kills the client connected from the address
+fn kill_by_address(address: &str, store: &mut Store) -> DataType {
+    let id = store.get_clients().iter()
+        .find(|client| !client.killed && client.get_address() == address)
+        .map(|client| client.id);
+    match id {
+        Some(id) => {
+            store.get_clients_mut().kill(id);
+            SimpleString(String::from("OK"))
+        }
+        None => Error(String::from("ERR No such client")),
+    }
+}
+
+/// Filters of CLIENT KILL, a client is killed if it matches all filters
+struct KillFilter {
+    id: Option<u64>,
+    address: Option<String>,
+    local_address: Option<String>,
+    max_age: Option<Duration>,
+    skip_me: bool,
+}
+
+impl KillFilter {
+    fn parse(args: &[String]) -> Result<KillFilter, DataType> {
+        if !args.len().is_multiple_of(2) {
+            return Err(Error(String::from("ERR syntax error")));
+        }
+
+        let mut filter = KillFilter { id: None, address: None, local_address: None, max_age: None, skip_me: true };
+        for option in args.chunks(2) {
+            let value = &option[1];
+            match option[0].to_uppercase().as_str() {
+                "ID" => match value.parse::<u64>() {
+                    Ok(id) if id > 0 => filter.id = Some(id),
+                    _ => return Err(Error(String::from("ERR client-id should be greater than 0"))),
+                },
+                "ADDR" => filter.address = Some(value.clone()),
+                "LADDR" => filter.local_address = Some(value.clone()),
+                // every client is authenticated as the default user
+                "USER" if value == "default" => {}
+                "USER" => return Err(Error(format!("ERR No such user '{}'", value))),
+                "SKIPME" => match value.to_lowercase().as_str() {
+                    "yes" => filter.skip_me = true,
+                    "no" => filter.skip_me = false,
+                    _ => return Err(Error(String::from("ERR syntax error"))),
+                },
+                "MAXAGE" => match value.parse::<u64>() {
+                    Ok(max_age) => filter.max_age = Some(Duration::from_secs(max_age)),
+                    Err(_) => return Err(Error(String::from("ERR value is not an integer or out of range"))),
+                },
+                _ => return Err(Error(String::from("ERR syntax error"))),
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, client: &ClientInfo, current_client: Option<u64>, now: Instant) -> bool {
+        self.id.is_none_or(|id| client.id == id)
+            && self.address.as_ref().is_none_or(|address| client.get_address() == *address)
+            && self.local_address.as_ref().is_none_or(|address| client.get_local_address() == *address)
+            && self.max_age.is_none_or(|max_age| now.duration_since(client.created_at) >= max_age)
+            && !(self.skip_me && current_client == Some(client.id))
+    }
+}
+
+/// Returns the number of killed clients
+fn kill(args: &[String], store: &mut Store) -> DataType {
+    let filter = match KillFilter::parse(args) {
+        Ok(filter) => filter,
+        Err(err) => return err,
+    };
+
+    let now = Instant::now();
+    let current_client = store.get_clients().get_current_client_id();
+    let ids: Vec<u64> = store.get_clients().iter()
+        .filter(|client| filter.matches(client, current_client, now))
+        .map(|client| client.id)
+        .collect();
+    let killed = ids.into_iter().filter(|id| store.get_clients_mut().kill(*id)).count();
+    Integer(killed as i64)
+}
+
+/// Requests of all clients wait until the timeout (milliseconds) is over, with WRITE only write commands wait
+fn pause(args: &[String], store: &mut Store) -> DataType {
+    let timeout = match args[0].parse::<i64>() {
+        Ok(timeout) if timeout >= 0 => Duration::from_millis(timeout as u64),
+        Ok(_) => return Error(String::from("ERR timeout is negative")),
+        Err(_) => return Error(String::from("ERR timeout is not an integer or out of range")),
+    };
+    let mode = match args.get(1).map(|mode| mode.to_uppercase()) {
+        None => PauseMode::All,
+        Some(mode) if mode == "ALL" => PauseMode::All,
+        Some(mode) if mode == "WRITE" => PauseMode::Write,
+        Some(_) => return Error(String::from("ERR syntax error")),
+    };
+
+    store.get_clients_mut().pause(mode, timeout);
+    SimpleString(String::from("OK"))
+}
@@ -0,0 +1,39 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, parse_integer, CMS_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cms.incrby/
+pub struct CmsIncrByCommand;
+
+impl Command for CmsIncrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() % 2 != 1 {
+            return Error(String::from("ERR wrong number of arguments for 'cms.incrby' command"));
+        }
+
+        let mut increments = Vec::new();
+        for pair in args[1..].chunks(2) {
+            match parse_integer(&pair[1], "ERR CMS: Cannot parse number") {
+                Ok(increment) => increments.push((pair[0].as_str(), increment)),
+                Err(err) => return err,
+            }
+        }
+
+        let sketch = match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(sketch)) => sketch,
+            Ok(None) => return Error(String::from(CMS_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let mut results = Vec::new();
+        for (item, increment) in increments {
+            match sketch.increment_by(item, increment) {
+                Ok(count) => results.push(Integer(count as i64)),
+                Err(err) => results.push(Error(err)),
+            }
+        }
+        Array(results)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, CMS_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cms.info/
+pub struct CmsInfoCommand;
+
+impl Command for CmsInfoCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(sketch)) => Array(vec![
+                SimpleString(String::from("width")),
+                Integer(sketch.get_width() as i64),
+                SimpleString(String::from("depth")),
+                Integer(sketch.get_depth() as i64),
+                SimpleString(String::from("count")),
+                Integer(sketch.get_count() as i64),
+            ]),
+            Ok(None) => Error(String::from(CMS_KEY_NOT_FOUND_ERROR)),
+            Err(err) => err,
+        }
+    }
+}
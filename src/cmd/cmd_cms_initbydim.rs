@@ -0,0 +1,37 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_CMS};
+use crate::probabilistic::count_min_sketch::CountMinSketch;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, parse_integer, CMS_KEY_EXISTS_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/cms.initbydim/
+pub struct CmsInitByDimCommand;
+
+impl Command for CmsInitByDimCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let width = match parse_integer(&args[1], "ERR CMS: invalid width") {
+            Ok(width) if width > 0 => width,
+            Ok(_) => return Error(String::from("ERR CMS: invalid width")),
+            Err(err) => return err,
+        };
+        let depth = match parse_integer(&args[2], "ERR CMS: invalid depth") {
+            Ok(depth) if depth > 0 => depth,
+            Ok(_) => return Error(String::from("ERR CMS: invalid depth")),
+            Err(err) => return err,
+        };
+        if width.checked_mul(depth).is_none() {
+            return Error(String::from("ERR CMS: invalid width/depth"));
+        }
+
+        match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(CMS_KEY_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        store.put(&args[0], ObjectValue::CountMinSketch(CountMinSketch::new(width, depth)), -1, OBJ_TYPE_CMS | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_CMS};
+use crate::probabilistic::count_min_sketch::CountMinSketch;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, CMS_KEY_EXISTS_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/cms.initbyprob/
+pub struct CmsInitByProbCommand;
+
+impl Command for CmsInitByProbCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let error = match args[1].parse::<f64>() {
+            Ok(error) if error > 0.0 && error < 1.0 => error,
+            _ => return Error(String::from("ERR CMS: invalid overestimation value")),
+        };
+        let probability = match args[2].parse::<f64>() {
+            Ok(probability) if probability > 0.0 && probability < 1.0 => probability,
+            _ => return Error(String::from("ERR CMS: invalid prob value")),
+        };
+
+        match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(CMS_KEY_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        let sketch = CountMinSketch::new_by_probability(error, probability);
+        store.put(&args[0], ObjectValue::CountMinSketch(sketch), -1, OBJ_TYPE_CMS | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_CMS};
+use crate::probabilistic::count_min_sketch::CountMinSketch;
+use crate::probabilistic::probabilistic_helper::get_count_min_sketch;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// Restores a count-min sketch persisted in the AOF file.
+/// Redis only persists sketches in RDB files, so there is no redis counterpart of this command (the arguments follow BF.LOADCHUNK)
+pub struct CmsLoadChunkCommand;
+
+impl Command for CmsLoadChunkCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args[1].parse::<i64>().is_err() {
+            return Error(String::from("ERR invalid iterator"));
+        }
+
+        if let Err(err) = get_count_min_sketch(store, &args[0]) {
+            return err;
+        }
+
+        let filter = match CountMinSketch::restore(&args[2]) {
+            Ok(filter) => filter,
+            Err(_) => return Error(String::from("ERR received bad data")),
+        };
+
+        store.put(&args[0], ObjectValue::CountMinSketch(filter), -1, OBJ_TYPE_CMS | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
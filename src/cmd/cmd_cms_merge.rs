@@ -0,0 +1,60 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, parse_integer, CMS_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cms.merge/
+pub struct CmsMergeCommand;
+
+impl Command for CmsMergeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let num_keys = match parse_integer(&args[1], "ERR CMS: invalid numkeys") {
+            Ok(num_keys) if num_keys > 0 => num_keys as usize,
+            Ok(_) => return Error(String::from("ERR CMS: invalid numkeys")),
+            Err(err) => return err,
+        };
+
+        let source_keys = match args.get(2..2 + num_keys) {
+            Some(keys) => keys.to_vec(),
+            None => return Error(String::from("ERR CMS: wrong number of keys")),
+        };
+
+        // WEIGHTS weight [weight ...], every source has a weight of 1 by default
+        let mut weights = vec![1; num_keys];
+        let options = &args[2 + num_keys..];
+        if !options.is_empty() {
+            if !options[0].eq_ignore_ascii_case("WEIGHTS") || options.len() != num_keys + 1 {
+                return Error(String::from("ERR CMS: wrong number of keys/weights"));
+            }
+            for (weight, value) in weights.iter_mut().zip(options[1..].iter()) {
+                match parse_integer(value, "ERR CMS: invalid weight value") {
+                    Ok(value) => *weight = value,
+                    Err(err) => return err,
+                }
+            }
+        }
+
+        // the destination can be one of the sources, so we work on copies of the sources
+        let mut sources = Vec::new();
+        for key in source_keys.iter() {
+            match get_count_min_sketch(store, key) {
+                Ok(Some(sketch)) => sources.push(sketch.clone()),
+                Ok(None) => return Error(String::from(CMS_KEY_NOT_FOUND_ERROR)),
+                Err(err) => return err,
+            }
+        }
+
+        let destination = match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(sketch)) => sketch,
+            Ok(None) => return Error(String::from(CMS_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let weighted_sources: Vec<_> = sources.iter().zip(weights).collect();
+        match destination.merge(&weighted_sources) {
+            Ok(_) => SimpleString(String::from("OK")),
+            Err(err) => Error(err),
+        }
+    }
+}
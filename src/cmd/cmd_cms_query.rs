@@ -0,0 +1,20 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_count_min_sketch, CMS_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/cms.query/
+pub struct CmsQueryCommand;
+
+impl Command for CmsQueryCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let sketch = match get_count_min_sketch(store, &args[0]) {
+            Ok(Some(sketch)) => sketch,
+            Ok(None) => return Error(String::from(CMS_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        Array(args[1..].iter().map(|item| Integer(sketch.query(item) as i64)).collect())
+    }
+}
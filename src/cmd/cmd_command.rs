@@ -0,0 +1,88 @@
+use crate::cmd::command::Command;
+use crate::cmd::command_table::{COMMAND_TABLE, CommandSpec, lookup_command};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer, Null, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/command/
+///
+/// COMMAND | COMMAND COUNT | COMMAND INFO [command ...] | COMMAND DOCS [command ...] | COMMAND GETKEYS command [arg ...]
+/// Replies are built from the command table, see command_table
+pub struct CommandCommand;
+
+impl Command for CommandCommand {
+    fn execute(&self, args: &mut Vec<String>, _: &mut Store) -> DataType {
+        let subcommand = match args.first() {
+            Some(subcommand) => subcommand.to_uppercase(),
+            None => return Array(COMMAND_TABLE.iter().map(to_command_info).collect()),
+        };
+
+        match subcommand.as_str() {
+            "COUNT" if args.len() == 1 => Integer(COMMAND_TABLE.len() as i64),
+            "INFO" if args.len() == 1 => Array(COMMAND_TABLE.iter().map(to_command_info).collect()),
+            "INFO" => Array(args[1..].iter()
+                .map(|name| lookup_command(name).map_or(Null, to_command_info))
+                .collect()),
+            "DOCS" if args.len() == 1 => Array(COMMAND_TABLE.iter().flat_map(to_command_docs).collect()),
+            // unknown commands are left out
+            "DOCS" => Array(args[1..].iter()
+                .filter_map(|name| lookup_command(name))
+                .flat_map(to_command_docs)
+                .collect()),
+            "GETKEYS" if args.len() > 1 => get_keys(&args[1..]),
+            "COUNT" | "GETKEYS" => Error(format!("ERR wrong number of arguments for 'command|{}' command", subcommand.to_lowercase())),
+            _ => Error(format!("ERR unknown subcommand '{}'. Try COMMAND HELP.", args[0])),
+        }
+    }
+}
+
+/// name, arity, flags, first key, last key, step, ACL categories, tips, key specs and subcommands
+fn to_command_info(spec: &CommandSpec) -> DataType {
+    let to_simple_strings = |values: Vec<&str>| Array(values.into_iter().map(|value| SimpleString(value.to_string())).collect());
+
+    Array(vec![
+        BulkString(spec.name.to_string()),
+        Integer(spec.arity as i64),
+        to_simple_strings(spec.get_flag_names()),
+        Integer(spec.first_key as i64),
+        Integer(spec.last_key as i64),
+        Integer(spec.step as i64),
+        to_simple_strings(spec.get_acl_categories()),
+        Array(Vec::new()),
+        Array(Vec::new()),
+        Array(Vec::new()),
+    ])
+}
+
+/// Name followed by the documentation, like the RESP2 reply of Redis
+fn to_command_docs(spec: &CommandSpec) -> Vec<DataType> {
+    vec![
+        BulkString(spec.name.to_string()),
+        Array(vec![
+            BulkString(String::from("summary")),
+            BulkString(spec.summary.to_string()),
+            BulkString(String::from("group")),
+            BulkString(spec.group.to_string()),
+        ]),
+    ]
+}
+
+/// Extracts the keys of a full command request, e.g. COMMAND GETKEYS MSET a 1 b 2 returns a and b
+fn get_keys(request: &[String]) -> DataType {
+    let spec = match lookup_command(&request[0]) {
+        Some(spec) => spec,
+        None => return Error(String::from("ERR Invalid command specified")),
+    };
+    if !spec.check_arity(request.len()) {
+        return Error(String::from("ERR Invalid number of arguments specified for command"));
+    }
+
+    let keys: Vec<DataType> = spec.get_key_positions(request)
+        .into_iter()
+        .map(|position| BulkString(request[position].clone()))
+        .collect();
+    if keys.is_empty() {
+        return Error(String::from("ERR The command has no key arguments"));
+    }
+    Array(keys)
+}
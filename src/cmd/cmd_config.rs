@@ -0,0 +1,104 @@
+use crate::cmd::command::Command;
+use crate::config::{parse_active_expire_effort, parse_bool, parse_memory};
+use crate::eviction::eviction::EvictionPolicy;
+use crate::eviction::lfu_eviction_strategy::LFUConfiguration;
+use crate::glob::glob_match;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/config-get/
+///
+/// CONFIG GET parameter [parameter ...] | SET parameter value [parameter value ...]
+/// Parameters are matched case-insensitive, CONFIG GET supports glob-style patterns
+pub struct ConfigCommand;
+
+const PARAMETERS: [&str; 8] = ["databases", "maxmemory", "maxmemory-policy", "maxmemory-samples", "lfu-log-factor", "lfu-decay-time", "active-expire-effort", "active-expire-index"];
+
+impl Command for ConfigCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match args[0].to_uppercase().as_str() {
+            "GET" if args.len() > 1 => config_get(&args[1..], store),
+            "SET" if args.len() > 1 && args.len() % 2 == 1 => config_set(&args[1..], store),
+            "GET" | "SET" => Error(format!("ERR wrong number of arguments for 'config|{}' command", args[0].to_lowercase())),
+            _ => Error(format!("ERR unknown subcommand '{}'. Try CONFIG HELP.", args[0])),
+        }
+    }
+}
+
+fn config_get(patterns: &[String], store: &Store) -> DataType {
+    let mut response = Vec::new();
+    for parameter in PARAMETERS.iter() {
+        if patterns.iter().any(|pattern| glob_match(&pattern.to_lowercase(), parameter)) {
+            response.push(BulkString(parameter.to_string()));
+            response.push(BulkString(get_parameter(parameter, store)));
+        }
+    }
+    Array(response)
+}
+
+/// Validates all values before applying them, so either all or none of the parameters are changed
+fn config_set(pairs: &[String], store: &mut Store) -> DataType {
+    let mut changes = Vec::new();
+    for pair in pairs.chunks(2) {
+        let parameter = pair[0].to_lowercase();
+        let change = match parameter.as_str() {
+            "databases" => Err(String::from("can't set immutable config")),
+            "maxmemory" => parse_memory(&pair[1]).map(ParameterChange::MaxMemory).ok_or(String::from("argument must be a memory value")),
+            "maxmemory-policy" => EvictionPolicy::parse(&pair[1]).map(ParameterChange::MaxMemoryPolicy).ok_or(String::from("argument(s) must be one of the following: \
+                noeviction, allkeys-lru, allkeys-lfu, allkeys-random, volatile-lru, volatile-lfu, volatile-random, volatile-ttl")),
+            "maxmemory-samples" => pair[1].parse::<usize>().ok().filter(|samples| *samples > 0).map(ParameterChange::MaxMemorySamples)
+                .ok_or(String::from("argument must be between 1 and 2147483647 inclusive")),
+            "lfu-log-factor" => pair[1].parse::<u32>().map(ParameterChange::LFULogFactor).map_err(|_| String::from("argument couldn't be parsed into an integer")),
+            "lfu-decay-time" => pair[1].parse::<u64>().map(ParameterChange::LFUDecayTime).map_err(|_| String::from("argument couldn't be parsed into an integer")),
+            "active-expire-effort" => parse_active_expire_effort(&pair[1]).map(ParameterChange::ActiveExpireEffort)
+                .ok_or(String::from("argument must be between 1 and 10 inclusive")),
+            "active-expire-index" => parse_bool(&pair[1]).map(ParameterChange::ActiveExpireIndex).ok_or(String::from("argument must be 'yes' or 'no'")),
+            _ => return Error(format!("ERR Unknown option or number of arguments for CONFIG SET - '{}'", pair[0])),
+        };
+
+        match change {
+            Ok(change) => changes.push(change),
+            Err(err) => return Error(format!("ERR CONFIG SET failed (possibly related to argument '{}') - {}", parameter, err)),
+        }
+    }
+
+    for change in changes {
+        match change {
+            ParameterChange::MaxMemory(maxmemory) => store.set_maxmemory(maxmemory),
+            ParameterChange::MaxMemoryPolicy(policy) => store.set_eviction_policy(policy),
+            ParameterChange::MaxMemorySamples(samples) => store.set_maxmemory_samples(samples),
+            ParameterChange::LFULogFactor(log_factor) => store.set_lfu_configuration(LFUConfiguration { log_factor, ..store.get_lfu_configuration() }),
+            ParameterChange::LFUDecayTime(decay_time) => store.set_lfu_configuration(LFUConfiguration { decay_time, ..store.get_lfu_configuration() }),
+            ParameterChange::ActiveExpireEffort(effort) => store.set_active_expire_effort(effort),
+            // rebuilding the index is skipped if the value does not change
+            ParameterChange::ActiveExpireIndex(enabled) if enabled == store.has_expiry_index() => {}
+            ParameterChange::ActiveExpireIndex(enabled) => store.set_expiry_index(enabled),
+        }
+    }
+    SimpleString(String::from("OK"))
+}
+
+enum ParameterChange {
+    MaxMemory(u64),
+    MaxMemoryPolicy(EvictionPolicy),
+    MaxMemorySamples(usize),
+    LFULogFactor(u32),
+    LFUDecayTime(u64),
+    ActiveExpireEffort(u32),
+    ActiveExpireIndex(bool),
+}
+
+fn get_parameter(parameter: &str, store: &Store) -> String {
+    match parameter {
+        "databases" => store.get_db_count().to_string(),
+        "maxmemory" => store.get_maxmemory().to_string(),
+        "maxmemory-policy" => store.get_eviction_policy().get_name().to_string(),
+        "maxmemory-samples" => store.get_maxmemory_samples().to_string(),
+        "lfu-log-factor" => store.get_lfu_configuration().log_factor.to_string(),
+        "lfu-decay-time" => store.get_lfu_configuration().decay_time.to_string(),
+        "active-expire-effort" => store.get_active_expire_effort().to_string(),
+        "active-expire-index" => String::from(if store.has_expiry_index() { "yes" } else { "no" }),
+        _ => String::new(),
+    }
+}
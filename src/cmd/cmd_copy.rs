@@ -0,0 +1,40 @@
+use crate::cmd::command::{parse_db_index, Command};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/copy/
+///
+/// COPY source destination [DB destination-db] [REPLACE]
+/// The expiration of the source key is copied as well
+pub struct CopyCommand;
+
+impl Command for CopyCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let mut db = store.get_selected_db();
+        let mut replace = false;
+
+        let mut i = 2;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "REPLACE" => {
+                    replace = true;
+                    i += 1;
+                }
+                "DB" if i + 1 < args.len() => {
+                    db = match parse_db_index(&args[i + 1]) {
+                        Ok(db) => db,
+                        Err(err) => return err,
+                    };
+                    i += 2;
+                }
+                _ => return Error(String::from("ERR syntax error")),
+            }
+        }
+
+        match store.copy(&args[0], &args[1], db, replace) {
+            Ok(copied) => Integer(copied as i64),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/dbsize/
+pub struct DbSizeCommand;
+
+impl Command for DbSizeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Integer(store.get_data().len() as i64)
+    }
+}
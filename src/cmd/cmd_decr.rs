@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::store::Store;
+use crate::string::string_helper::increment;
+
+/// see https://redis.io/commands/decr/
+pub struct DecrCommand;
+
+impl Command for DecrCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        increment(store, &args[0], -1)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::Store;
+use crate::string::string_helper::increment;
+
+/// see https://redis.io/commands/decrby/
+pub struct DecrByCommand;
+
+impl Command for DecrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match args[1].parse::<i64>() {
+            // the minimum can not be negated
+            Ok(i64::MIN) => Error(String::from("ERR decrement would overflow")),
+            Ok(value) => increment(store, &args[0], -value),
+            Err(_) => Error(String::from("ERR value is not an integer or out of range")),
+        }
+    }
+}
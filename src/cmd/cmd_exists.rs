@@ -0,0 +1,15 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/exists/
+///
+/// Returns the number of existing keys, keys given multiple times are counted multiple times
+pub struct ExistsCommand;
+
+impl Command for ExistsCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Integer(args.iter().filter(|key| store.contains_key(key)).count() as i64)
+    }
+}
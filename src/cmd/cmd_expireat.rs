@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::expire::expire_helper::{expire, TimeUnit};
+use crate::resp::DataType;
+use crate::store::Store;
+
+/// see https://redis.io/commands/expireat/
+pub struct ExpireAtCommand;
+
+impl Command for ExpireAtCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        expire(args, store, TimeUnit::Seconds, true, "expireat")
+    }
+}
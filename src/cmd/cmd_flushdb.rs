@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/flushdb/
+///
+/// Removes all keys and search indexes of the selected database. ASYNC and SYNC are accepted, the database is always flushed synchronously
+pub struct FlushDbCommand;
+
+impl Command for FlushDbCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match args.first().map(|mode| mode.to_uppercase()) {
+            None => {}
+            Some(mode) if args.len() == 1 && (mode == "ASYNC" || mode == "SYNC") => {}
+            Some(_) => return Error(String::from("ERR syntax error")),
+        }
+
+        store.flush_db();
+        SimpleString(String::from("OK"))
+    }
+}
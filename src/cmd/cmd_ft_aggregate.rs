@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::search::aggregation::{AggregatePlan, Row};
+use crate::search::query::Query;
+use crate::store::Store;
+
+/// see https://redis.io/commands/ft.aggregate/
+///
+/// Runs the query and passes the matching documents through the LOAD, GROUPBY, SORTBY and LIMIT steps.
+/// Returns the number of resulting rows followed by the fields of every row
+pub struct FtAggregateCommand;
+
+impl Command for FtAggregateCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let plan = match AggregatePlan::parse(&args[2..]) {
+            Ok(plan) => plan,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+
+        let query = match Query::parse(&args[1], &HashMap::new()) {
+            Ok(query) => query,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+
+        let results = match store.search(&args[0], &query) {
+            Ok(results) => results,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+
+        let index = match store.find_search_index(&args[0]) {
+            Some(index) => index,
+            None => return Error(format!("ERR {}: no such index", args[0])),
+        };
+        let data = store.get_data();
+        let document_fields = plan.get_document_fields();
+
+        let rows: Vec<Row> = results.iter()
+            .filter_map(|result| data.get(&result.key))
+            .map(|store_object| Row {
+                values: document_fields.iter()
+                    .filter_map(|field| index.get_field_value(store_object.value.as_ref(), field).map(|value| (field.clone(), value)))
+                    .collect(),
+                fields: plan.load.clone(),
+            })
+            .collect();
+
+        let rows = plan.execute(rows);
+
+        let mut response = vec![Integer(rows.len() as i64)];
+        for row in rows.iter() {
+            response.push(Array(row.to_pairs().into_iter().flat_map(|(field, value)| vec![BulkString(field), BulkString(value)]).collect()));
+        }
+        Array(response)
+    }
+}
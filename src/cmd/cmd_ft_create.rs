@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::search::schema::IndexDefinition;
+use crate::store::Store;
+
+/// see https://redis.io/commands/ft.create/
+pub struct FtCreateCommand;
+
+impl Command for FtCreateCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let definition = match IndexDefinition::parse(args) {
+            Ok(definition) => definition,
+            Err(err) => return Error(err),
+        };
+
+        match store.create_search_index(definition) {
+            Ok(()) => SimpleString(String::from("OK")),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/ft.dropindex/
+///
+/// With DD the keys indexed by the index are deleted as well
+pub struct FtDropIndexCommand;
+
+impl Command for FtDropIndexCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'ft.dropindex' command"));
+        }
+
+        let delete_documents = match args.get(1) {
+            Some(option) if option.eq_ignore_ascii_case("DD") => true,
+            Some(_) => return Error(String::from("ERR syntax error")),
+            None => false,
+        };
+
+        match store.drop_search_index(&args[0], delete_documents) {
+            Ok(()) => SimpleString(String::from("OK")),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
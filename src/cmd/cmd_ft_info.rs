@@ -0,0 +1,95 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::search::schema::{FieldType, IndexTarget, VectorAlgorithm};
+use crate::search::vector::distance::DistanceMetric;
+use crate::store::Store;
+
+/// see https://redis.io/commands/ft.info/
+///
+/// Returns the definition, the attributes and the number of documents of the index
+pub struct FtInfoCommand;
+
+impl Command for FtInfoCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let index = match store.get_search_index(&args[0]) {
+            Ok(index) => index,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+        let definition = &index.definition;
+
+        let key_type = match definition.target {
+            IndexTarget::Hash => "HASH",
+            IndexTarget::Json => "JSON",
+        };
+
+        let attributes = definition.fields.iter().map(|field| {
+            let mut attribute = vec![
+                BulkString(String::from("identifier")),
+                BulkString(field.path.clone()),
+                BulkString(String::from("attribute")),
+                BulkString(field.name.clone()),
+                BulkString(String::from("type")),
+            ];
+
+            match &field.field_type {
+                FieldType::Text { weight, no_stem } => {
+                    attribute.push(BulkString(String::from("TEXT")));
+                    attribute.push(BulkString(String::from("WEIGHT")));
+                    attribute.push(BulkString(weight.to_string()));
+                    if *no_stem {
+                        attribute.push(BulkString(String::from("NOSTEM")));
+                    }
+                }
+                FieldType::Numeric => attribute.push(BulkString(String::from("NUMERIC"))),
+                FieldType::Tag { separator, case_sensitive } => {
+                    attribute.push(BulkString(String::from("TAG")));
+                    attribute.push(BulkString(String::from("SEPARATOR")));
+                    attribute.push(BulkString(separator.to_string()));
+                    if *case_sensitive {
+                        attribute.push(BulkString(String::from("CASESENSITIVE")));
+                    }
+                }
+                FieldType::Vector(options) => {
+                    let algorithm = match options.algorithm {
+                        VectorAlgorithm::Flat => "FLAT",
+                        VectorAlgorithm::Hnsw { .. } => "HNSW",
+                    };
+                    let metric = match options.metric {
+                        DistanceMetric::L2 => "L2",
+                        DistanceMetric::InnerProduct => "IP",
+                        DistanceMetric::Cosine => "COSINE",
+                    };
+                    attribute.extend(vec![
+                        BulkString(String::from("VECTOR")),
+                        BulkString(String::from("algorithm")),
+                        BulkString(algorithm.to_string()),
+                        BulkString(String::from("data_type")),
+                        BulkString(String::from("FLOAT32")),
+                        BulkString(String::from("dim")),
+                        Integer(options.dim as i64),
+                        BulkString(String::from("distance_metric")),
+                        BulkString(metric.to_string()),
+                    ]);
+                }
+            }
+            Array(attribute)
+        }).collect();
+
+        Array(vec![
+            BulkString(String::from("index_name")),
+            BulkString(definition.name.clone()),
+            BulkString(String::from("index_definition")),
+            Array(vec![
+                BulkString(String::from("key_type")),
+                BulkString(key_type.to_string()),
+                BulkString(String::from("prefixes")),
+                Array(definition.prefixes.iter().map(|prefix| BulkString(prefix.clone())).collect()),
+            ]),
+            BulkString(String::from("attributes")),
+            Array(attributes),
+            BulkString(String::from("num_docs")),
+            Integer(index.num_docs() as i64),
+        ])
+    }
+}
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::search::query::Query;
+use crate::search::search_index::SearchResult;
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/ft.search/
+///
+/// Supported options: NOCONTENT, WITHSCORES, RETURN, SORTBY, LIMIT, PARAMS and DIALECT (only dialect 2 is implemented, other values are accepted).
+/// Results are ordered by their BM25 score, KNN queries by distance. The distance of KNN queries is returned
+/// as additional field (`__<field>_score` or the name given with AS)
+pub struct FtSearchCommand;
+
+const DEFAULT_LIMIT: usize = 10;
+
+impl Command for FtSearchCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let mut no_content = false;
+        let mut with_scores = false;
+        let mut sort_by: Option<(String, bool)> = None;
+        let mut return_fields: Option<Vec<String>> = None;
+        let mut offset = 0;
+        let mut limit = DEFAULT_LIMIT;
+        let mut params = HashMap::new();
+
+        let mut i = 2;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "NOCONTENT" => {
+                    no_content = true;
+                    i += 1;
+                }
+                "WITHSCORES" => {
+                    with_scores = true;
+                    i += 1;
+                }
+                "SORTBY" if i + 1 < args.len() => {
+                    let field = args[i + 1].strip_prefix('@').unwrap_or(&args[i + 1]).to_string();
+                    match args.get(i + 2).map(|order| order.to_uppercase()) {
+                        Some(order) if order == "ASC" || order == "DESC" => {
+                            sort_by = Some((field, order == "ASC"));
+                            i += 3;
+                        }
+                        _ => {
+                            sort_by = Some((field, true));
+                            i += 2;
+                        }
+                    }
+                }
+                "RETURN" | "PARAMS" if i + 1 < args.len() => {
+                    let count = match args[i + 1].parse::<usize>() {
+                        Ok(count) if i + 2 + count <= args.len() => count,
+                        _ => return Error(format!("ERR Bad arguments for {}", args[i].to_uppercase())),
+                    };
+                    let values = &args[i + 2..i + 2 + count];
+
+                    if args[i].eq_ignore_ascii_case("RETURN") {
+                        return_fields = Some(values.to_vec());
+                    } else {
+                        if !count.is_multiple_of(2) {
+                            return Error(String::from("ERR Bad arguments for PARAMS: Parameters must be name value pairs"));
+                        }
+                        params = values.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect();
+                    }
+                    i += 2 + count;
+                }
+                "LIMIT" if i + 2 < args.len() => {
+                    match (args[i + 1].parse::<usize>(), args[i + 2].parse::<usize>()) {
+                        (Ok(first), Ok(count)) => {
+                            offset = first;
+                            limit = count;
+                        }
+                        _ => return Error(String::from("ERR Bad arguments for LIMIT")),
+                    }
+                    i += 3;
+                }
+                "DIALECT" if i + 1 < args.len() => {
+                    if args[i + 1].parse::<u8>().is_err() {
+                        return Error(String::from("ERR Bad arguments for DIALECT"));
+                    }
+                    i += 2;
+                }
+                _ => return Error(format!("ERR Unknown argument `{}`", args[i])),
+            }
+        }
+
+        let query = match Query::parse(&args[1], &params) {
+            Ok(query) => query,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+
+        let mut results = match store.search(&args[0], &query) {
+            Ok(results) => results,
+            Err(err) => return Error(format!("ERR {}", err)),
+        };
+
+        let index = match store.find_search_index(&args[0]) {
+            Some(index) => index,
+            None => return Error(format!("ERR {}: no such index", args[0])),
+        };
+        let data = store.get_data();
+        let score_field = query.knn.as_ref().map(|knn| knn.score_field.as_str());
+
+        // returns the KNN distance for the score field and the document value for all other fields
+        let get_value = |result: &SearchResult, name: &str| -> Option<String> {
+            match result.distance {
+                Some(distance) if score_field == Some(name) => Some(distance.to_string()),
+                _ => index.get_field_value(data.get(&result.key)?.value.as_ref(), name),
+            }
+        };
+
+        if let Some((field, ascending)) = &sort_by {
+            results.sort_by_cached_key(|result| SortValue(get_value(result, field)));
+            if !ascending {
+                results.reverse();
+            }
+        }
+
+        let mut response = vec![Integer(results.len() as i64)];
+        for result in results.iter().skip(offset).take(limit) {
+            response.push(BulkString(result.key.clone()));
+            if with_scores {
+                response.push(BulkString(result.score.to_string()));
+            }
+            if no_content {
+                continue;
+            }
+
+            let value = match data.get(&result.key) {
+                Some(store_object) => store_object.value.as_ref(),
+                None => continue,
+            };
+
+            let mut fields: Vec<(String, String)> = Vec::new();
+            match &return_fields {
+                Some(return_fields) => {
+                    for name in return_fields.iter() {
+                        if let Some(field_value) = get_value(result, name) {
+                            fields.push((name.clone(), field_value));
+                        }
+                    }
+                }
+                None => {
+                    if let (Some(score_field), Some(distance)) = (score_field, result.distance) {
+                        fields.push((score_field.to_string(), distance.to_string()));
+                    }
+                    match value {
+                        ObjectValue::Hash(hash) => {
+                            let mut hash_fields: Vec<(String, String)> = hash.iter().map(|(field, value)| (field.clone(), value.clone())).collect();
+                            hash_fields.sort();
+                            fields.extend(hash_fields);
+                        }
+                        ObjectValue::Json(document) => fields.push((String::from("$"), document.to_json_string())),
+                        _ => {}
+                    }
+                }
+            }
+
+            response.push(Array(fields.into_iter().flat_map(|(field, value)| vec![BulkString(field), BulkString(value)]).collect()));
+        }
+
+        Array(response)
+    }
+}
+
+/// Sort key of SORTBY, numbers are compared numerically and documents without a value are sorted last
+#[derive(PartialEq, Eq)]
+struct SortValue(Option<String>);
+
+impl PartialOrd for SortValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.total_cmp(&b),
+                _ => a.cmp(b),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
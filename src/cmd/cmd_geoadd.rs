@@ -0,0 +1,92 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::{get_sorted_set_mut, parse_coordinate};
+use crate::geo::geohash::encode_wgs84;
+use crate::object_type_encoding::{OBJ_ENCODING_SKIPLIST, OBJ_TYPE_ZSET};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::sorted_set::SortedSet;
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/geoadd/
+pub struct GeoAddCommand;
+
+impl Command for GeoAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let key = args[0].clone();
+        let mut only_new = false;
+        let mut only_existing = false;
+        let mut count_changed = false;
+
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "NX" => only_new = true,
+                "XX" => only_existing = true,
+                "CH" => count_changed = true,
+                _ => break,
+            }
+            i += 1;
+        }
+
+        if only_new && only_existing {
+            return Error(String::from("ERR XX and NX options at the same time are not compatible"));
+        }
+
+        let elements = &args[i..];
+        if elements.is_empty() || elements.len() % 3 != 0 {
+            return Error(String::from("ERR syntax error. Try GEOADD key [x1] [y1] [name1] [x2] [y2] [name2] ... "));
+        }
+
+        // validate all coordinates first, so we do not add only a part of the members
+        let mut members = Vec::with_capacity(elements.len() / 3);
+        for element in elements.chunks(3) {
+            match parse_coordinate(&element[0], &element[1]) {
+                Ok((longitude, latitude)) => {
+                    members.push((element[2].clone(), encode_wgs84(longitude, latitude) as f64));
+                }
+                Err(err) => {
+                    return err;
+                }
+            }
+        }
+
+        match get_sorted_set_mut(store, &key) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                if only_existing {
+                    return Integer(0);
+                }
+                store.put(&key, ObjectValue::SortedSet(Box::new(SortedSet::new())), -1, OBJ_TYPE_ZSET | OBJ_ENCODING_SKIPLIST);
+            }
+            Err(err) => {
+                return err;
+            }
+        }
+        let sorted_set = get_sorted_set_mut(store, &key).unwrap().expect("Sorted set not found");
+
+        let mut added = 0;
+        let mut changed = 0;
+
+        for (member, score) in members {
+            let existing_score = sorted_set.score(&member);
+
+            if (only_new && existing_score.is_some()) || (only_existing && existing_score.is_none()) {
+                continue;
+            }
+
+            match existing_score {
+                None => added += 1,
+                Some(existing_score) if existing_score != score => changed += 1,
+                _ => {}
+            }
+
+            sorted_set.insert(&member, score);
+        }
+
+        if count_changed {
+            return Integer(added + changed);
+        }
+
+        Integer(added)
+    }
+}
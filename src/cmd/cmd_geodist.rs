@@ -0,0 +1,42 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::{format_distance, get_sorted_set, parse_unit};
+use crate::geo::geohash::{decode_wgs84, distance};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/geodist/
+pub struct GeoDistCommand;
+
+impl Command for GeoDistCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() > 4 {
+            return Error(String::from("ERR wrong number of arguments for 'geodist' command"));
+        }
+
+        let meters_per_unit = match args.get(3) {
+            Some(unit) => {
+                match parse_unit(unit) {
+                    Ok(meters_per_unit) => meters_per_unit,
+                    Err(err) => return err,
+                }
+            }
+            None => 1.0,
+        };
+
+        let sorted_set = match get_sorted_set(store, &args[0]) {
+            Ok(Some(sorted_set)) => sorted_set,
+            Ok(None) => return Null,
+            Err(err) => return err,
+        };
+
+        match (sorted_set.score(&args[1]), sorted_set.score(&args[2])) {
+            (Some(score1), Some(score2)) => {
+                let (lon1, lat1) = decode_wgs84(score1 as u64);
+                let (lon2, lat2) = decode_wgs84(score2 as u64);
+                format_distance(distance(lon1, lat1, lon2, lat2), meters_per_unit)
+            }
+            _ => Null,
+        }
+    }
+}
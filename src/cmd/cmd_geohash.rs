@@ -0,0 +1,33 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::get_sorted_set;
+use crate::geo::geohash::{decode_wgs84, to_geohash_string};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/geohash/
+pub struct GeoHashCommand;
+
+impl Command for GeoHashCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let sorted_set = match get_sorted_set(store, &args[0]) {
+            Ok(sorted_set) => sorted_set,
+            Err(err) => return err,
+        };
+
+        let mut result = Vec::with_capacity(args.len() - 1);
+        for member in args[1..].iter() {
+            match sorted_set.as_ref().and_then(|sorted_set| sorted_set.score(member)) {
+                Some(score) => {
+                    let (longitude, latitude) = decode_wgs84(score as u64);
+                    result.push(BulkString(to_geohash_string(longitude, latitude)));
+                }
+                None => {
+                    result.push(Null);
+                }
+            }
+        }
+
+        Array(result)
+    }
+}
@@ -0,0 +1,33 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::{format_coordinate, get_sorted_set};
+use crate::geo::geohash::decode_wgs84;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/geopos/
+pub struct GeoPosCommand;
+
+impl Command for GeoPosCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let sorted_set = match get_sorted_set(store, &args[0]) {
+            Ok(sorted_set) => sorted_set,
+            Err(err) => return err,
+        };
+
+        let mut result = Vec::with_capacity(args.len() - 1);
+        for member in args[1..].iter() {
+            match sorted_set.as_ref().and_then(|sorted_set| sorted_set.score(member)) {
+                Some(score) => {
+                    let (longitude, latitude) = decode_wgs84(score as u64);
+                    result.push(format_coordinate(longitude, latitude));
+                }
+                None => {
+                    result.push(Null);
+                }
+            }
+        }
+
+        Array(result)
+    }
+}
@@ -0,0 +1,216 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::{format_coordinate, format_distance, get_sorted_set, parse_coordinate, parse_float, parse_unit};
+use crate::geo::geohash::decode_wgs84;
+use crate::geo::search::{search, GeoSearchResult, GeoShape};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/geosearch/
+pub struct GeoSearchCommand;
+
+pub enum GeoSearchCenter {
+    Member(String),
+    Coordinate(f64, f64),
+}
+
+#[derive(PartialEq)]
+pub enum GeoSortOrder {
+    Asc,
+    Desc,
+}
+
+pub struct GeoSearchOptions {
+    pub center: GeoSearchCenter,
+    pub shape: GeoShape,
+    pub meters_per_unit: f64,
+    pub sort: Option<GeoSortOrder>,
+    pub count: Option<usize>,
+    pub any: bool,
+    pub with_coord: bool,
+    pub with_dist: bool,
+    pub with_hash: bool,
+    // only supported by GEOSEARCHSTORE
+    pub store_dist: bool,
+}
+
+impl Command for GeoSearchCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let options = match parse_search_options(&args[1..], false) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        let results = match find_members(store, &args[0], &options) {
+            Ok(results) => results,
+            Err(err) => return err,
+        };
+
+        let with_any = options.with_coord || options.with_dist || options.with_hash;
+
+        let mut response = Vec::with_capacity(results.len());
+        for result in results {
+            if !with_any {
+                response.push(BulkString(result.member));
+                continue;
+            }
+
+            let mut item = vec![BulkString(result.member)];
+            if options.with_dist {
+                item.push(format_distance(result.distance_meters, options.meters_per_unit));
+            }
+            if options.with_hash {
+                item.push(Integer(result.score as i64));
+            }
+            if options.with_coord {
+                item.push(format_coordinate(result.longitude, result.latitude));
+            }
+            response.push(Array(item));
+        }
+
+        Array(response)
+    }
+}
+
+/// Parses the options of GEOSEARCH and GEOSEARCHSTORE (all arguments after the key(s))
+pub fn parse_search_options(args: &[String], is_store: bool) -> Result<GeoSearchOptions, DataType> {
+    let mut center = None;
+    let mut shape = None;
+    let mut meters_per_unit = 1.0;
+    let mut sort = None;
+    let mut count = None;
+    let mut any = false;
+    let mut with_coord = false;
+    let mut with_dist = false;
+    let mut with_hash = false;
+    let mut store_dist = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        let remaining = args.len() - i - 1;
+
+        match args[i].to_uppercase().as_str() {
+            "FROMMEMBER" if remaining >= 1 => {
+                if center.is_some() {
+                    return Err(Error(String::from("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH")));
+                }
+                center = Some(GeoSearchCenter::Member(args[i + 1].clone()));
+                i += 1;
+            }
+            "FROMLONLAT" if remaining >= 2 => {
+                if center.is_some() {
+                    return Err(Error(String::from("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH")));
+                }
+                let (longitude, latitude) = parse_coordinate(&args[i + 1], &args[i + 2])?;
+                center = Some(GeoSearchCenter::Coordinate(longitude, latitude));
+                i += 2;
+            }
+            "BYRADIUS" if remaining >= 2 => {
+                if shape.is_some() {
+                    return Err(Error(String::from("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH")));
+                }
+                let radius = parse_float(&args[i + 1])?;
+                if radius < 0.0 {
+                    return Err(Error(String::from("ERR radius cannot be negative")));
+                }
+                meters_per_unit = parse_unit(&args[i + 2])?;
+                shape = Some(GeoShape::Radius { radius_meters: radius * meters_per_unit });
+                i += 2;
+            }
+            "BYBOX" if remaining >= 3 => {
+                if shape.is_some() {
+                    return Err(Error(String::from("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH")));
+                }
+                let width = parse_float(&args[i + 1])?;
+                let height = parse_float(&args[i + 2])?;
+                if width < 0.0 || height < 0.0 {
+                    return Err(Error(String::from("ERR height or width cannot be negative")));
+                }
+                meters_per_unit = parse_unit(&args[i + 3])?;
+                shape = Some(GeoShape::Box { width_meters: width * meters_per_unit, height_meters: height * meters_per_unit });
+                i += 3;
+            }
+            "ASC" => sort = Some(GeoSortOrder::Asc),
+            "DESC" => sort = Some(GeoSortOrder::Desc),
+            "COUNT" if remaining >= 1 => {
+                match args[i + 1].parse::<i64>() {
+                    Ok(value) if value > 0 => count = Some(value as usize),
+                    Ok(_) => return Err(Error(String::from("ERR COUNT must be > 0"))),
+                    Err(_) => return Err(Error(String::from("ERR value is not an integer or out of range"))),
+                }
+                i += 1;
+                if i + 1 < args.len() && args[i + 1].to_uppercase() == "ANY" {
+                    any = true;
+                    i += 1;
+                }
+            }
+            "WITHCOORD" if !is_store => with_coord = true,
+            "WITHDIST" if !is_store => with_dist = true,
+            "WITHHASH" if !is_store => with_hash = true,
+            "STOREDIST" if is_store => store_dist = true,
+            _ => return Err(Error(String::from("ERR syntax error"))),
+        }
+
+        i += 1;
+    }
+
+    let center = match center {
+        Some(center) => center,
+        None => return Err(Error(String::from("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH"))),
+    };
+    let shape = match shape {
+        Some(shape) => shape,
+        None => return Err(Error(String::from("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH"))),
+    };
+
+    // without ANY the closest matches are returned, so we need to sort them
+    if count.is_some() && !any && sort.is_none() {
+        sort = Some(GeoSortOrder::Asc);
+    }
+
+    Ok(GeoSearchOptions {
+        center,
+        shape,
+        meters_per_unit,
+        sort,
+        count,
+        any,
+        with_coord,
+        with_dist,
+        with_hash,
+        store_dist,
+    })
+}
+
+/// Searches the members of the sorted set stored at key which are located within the search area
+pub fn find_members(store: &mut Store, key: &str, options: &GeoSearchOptions) -> Result<Vec<GeoSearchResult>, DataType> {
+    let sorted_set = match get_sorted_set(store, key)? {
+        Some(sorted_set) => sorted_set,
+        None => return Ok(Vec::new()),
+    };
+
+    let (longitude, latitude) = match &options.center {
+        GeoSearchCenter::Member(member) => {
+            match sorted_set.score(member) {
+                Some(score) => decode_wgs84(score as u64),
+                None => return Err(Error(String::from("ERR could not decode requested zset member"))),
+            }
+        }
+        GeoSearchCenter::Coordinate(longitude, latitude) => (*longitude, *latitude),
+    };
+
+    let limit = if options.any { options.count } else { None };
+    let mut results = search(sorted_set, longitude, latitude, options.shape, limit);
+
+    match options.sort {
+        Some(GeoSortOrder::Asc) => results.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters)),
+        Some(GeoSortOrder::Desc) => results.sort_by(|a, b| b.distance_meters.total_cmp(&a.distance_meters)),
+        None => {}
+    }
+
+    if let Some(count) = options.count {
+        results.truncate(count);
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,47 @@
+use crate::cmd::cmd_geosearch::{find_members, parse_search_options};
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_SKIPLIST, OBJ_TYPE_ZSET};
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::sorted_set::SortedSet;
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/geosearchstore/
+pub struct GeoSearchStoreCommand;
+
+impl Command for GeoSearchStoreCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let destination = args[0].clone();
+        let source = args[1].clone();
+
+        let options = match parse_search_options(&args[2..], true) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        let results = match find_members(store, &source, &options) {
+            Ok(results) => results,
+            Err(err) => return err,
+        };
+
+        // the destination gets overwritten, regardless of the type it had before
+        store.remove(&destination);
+
+        if results.is_empty() {
+            return Integer(0);
+        }
+
+        let mut sorted_set = SortedSet::new();
+        for result in results.iter() {
+            let score = if options.store_dist {
+                result.distance_meters / options.meters_per_unit
+            } else {
+                result.score
+            };
+            sorted_set.insert(&result.member, score);
+        }
+
+        store.put(&destination, ObjectValue::SortedSet(Box::new(sorted_set)), -1, OBJ_TYPE_ZSET | OBJ_ENCODING_SKIPLIST);
+        Integer(results.len() as i64)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::cmd::command::Command;
+use crate::cmd::command::{Command, WRONG_TYPE_ERROR};
+use crate::object_type_encoding::{get_type, OBJ_TYPE_STRING};
 use crate::resp::DataType;
 use crate::resp::DataType::{Error};
 use crate::store::{Store, store_object_to_datatype};
@@ -12,6 +13,9 @@ impl Command for GetCommand {
 
         return match store.get(key.as_str()) {
             Some(store_object) => {
+                if get_type(store_object.type_encoding) != OBJ_TYPE_STRING {
+                    return Error(String::from(WRONG_TYPE_ERROR));
+                }
                 store_object_to_datatype(&store_object)
             }
             None => {
@@ -0,0 +1,20 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::store::Store;
+use crate::string::string_helper::{get_string, to_bulk_string};
+
+/// see https://redis.io/commands/getdel/
+pub struct GetDelCommand;
+
+impl Command for GetDelCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let value = match get_string(store, &args[0]) {
+            Ok(value) => value,
+            Err(err) => return err,
+        };
+        if value.is_some() {
+            store.remove(&args[0]);
+        }
+        to_bulk_string(value)
+    }
+}
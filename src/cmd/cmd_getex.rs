@@ -0,0 +1,49 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::Store;
+use crate::string::string_helper::{get_string, parse_expiration, to_bulk_string, Expiration};
+
+/// see https://redis.io/commands/getex/
+///
+/// GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | PERSIST]
+/// An expiration in the past deletes the key
+pub struct GetExCommand;
+
+impl Command for GetExCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let expiration = match args.len() {
+            1 => None,
+            2 if args[1].eq_ignore_ascii_case("PERSIST") => Some(Expiration::Persist),
+            3 => match parse_expiration(&args[1], args.get(2), "getex") {
+                Some(Ok(expires_at)) => Some(Expiration::At(expires_at)),
+                Some(Err(err)) => return err,
+                None => return Error(String::from("ERR syntax error")),
+            },
+            _ => return Error(String::from("ERR syntax error")),
+        };
+
+        let key = &args[0];
+        let value = match get_string(store, key) {
+            Ok(value) => value,
+            Err(err) => return err,
+        };
+        if value.is_none() {
+            return to_bulk_string(value);
+        }
+
+        match expiration {
+            Some(Expiration::At(expires_at)) if expires_at <= chrono::Utc::now().timestamp_millis() => {
+                store.remove(key);
+            }
+            Some(Expiration::At(expires_at)) => {
+                store.set_expiry(key, expires_at);
+            }
+            Some(Expiration::Persist) => {
+                store.persist(key);
+            }
+            Some(Expiration::KeepTTL) | None => {}
+        }
+        to_bulk_string(value)
+    }
+}
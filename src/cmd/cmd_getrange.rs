@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error};
+use crate::store::Store;
+use crate::string::string_helper::{get_range, get_string};
+
+/// see https://redis.io/commands/getrange/
+pub struct GetRangeCommand;
+
+impl Command for GetRangeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let (start, end) = match (args[1].parse::<i64>(), args[2].parse::<i64>()) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => return Error(String::from("ERR value is not an integer or out of range")),
+        };
+
+        match get_string(store, &args[0]) {
+            Ok(value) => BulkString(get_range(&value.unwrap_or_default(), start, end)),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::store::Store;
+use crate::string::string_helper::{get_string, put_string, to_bulk_string};
+
+/// see https://redis.io/commands/getset/
+///
+/// Like SET, the expiration of the key is removed
+pub struct GetSetCommand;
+
+impl Command for GetSetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let old_value = match get_string(store, &args[0]) {
+            Ok(old_value) => old_value,
+            Err(err) => return err,
+        };
+        put_string(store, &args[0], args[1].clone(), None);
+        to_bulk_string(old_value)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::cmd::command::Command;
+use crate::hash::hash_helper::get_hash;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/hdel/
+pub struct HDelCommand;
+
+impl Command for HDelCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let hash = match get_hash(store, &args[0]) {
+            Ok(Some(hash)) => hash,
+            Ok(None) => return Integer(0),
+            Err(err) => return err,
+        };
+
+        let removed = args[1..].iter().filter(|field| hash.remove(*field).is_some()).count();
+
+        // empty hashes are not kept
+        if hash.is_empty() {
+            store.remove(&args[0]);
+        }
+
+        Integer(removed as i64)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::cmd::command::Command;
+use crate::hash::hash_helper::get_hash;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/hget/
+pub struct HGetCommand;
+
+impl Command for HGetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_hash(store, &args[0]) {
+            Ok(Some(hash)) => match hash.get(&args[1]) {
+                Some(value) => BulkString(value.clone()),
+                None => Null,
+            },
+            Ok(None) => Null,
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::cmd::command::Command;
+use crate::hash::hash_helper::get_hash;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/hgetall/
+pub struct HGetAllCommand;
+
+impl Command for HGetAllCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_hash(store, &args[0]) {
+            Ok(Some(hash)) => {
+                let mut fields: Vec<(&String, &String)> = hash.iter().collect();
+                fields.sort();
+                Array(fields.into_iter().flat_map(|(field, value)| vec![BulkString(field.clone()), BulkString(value.clone())]).collect())
+            }
+            Ok(None) => Array(vec![]),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::cmd::command::Command;
+use crate::hash::hash_helper::get_or_create_hash;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/hset/
+pub struct HSetCommand;
+
+impl Command for HSetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len().is_multiple_of(2) {
+            return Error(String::from("ERR wrong number of arguments for 'hset' command"));
+        }
+
+        let hash = match get_or_create_hash(store, &args[0]) {
+            Ok(hash) => hash,
+            Err(err) => return err,
+        };
+
+        let mut added = 0;
+        for pair in args[1..].chunks(2) {
+            if hash.insert(pair[0].clone(), pair[1].clone()).is_none() {
+                added += 1;
+            }
+        }
+
+        Integer(added)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::Store;
+use crate::string::string_helper::increment;
+
+/// see https://redis.io/commands/incrby/
+pub struct IncrByCommand;
+
+impl Command for IncrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match args[1].parse::<i64>() {
+            Ok(value) => increment(store, &args[0], value),
+            Err(_) => Error(String::from("ERR value is not an integer or out of range")),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cmd::command::{Command, WRONG_TYPE_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error};
+use crate::store::{ObjectValue, Store};
+use crate::string::string_helper::put_string;
+
+/// see https://redis.io/commands/incrbyfloat/
+///
+/// The result is stored as string, integral results are stored as int (e.g. 10.5 + 0.5 = "11")
+pub struct IncrByFloatCommand;
+
+impl Command for IncrByFloatCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let not_a_float = || Error(String::from("ERR value is not a valid float"));
+        let increment = match parse_float(&args[1]) {
+            Some(increment) => increment,
+            None => return not_a_float(),
+        };
+
+        let key = &args[0];
+        let value = match store.get(key) {
+            Some(store_object) => match store_object.value.as_ref() {
+                ObjectValue::String(string) => match parse_float(&string.as_str()) {
+                    Some(value) => value,
+                    None => return not_a_float(),
+                },
+                _ => return Error(String::from(WRONG_TYPE_ERROR)),
+            },
+            None => 0.0,
+        };
+
+        let new_value = value + increment;
+        if !new_value.is_finite() {
+            return Error(String::from("ERR increment would produce NaN or Infinity"));
+        }
+
+        // like SET, but the expiration of the key is kept
+        let new_value = new_value.to_string();
+        let expires_at = store.get_expiry(key);
+        put_string(store, key, new_value.clone(), expires_at);
+        BulkString(new_value)
+    }
+}
+
+/// Parses a finite float, Rust accepts "inf" and "NaN" which Redis does not
+fn parse_float(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|value| value.is_finite())
+}
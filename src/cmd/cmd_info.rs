@@ -1,6 +1,7 @@
+use crate::allocator::{get_allocated_bytes, get_peak_allocated_bytes};
 use crate::cmd::command::Command;
 use crate::resp::DataType;
-use crate::stats::KEYSPACE_STATISTICS;
+use crate::stats::{EXPIRATION_STATISTICS, KEYSPACE_STATISTICS};
 use crate::store::Store;
 
 /// see https://redis.io/commands/info/
@@ -8,11 +9,38 @@ use crate::store::Store;
 pub struct InfoCommand;
 
 impl Command for InfoCommand {
-    fn execute(&self, _: &mut Vec<String>, _: &mut Store) -> DataType {
+    fn execute(&self, _: &mut Vec<String>, store: &mut Store) -> DataType {
         let mut response = String::new();
+        response.push_str("# Clients\r\n");
+        response.push_str(&format!("connected_clients:{}\r\n", store.get_clients().iter().count()));
+        response.push_str("\r\n");
+
+        response.push_str("# Memory\r\n");
+        // memory allocated by the server and the part of it used by keys and values
+        response.push_str(&format!("used_memory:{}\r\n", get_allocated_bytes()));
+        response.push_str(&format!("used_memory_peak:{}\r\n", get_peak_allocated_bytes()));
+        response.push_str(&format!("used_memory_dataset:{}\r\n", store.get_dataset_memory()));
+        // old hash tables which are still being moved to their resized table, see dict
+        response.push_str(&format!("mem_overhead_db_hashtable_rehashing:{}\r\n", store.get_rehashing_memory()));
+        response.push_str(&format!("db_dict_rehashing_count:{}\r\n", store.get_rehashing_dict_count()));
+        response.push_str(&format!("maxmemory:{}\r\n", store.get_maxmemory()));
+        response.push_str(&format!("maxmemory_policy:{}\r\n", store.get_eviction_policy().get_name()));
+        response.push_str("\r\n");
+
+        response.push_str("# Stats\r\n");
+        if let Ok(stats) = EXPIRATION_STATISTICS.lock() {
+            response.push_str(&format!("expired_keys:{}\r\n", stats.expired_keys));
+            response.push_str(&format!("expired_stale_perc:{:.2}\r\n", stats.expired_stale_perc * 100.0));
+        }
+        response.push_str("\r\n");
+
         response.push_str("# Keyspace\r\n");
 
+        // like redis, only databases containing keys are listed
         for (keyspace_id, keyspace_stats) in KEYSPACE_STATISTICS.lock().unwrap().iter().enumerate() {
+            if keyspace_stats.number_of_keys == 0 {
+                continue;
+            }
             response.push_str(&format!("db{}:keys={}\r\n", keyspace_id, keyspace_stats.number_of_keys));
         }
 
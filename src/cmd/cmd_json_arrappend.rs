@@ -0,0 +1,47 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, parse_value, to_response, KEY_NOT_FOUND_ERROR};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.arrappend/
+pub struct JsonArrAppendCommand;
+
+impl Command for JsonArrAppendCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let path = match parse_path(&args[1]) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let mut values = Vec::with_capacity(args.len() - 2);
+        for value in args[2..].iter() {
+            match parse_value(value) {
+                Ok(value) => values.push(value),
+                Err(err) => return err,
+            }
+        }
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "array", |value| {
+            match value {
+                JsonValue::Array(array) => {
+                    array.extend(values.iter().cloned());
+                    Ok(Some(array.len() as i64))
+                }
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => to_response(results, &path, Integer),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, parse_value, to_response, KEY_NOT_FOUND_ERROR};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.arrinsert/
+pub struct JsonArrInsertCommand;
+
+impl Command for JsonArrInsertCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let path = match parse_path(&args[1]) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let index = match args[2].parse::<i64>() {
+            Ok(index) => index,
+            Err(_) => return Error(String::from("ERR value is not an integer or out of range")),
+        };
+
+        let mut values = Vec::with_capacity(args.len() - 3);
+        for value in args[3..].iter() {
+            match parse_value(value) {
+                Ok(value) => values.push(value),
+                Err(err) => return err,
+            }
+        }
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "array", |value| {
+            match value {
+                JsonValue::Array(array) => {
+                    // negative indexes are relative to the end, the length itself is allowed to append the values
+                    let position = if index < 0 { array.len() as i64 + index } else { index };
+                    if position < 0 || position > array.len() as i64 {
+                        return Err(Error(String::from("ERR index out of bounds")));
+                    }
+
+                    let position = position as usize;
+                    array.splice(position..position, values.iter().cloned());
+                    Ok(Some(array.len() as i64))
+                }
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => to_response(results, &path, Integer),
+            Err(err) => err,
+        }
+    }
+}
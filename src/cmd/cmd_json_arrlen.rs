@@ -0,0 +1,40 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, to_response};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.arrlen/
+pub struct JsonArrLenCommand;
+
+impl Command for JsonArrLenCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'json.arrlen' command"));
+        }
+
+        let path = match parse_path(args.get(1).map(|path| path.as_str()).unwrap_or(".")) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Null,
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "array", |value| {
+            match value {
+                JsonValue::Array(array) => Ok(Some(array.len() as i64)),
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => to_response(results, &path, Integer),
+            Err(err) => err,
+        }
+    }
+}
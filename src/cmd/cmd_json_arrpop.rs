@@ -0,0 +1,62 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, to_response, KEY_NOT_FOUND_ERROR};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.arrpop/
+pub struct JsonArrPopCommand;
+
+impl Command for JsonArrPopCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 3 {
+            return Error(String::from("ERR wrong number of arguments for 'json.arrpop' command"));
+        }
+
+        let path = match parse_path(args.get(1).map(|path| path.as_str()).unwrap_or(".")) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let index = match args.get(2).map(|index| index.parse::<i64>()) {
+            Some(Ok(index)) => index,
+            Some(Err(_)) => return Error(String::from("ERR value is not an integer or out of range")),
+            None => -1,
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "array", |value| {
+            match value {
+                JsonValue::Array(array) => {
+                    if array.is_empty() {
+                        return Ok(Some(None));
+                    }
+
+                    // out of range indexes are rounded to the respective end of the array
+                    let position = if index < 0 { array.len() as i64 + index } else { index };
+                    let position = position.clamp(0, array.len() as i64 - 1) as usize;
+                    Ok(Some(Some(array.remove(position))))
+                }
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => {
+                to_response(results, &path, |popped| {
+                    match popped {
+                        Some(popped) => BulkString(popped.to_json_string()),
+                        None => Null,
+                    }
+                })
+            }
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{get_json_document, parse_path};
+use crate::json::json_path::remove_at;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.del/
+pub struct JsonDelCommand;
+
+impl Command for JsonDelCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'json.del' command"));
+        }
+
+        let key = args[0].clone();
+        let path = match parse_path(args.get(1).map(|path| path.as_str()).unwrap_or("$")) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &key) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Integer(0),
+            Err(err) => return err,
+        };
+
+        // deleting the root element deletes the whole key
+        if path.is_root() {
+            store.remove(&key);
+            return Integer(1);
+        }
+
+        // removing elements changes the indexes of the following array elements, so we start with the last location
+        let mut locations = path.find(document);
+        locations.sort();
+
+        let mut deleted = 0;
+        for location in locations.iter().rev() {
+            if remove_at(document, location).is_some() {
+                deleted += 1;
+            }
+        }
+
+        Integer(deleted)
+    }
+}
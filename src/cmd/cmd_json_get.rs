@@ -0,0 +1,74 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{get_json_document, get_path_value, parse_path};
+use crate::json::json_value::{JsonFormat, JsonValue};
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.get/
+pub struct JsonGetCommand;
+
+impl Command for JsonGetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let mut format = JsonFormat::default();
+        let mut paths = Vec::new();
+
+        let mut i = 1;
+        while i < args.len() {
+            let has_value = i + 1 < args.len();
+
+            match args[i].to_uppercase().as_str() {
+                "INDENT" if has_value => {
+                    format.indent = args[i + 1].clone();
+                    i += 1;
+                }
+                "NEWLINE" if has_value => {
+                    format.newline = args[i + 1].clone();
+                    i += 1;
+                }
+                "SPACE" if has_value => {
+                    format.space = args[i + 1].clone();
+                    i += 1;
+                }
+                _ => {
+                    match parse_path(&args[i]) {
+                        Ok(path) => paths.push(path),
+                        Err(err) => return err,
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Null,
+            Err(err) => return err,
+        };
+
+        // without a path the whole document is returned
+        if paths.is_empty() {
+            return BulkString(document.to_formatted_json_string(&format));
+        }
+
+        if paths.len() == 1 {
+            return match get_path_value(document, &paths[0]) {
+                Ok(value) => BulkString(value.to_formatted_json_string(&format)),
+                Err(err) => err,
+            };
+        }
+
+        // multiple paths are returned as object with the path as key
+        let mut result = JsonValue::Object(Vec::with_capacity(paths.len()));
+        for path in paths.iter() {
+            match get_path_value(document, path) {
+                Ok(value) => {
+                    result.set_key(&path.text, value);
+                }
+                Err(err) => return err,
+            }
+        }
+
+        BulkString(result.to_formatted_json_string(&format))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{get_json_document, get_path_value, parse_path};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.mget/
+pub struct JsonMGetCommand;
+
+impl Command for JsonMGetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let (path, keys) = args.split_last().unwrap();
+        let path = match parse_path(path) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let mut result = Vec::with_capacity(keys.len());
+        for key in keys {
+            // keys that do not exist or hold another type result in null
+            match get_json_document(store, key) {
+                Ok(Some(document)) => {
+                    match get_path_value(document, &path) {
+                        Ok(value) => result.push(BulkString(value.to_json_string())),
+                        Err(_) => result.push(Null),
+                    }
+                }
+                _ => result.push(Null),
+            }
+        }
+
+        Array(result)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, parse_value, KEY_NOT_FOUND_ERROR};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.numincrby/
+pub struct JsonNumIncrByCommand;
+
+impl Command for JsonNumIncrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let path = match parse_path(&args[1]) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let increment = match parse_value(&args[2]) {
+            Ok(value @ JsonValue::Integer(_)) | Ok(value @ JsonValue::Float(_)) => value,
+            _ => return Error(String::from("ERR value is not a number")),
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "number", |value| {
+            let result = match (&*value, &increment) {
+                (JsonValue::Integer(a), JsonValue::Integer(b)) => {
+                    match a.checked_add(*b) {
+                        Some(sum) => JsonValue::Integer(sum),
+                        None => JsonValue::Float(*a as f64 + *b as f64),
+                    }
+                }
+                (JsonValue::Integer(a), JsonValue::Float(b)) => JsonValue::Float(*a as f64 + b),
+                (JsonValue::Float(a), JsonValue::Integer(b)) => JsonValue::Float(a + *b as f64),
+                (JsonValue::Float(a), JsonValue::Float(b)) => JsonValue::Float(a + b),
+                _ => return Ok(None),
+            };
+
+            if let JsonValue::Float(float) = result {
+                if !float.is_finite() {
+                    return Err(Error(String::from("ERR result is an infinite number")));
+                }
+            }
+
+            *value = result.clone();
+            Ok(Some(result))
+        });
+
+        let results = match results {
+            Ok(results) => results,
+            Err(err) => return err,
+        };
+
+        // the new values are returned as JSON instead of a RESP array
+        if path.is_legacy {
+            let value = results.into_iter().next().flatten().unwrap_or(JsonValue::Null);
+            return BulkString(value.to_json_string());
+        }
+
+        let values = results.into_iter().map(|result| result.unwrap_or(JsonValue::Null)).collect();
+        BulkString(JsonValue::Array(values).to_json_string())
+    }
+}
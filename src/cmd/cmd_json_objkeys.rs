@@ -0,0 +1,40 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, to_response};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.objkeys/
+pub struct JsonObjKeysCommand;
+
+impl Command for JsonObjKeysCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'json.objkeys' command"));
+        }
+
+        let path = match parse_path(args.get(1).map(|path| path.as_str()).unwrap_or(".")) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Null,
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "object", |value| {
+            match value {
+                JsonValue::Object(entries) => Ok(Some(entries.iter().map(|(key, _)| BulkString(key.clone())).collect())),
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => to_response(results, &path, Array),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{get_json_document, parse_path, parse_value};
+use crate::json::json_path::get_at_mut;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_JSON};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Null, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/json.set/
+pub struct JsonSetCommand;
+
+impl Command for JsonSetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() > 4 {
+            return Error(String::from("ERR wrong number of arguments for 'json.set' command"));
+        }
+
+        let key = args[0].clone();
+        let mut only_new = false;
+        let mut only_existing = false;
+
+        if let Some(condition) = args.get(3) {
+            match condition.to_uppercase().as_str() {
+                "NX" => only_new = true,
+                "XX" => only_existing = true,
+                _ => return Error(String::from("ERR syntax error")),
+            }
+        }
+
+        let path = match parse_path(&args[1]) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+        let value = match parse_value(&args[2]) {
+            Ok(value) => value,
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &key) {
+            Ok(document) => document,
+            Err(err) => return err,
+        };
+
+        let document = match document {
+            Some(document) => document,
+            None => {
+                if !path.is_root() {
+                    return Error(String::from("ERR new objects must be created at the root"));
+                }
+                if only_existing {
+                    return Null;
+                }
+                store.put(&key, ObjectValue::Json(value), -1, OBJ_TYPE_JSON | OBJ_ENCODING_RAW);
+                return SimpleString(String::from("OK"));
+            }
+        };
+
+        let locations = path.find(document);
+
+        if !locations.is_empty() {
+            if only_new {
+                return Null;
+            }
+
+            for location in locations.iter() {
+                *get_at_mut(document, location).expect("Location of match not found") = value.clone();
+            }
+            return SimpleString(String::from("OK"));
+        }
+
+        if only_existing {
+            return Null;
+        }
+
+        // the path does not exist yet, but we can add the last key of the path to its parent objects
+        let (parent_path, new_key) = match path.split_last_key() {
+            Some(split) => split,
+            None => return Null,
+        };
+
+        let mut updated = false;
+        for location in parent_path.find(document).iter() {
+            let parent = get_at_mut(document, location).expect("Location of match not found");
+            updated |= parent.set_key(&new_key, value.clone());
+        }
+
+        if !updated {
+            return Null;
+        }
+
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,52 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, parse_value, to_response, KEY_NOT_FOUND_ERROR};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.strappend/
+pub struct JsonStrAppendCommand;
+
+impl Command for JsonStrAppendCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 2 || args.len() > 3 {
+            return Error(String::from("ERR wrong number of arguments for 'json.strappend' command"));
+        }
+
+        // the path is optional and comes before the value
+        let (path, value) = if args.len() == 3 { (args[1].as_str(), &args[2]) } else { (".", &args[1]) };
+
+        let path = match parse_path(path) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let suffix = match parse_value(value) {
+            Ok(JsonValue::String(suffix)) => suffix,
+            Ok(_) => return Error(String::from("ERR value must be a JSON string")),
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = apply_to_path(document, &path, "string", |value| {
+            match value {
+                JsonValue::String(string) => {
+                    string.push_str(&suffix);
+                    Ok(Some(string.len() as i64))
+                }
+                _ => Ok(None),
+            }
+        });
+
+        match results {
+            Ok(results) => to_response(results, &path, Integer),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::cmd::command::Command;
+use crate::json::json_helper::{apply_to_path, get_json_document, parse_path, to_response};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Null, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/json.type/
+pub struct JsonTypeCommand;
+
+impl Command for JsonTypeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'json.type' command"));
+        }
+
+        let path = match parse_path(args.get(1).map(|path| path.as_str()).unwrap_or(".")) {
+            Ok(path) => path,
+            Err(err) => return err,
+        };
+
+        let document = match get_json_document(store, &args[0]) {
+            Ok(Some(document)) => document,
+            Ok(None) => return Null,
+            Err(err) => return err,
+        };
+
+        let results = match apply_to_path(document, &path, "any", |value| Ok(Some(value.type_name()))) {
+            Ok(results) => results,
+            Err(_) if path.is_legacy => return Null,
+            Err(err) => return err,
+        };
+
+        to_response(results, &path, |type_name| SimpleString(type_name.to_string()))
+    }
+}
@@ -0,0 +1,15 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/keys/
+///
+/// Returns all keys of the selected database matching the glob-style pattern. Use SCAN to iterate over large databases
+pub struct KeysCommand;
+
+impl Command for KeysCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Array(store.get_keys(&args[0]).into_iter().map(BulkString).collect())
+    }
+}
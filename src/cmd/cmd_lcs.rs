@@ -0,0 +1,80 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::store::Store;
+use crate::string::lcs::{get_lcs_memory_usage, lcs};
+use crate::string::string_helper::get_string;
+use crate::string_value::STRING_MAX_SIZE;
+
+/// see https://redis.io/commands/lcs/
+///
+/// LCS key1 key2 [LEN] [IDX] [MINMATCHLEN min-match-len] [WITHMATCHLEN]
+/// Missing keys count as empty strings
+pub struct LcsCommand;
+
+impl Command for LcsCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let mut len = false;
+        let mut idx = false;
+        let mut with_match_len = false;
+        let mut min_match_len = 0;
+
+        let mut i = 2;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "LEN" => len = true,
+                "IDX" => idx = true,
+                "WITHMATCHLEN" => with_match_len = true,
+                "MINMATCHLEN" if i + 1 < args.len() => {
+                    min_match_len = match args[i + 1].parse::<i64>() {
+                        Ok(min_match_len) => min_match_len.max(0) as usize,
+                        Err(_) => return Error(String::from("ERR value is not an integer or out of range")),
+                    };
+                    i += 1;
+                }
+                _ => return Error(String::from("ERR syntax error")),
+            }
+            i += 1;
+        }
+        if len && idx {
+            return Error(String::from("ERR If you want both the length and indexes, please just use IDX."));
+        }
+
+        let first = match get_string(store, &args[0]) {
+            Ok(value) => value.unwrap_or_default(),
+            Err(err) => return err,
+        };
+        let second = match get_string(store, &args[1]) {
+            Ok(value) => value.unwrap_or_default(),
+            Err(err) => return err,
+        };
+        if get_lcs_memory_usage(first.as_bytes(), second.as_bytes()) > STRING_MAX_SIZE as u64 {
+            return Error(String::from("ERR Insufficient memory, transient memory for LCS exceeds proto-max-bulk-len"));
+        }
+
+        let result = lcs(first.as_bytes(), second.as_bytes(), min_match_len);
+        if len {
+            return Integer(result.subsequence.len() as i64);
+        }
+        if !idx {
+            return BulkString(String::from_utf8_lossy(&result.subsequence).into_owned());
+        }
+
+        let to_range = |(start, end): (usize, usize)| Array(vec![Integer(start as i64), Integer(end as i64)]);
+        let matches = result.matches.iter()
+            .map(|lcs_match| {
+                let mut entry = vec![to_range(lcs_match.first), to_range(lcs_match.second)];
+                if with_match_len {
+                    entry.push(Integer(lcs_match.len() as i64));
+                }
+                Array(entry)
+            })
+            .collect();
+        Array(vec![
+            BulkString(String::from("matches")),
+            Array(matches),
+            BulkString(String::from("len")),
+            Integer(result.subsequence.len() as i64),
+        ])
+    }
+}
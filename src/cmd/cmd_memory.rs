@@ -0,0 +1,235 @@
+use crate::allocator::{get_allocated_bytes, get_startup_allocated_bytes};
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/memory-usage/
+///
+/// MEMORY USAGE | STATS | DOCTOR
+/// All sizes are estimates based on the size of the values and the capacity of the hash tables
+pub struct MemoryCommand;
+
+// the doctor only reports issues if the dataset has at least this size
+const DOCTOR_MIN_DATASET_BYTES: u64 = 1024 * 1024;
+// keys using more than this percentage of the dataset are reported as big keys
+const DOCTOR_BIG_KEY_PERCENTAGE: f64 = 10.0;
+const DOCTOR_MAX_BIG_KEYS: usize = 5;
+// number of elements of aggregate values MEMORY USAGE looks at without the SAMPLES option
+const DEFAULT_MEMORY_USAGE_SAMPLES: usize = 5;
+
+impl Command for MemoryCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match args[0].to_uppercase().as_str() {
+            "USAGE" => memory_usage(&args[1..], store),
+            "STATS" if args.len() == 1 => memory_stats(store),
+            "DOCTOR" if args.len() == 1 => memory_doctor(store),
+            _ => Error(format!("ERR unknown subcommand '{}'. Try MEMORY HELP.", args[0])),
+        }
+    }
+}
+
+/// MEMORY USAGE key [SAMPLES count]
+/// Like Redis, the size of aggregate values is extrapolated from count elements (5 by default), 0 samples all elements
+fn memory_usage(args: &[String], store: &mut Store) -> DataType {
+    if args.len() != 1 && args.len() != 3 {
+        return Error(String::from("ERR syntax error"));
+    }
+    let samples = match args.get(1..3) {
+        None => DEFAULT_MEMORY_USAGE_SAMPLES,
+        Some([option, count]) if option.eq_ignore_ascii_case("SAMPLES") => match count.parse::<usize>() {
+            Ok(samples) => samples,
+            Err(_) => return Error(String::from("ERR syntax error")),
+        },
+        Some(_) => return Error(String::from("ERR syntax error")),
+    };
+
+    match store.get_without_touch(&args[0]) {
+        Some(store_object) => Integer((args[0].len() as u64 + store_object.get_sampled_memory_usage(samples)) as i64),
+        None => Null,
+    }
+}
+
+/// Allocated memory split into the memory at startup, the overhead of the hash tables per database and the dataset
+fn memory_stats(store: &Store) -> DataType {
+    let total_bytes = get_allocated_bytes();
+    let startup_bytes = get_startup_allocated_bytes();
+    let overheads = store.get_database_overheads();
+    let overhead_bytes: u64 = startup_bytes + overheads.iter().map(|overhead| overhead.main_bytes + overhead.expires_bytes).sum::<u64>();
+    let keys_count: usize = overheads.iter().map(|overhead| overhead.keys).sum();
+    let dataset_bytes = total_bytes.saturating_sub(overhead_bytes);
+    // like Redis, the memory at startup is not attributed to the keys
+    let net_bytes = total_bytes.saturating_sub(startup_bytes);
+
+    let mut response = vec![
+        BulkString(String::from("total.allocated")),
+        Integer(total_bytes as i64),
+        BulkString(String::from("startup.allocated")),
+        Integer(startup_bytes as i64),
+    ];
+    for overhead in overheads.iter() {
+        response.push(BulkString(format!("db.{}", overhead.db)));
+        response.push(Array(vec![
+            BulkString(String::from("overhead.hashtable.main")),
+            Integer(overhead.main_bytes as i64),
+            BulkString(String::from("overhead.hashtable.expires")),
+            Integer(overhead.expires_bytes as i64),
+        ]));
+    }
+    response.extend(vec![
+        BulkString(String::from("overhead.total")),
+        Integer(overhead_bytes as i64),
+        BulkString(String::from("keys.count")),
+        Integer(keys_count as i64),
+        BulkString(String::from("keys.bytes-per-key")),
+        Integer(if keys_count == 0 { 0 } else { (net_bytes / keys_count as u64) as i64 }),
+        BulkString(String::from("dataset.bytes")),
+        Integer(dataset_bytes as i64),
+        BulkString(String::from("dataset.percentage")),
+        BulkString(percentage(dataset_bytes, net_bytes).to_string()),
+    ]);
+
+    Array(response)
+}
+
+/// Reports a high overhead of the hash tables and keys which use a large part of the dataset.
+/// The doctor visits all keys to find the biggest keys, so it compares them with the estimated size of the dataset
+fn memory_doctor(store: &Store) -> DataType {
+    let dataset_bytes = store.estimate_dataset_memory();
+    if dataset_bytes < DOCTOR_MIN_DATASET_BYTES {
+        return BulkString(String::from("This instance is empty or uses very little memory, there is nothing to report."));
+    }
+
+    let overhead_bytes: u64 = store.get_database_overheads().iter().map(|overhead| overhead.main_bytes + overhead.expires_bytes).sum();
+    let mut issues = Vec::new();
+
+    let overhead_percentage = percentage(overhead_bytes, overhead_bytes + dataset_bytes);
+    if overhead_percentage > 50.0 {
+        issues.push(format!(
+            "High hash table overhead: {:.2}% of the memory is used by the keyspace itself. \
+            This is typical for many small keys, consider grouping related values in a hash.",
+            overhead_percentage,
+        ));
+    }
+
+    for (db, key, size) in store.get_biggest_keys(DOCTOR_MAX_BIG_KEYS) {
+        let key_percentage = percentage(size, dataset_bytes);
+        if key_percentage > DOCTOR_BIG_KEY_PERCENTAGE {
+            issues.push(format!("Big key: '{}' in db {} uses {} bytes ({:.2}% of the dataset).", key, db, size, key_percentage));
+        }
+    }
+
+    if issues.is_empty() {
+        return BulkString(String::from("No memory issues found in this instance."));
+    }
+    BulkString(format!("Memory issues found in this instance:\n\n{}", issues.iter().map(|issue| format!(" * {}", issue)).collect::<Vec<_>>().join("\n")))
+}
+
+fn percentage(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+
+    /// Value of a field of MEMORY STATS
+    fn get_stat(stats: &DataType, name: &str) -> Option<DataType> {
+        match stats {
+            Array(fields) => fields.chunks(2)
+                .find(|field| field[0] == BulkString(name.to_string()))
+                .map(|field| field[1].clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET small abc");
+        execute(&mut handler, &mut store, &format!("SET large {}", "a".repeat(1000)));
+
+        // when
+        let small = execute(&mut handler, &mut store, "MEMORY USAGE small");
+        let large = execute(&mut handler, &mut store, "MEMORY USAGE large SAMPLES 5");
+
+        // then
+        match (small, large) {
+            (Integer(small), Integer(large)) => assert!(small > 0 && large > small && large >= 1000),
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert_eq!(execute(&mut handler, &mut store, "MEMORY USAGE missing"), Null);
+        assert_eq!(execute(&mut handler, &mut store, "MEMORY USAGE small SAMPLES x"), Error(String::from("ERR syntax error")));
+    }
+
+    #[test]
+    fn test_memory_usage_samples() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        let fields: Vec<String> = (0..100).map(|i| format!("field{} value{}", i, i)).collect();
+        execute(&mut handler, &mut store, &format!("HSET hash {}", fields.join(" ")));
+
+        // when
+        let all = execute(&mut handler, &mut store, "MEMORY USAGE hash SAMPLES 0");
+        let sampled = execute(&mut handler, &mut store, "MEMORY USAGE hash");
+        let few = execute(&mut handler, &mut store, "MEMORY USAGE hash SAMPLES 1");
+
+        // then
+        match (all, sampled, few) {
+            (Integer(all), Integer(sampled), Integer(few)) => {
+                // all fields have a similar size, so the extrapolation is close to the exact size
+                assert!(sampled > all * 9 / 10 && sampled < all * 11 / 10);
+                assert!(few > all * 9 / 10 && few < all * 11 / 10);
+            }
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_memory_stats() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        let empty_stats = execute(&mut handler, &mut store, "MEMORY STATS");
+
+        // when
+        execute(&mut handler, &mut store, "SET a 1");
+        execute(&mut handler, &mut store, &format!("SET b {}", "b".repeat(100)));
+        let stats = execute(&mut handler, &mut store, "MEMORY STATS");
+
+        // then
+        assert_eq!(get_stat(&empty_stats, "keys.count"), Some(Integer(0)));
+        assert_eq!(get_stat(&empty_stats, "keys.bytes-per-key"), Some(Integer(0)));
+        assert_eq!(get_stat(&stats, "keys.count"), Some(Integer(2)));
+        assert!(matches!(get_stat(&stats, "dataset.bytes"), Some(Integer(bytes)) if bytes >= 100));
+        assert!(matches!(get_stat(&stats, "db.0"), Some(Array(_))));
+    }
+
+    #[test]
+    fn test_memory_doctor() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        let empty_report = execute(&mut handler, &mut store, "MEMORY DOCTOR");
+
+        // when
+        execute(&mut handler, &mut store, &format!("SET big {}", "a".repeat(2 * DOCTOR_MIN_DATASET_BYTES as usize)));
+        let report = execute(&mut handler, &mut store, "MEMORY DOCTOR");
+
+        // then
+        assert_eq!(empty_report, BulkString(String::from("This instance is empty or uses very little memory, there is nothing to report.")));
+        match report {
+            BulkString(report) => assert!(report.contains("Big key: 'big' in db 0")),
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert_eq!(execute(&mut handler, &mut store, "MEMORY FOO"), Error(String::from("ERR unknown subcommand 'FOO'. Try MEMORY HELP.")));
+    }
+}
@@ -0,0 +1,21 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Null};
+use crate::store::Store;
+use crate::string::string_helper::{get_string, to_bulk_string};
+
+/// see https://redis.io/commands/mget/
+///
+/// Keys which do not exist or hold another type than string return null
+pub struct MGetCommand;
+
+impl Command for MGetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Array(args.iter()
+            .map(|key| match get_string(store, key) {
+                Ok(value) => to_bulk_string(value),
+                Err(_) => Null,
+            })
+            .collect())
+    }
+}
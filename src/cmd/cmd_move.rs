@@ -0,0 +1,23 @@
+use crate::cmd::command::{parse_db_index, Command};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/move/
+///
+/// Returns 1 if the key was moved, 0 if it does not exist or already exists in the destination database
+pub struct MoveCommand;
+
+impl Command for MoveCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let db = match parse_db_index(&args[1]) {
+            Ok(db) => db,
+            Err(err) => return err,
+        };
+
+        match store.move_key(&args[0], db) {
+            Ok(moved) => Integer(moved as i64),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
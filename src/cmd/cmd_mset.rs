@@ -0,0 +1,21 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+use crate::string::string_helper::put_string;
+
+/// see https://redis.io/commands/mset/
+pub struct MSetCommand;
+
+impl Command for MSetCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() % 2 != 0 {
+            return Error(String::from("ERR wrong number of arguments for 'mset' command"));
+        }
+
+        for pair in args.chunks(2) {
+            put_string(store, &pair[0], pair[1].clone(), None);
+        }
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,26 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+use crate::string::string_helper::put_string;
+
+/// see https://redis.io/commands/msetnx/
+///
+/// No key is set if any of the keys already exists
+pub struct MSetNxCommand;
+
+impl Command for MSetNxCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() % 2 != 0 {
+            return Error(String::from("ERR wrong number of arguments for 'msetnx' command"));
+        }
+
+        if args.iter().step_by(2).any(|key| store.contains_key(key)) {
+            return Integer(0);
+        }
+        for pair in args.chunks(2) {
+            put_string(store, &pair[0], pair[1].clone(), None);
+        }
+        Integer(1)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::get_encoding_name;
+use crate::eviction::lfu_eviction_strategy::get_decayed_counter;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer, Null, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/object/
+///
+/// OBJECT ENCODING | IDLETIME | FREQ | REFCOUNT key | OBJECT HELP
+/// Looking up the key does not update its access time.
+/// Objects track either the access time or the access frequency, depending on the maxmemory-policy
+pub struct ObjectCommand;
+
+const LFU_SELECTED_ERROR: &str = "ERR An LFU maxmemory policy is selected, idle time not tracked. \
+    Please note that when switching between policies at runtime LRU and LFU data will take some time to adjust.";
+const LFU_NOT_SELECTED_ERROR: &str = "ERR An LFU maxmemory policy is not selected, access frequency not tracked. \
+    Please note that when switching between policies at runtime LRU and LFU data will take some time to adjust.";
+
+impl Command for ObjectCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let subcommand = args[0].to_uppercase();
+        if !["ENCODING", "IDLETIME", "FREQ", "REFCOUNT", "HELP"].contains(&subcommand.as_str()) {
+            return Error(format!("ERR unknown subcommand '{}'. Try OBJECT HELP.", args[0]));
+        }
+        match (subcommand.as_str(), args.len()) {
+            ("HELP", 1) => return help(),
+            (_, 2) if subcommand != "HELP" => {}
+            _ => return Error(format!("ERR wrong number of arguments for 'object|{}' command", subcommand.to_lowercase())),
+        }
+
+        let lfu_selected = store.get_eviction_policy().is_lfu();
+        let lfu = store.get_lfu_configuration();
+        let store_object = match store.get_without_touch(&args[1]) {
+            Some(store_object) => store_object,
+            None => return Null,
+        };
+
+        match subcommand.as_str() {
+            "ENCODING" => BulkString(get_encoding_name(store_object.type_encoding).to_string()),
+            "IDLETIME" if lfu_selected => Error(String::from(LFU_SELECTED_ERROR)),
+            "IDLETIME" => Integer((store_object.get_idle_time_ms() / 1000) as i64),
+            "FREQ" if lfu_selected => Integer(get_decayed_counter(store_object.last_accessed_at, &lfu) as i64),
+            "FREQ" => Error(String::from(LFU_NOT_SELECTED_ERROR)),
+            // REFCOUNT, small integers are shared between keys, see shared_integers
+            _ => Integer(store_object.get_ref_count() as i64),
+        }
+    }
+}
+
+fn help() -> DataType {
+    let lines = [
+        "OBJECT <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+        "ENCODING <key>",
+        "    Return the kind of internal representation used in order to store the value",
+        "    associated with a <key>.",
+        "FREQ <key>",
+        "    Return the access frequency index of the <key>. The returned integer is",
+        "    proportional to the logarithm of the recent access frequency of the key.",
+        "IDLETIME <key>",
+        "    Return the idle time of the <key>, that is the approximated number of",
+        "    seconds elapsed since the last access to the key.",
+        "REFCOUNT <key>",
+        "    Return the number of references of the value associated with the specified",
+        "    <key>.",
+        "HELP",
+        "    Print this help.",
+    ];
+    Array(lines.iter().map(|line| SimpleString(line.to_string())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::command::WRONG_TYPE_ERROR;
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+
+    fn encoding(handler: &mut CommandHandler, store: &mut Store, key: &str) -> DataType {
+        execute(handler, store, &format!("OBJECT ENCODING {}", key))
+    }
+
+    #[test]
+    fn test_object_encoding() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        let long_string = "a".repeat(50);
+
+        // when
+        execute(&mut handler, &mut store, "SET int 12");
+        execute(&mut handler, &mut store, "SET embstr abc");
+        execute(&mut handler, &mut store, &format!("SET raw {}", long_string));
+        execute(&mut handler, &mut store, "HSET hash field 1");
+        execute(&mut handler, &mut store, "GEOADD geo 13.361389 38.115556 Palermo");
+
+        // then
+        assert_eq!(encoding(&mut handler, &mut store, "int"), BulkString(String::from("int")));
+        assert_eq!(encoding(&mut handler, &mut store, "embstr"), BulkString(String::from("embstr")));
+        assert_eq!(encoding(&mut handler, &mut store, "raw"), BulkString(String::from("raw")));
+        assert_eq!(encoding(&mut handler, &mut store, "hash"), BulkString(String::from("hashtable")));
+        assert_eq!(encoding(&mut handler, &mut store, "geo"), BulkString(String::from("skiplist")));
+        assert_eq!(encoding(&mut handler, &mut store, "missing"), Null);
+    }
+
+    #[test]
+    fn test_object_encoding_transitions() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET int 12");
+        execute(&mut handler, &mut store, "SET embstr abc");
+        execute(&mut handler, &mut store, "SET counter 5");
+        execute(&mut handler, &mut store, "SET float 1.5");
+
+        // when
+        execute(&mut handler, &mut store, "APPEND int 3");
+        execute(&mut handler, &mut store, "SETRANGE embstr 1 x");
+        execute(&mut handler, &mut store, "INCR counter");
+        execute(&mut handler, &mut store, "INCRBYFLOAT float 1");
+
+        // then
+        // modified strings become raw, integers stay int and floats are stored as text
+        assert_eq!(encoding(&mut handler, &mut store, "int"), BulkString(String::from("raw")));
+        assert_eq!(encoding(&mut handler, &mut store, "embstr"), BulkString(String::from("raw")));
+        assert_eq!(encoding(&mut handler, &mut store, "counter"), BulkString(String::from("int")));
+        assert_eq!(encoding(&mut handler, &mut store, "float"), BulkString(String::from("embstr")));
+    }
+
+    #[test]
+    fn test_object_refcount() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET shared 12");
+        execute(&mut handler, &mut store, "SET other 12");
+        execute(&mut handler, &mut store, "SET string abc");
+
+        // when
+        let shared = execute(&mut handler, &mut store, "OBJECT REFCOUNT shared");
+        let string = execute(&mut handler, &mut store, "OBJECT REFCOUNT string");
+
+        // then
+        // small integers reference the shared object
+        assert!(matches!(shared, Integer(count) if count > 2));
+        assert_eq!(string, Integer(1));
+        assert_eq!(execute(&mut handler, &mut store, "OBJECT REFCOUNT missing"), Null);
+    }
+
+    #[test]
+    fn test_wrong_type_keeps_object_shared() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET shared 12");
+        let ref_count = execute(&mut handler, &mut store, "OBJECT REFCOUNT shared");
+
+        // when
+        let hset = execute(&mut handler, &mut store, "HSET shared field 1");
+        let bf_add = execute(&mut handler, &mut store, "BF.ADD shared item");
+        let geoadd = execute(&mut handler, &mut store, "GEOADD shared 13.361389 38.115556 Palermo");
+
+        // then
+        assert_eq!(hset, Error(String::from(WRONG_TYPE_ERROR)));
+        assert_eq!(bf_add, Error(String::from(WRONG_TYPE_ERROR)));
+        assert_eq!(geoadd, Error(String::from(WRONG_TYPE_ERROR)));
+        assert_eq!(execute(&mut handler, &mut store, "OBJECT REFCOUNT shared"), ref_count);
+    }
+
+    #[test]
+    fn test_object_idletime_and_freq() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET key value");
+
+        // when
+        let idle_time = execute(&mut handler, &mut store, "OBJECT IDLETIME key");
+        let freq_without_lfu = execute(&mut handler, &mut store, "OBJECT FREQ key");
+        execute(&mut handler, &mut store, "CONFIG SET maxmemory-policy allkeys-lfu");
+
+        // then
+        assert_eq!(idle_time, Integer(0));
+        assert_eq!(freq_without_lfu, Error(String::from(LFU_NOT_SELECTED_ERROR)));
+        assert!(matches!(execute(&mut handler, &mut store, "OBJECT FREQ key"), Integer(_)));
+        assert_eq!(execute(&mut handler, &mut store, "OBJECT IDLETIME key"), Error(String::from(LFU_SELECTED_ERROR)));
+        assert_eq!(execute(&mut handler, &mut store, "OBJECT FREQ missing"), Null);
+    }
+
+    #[test]
+    fn test_object_errors() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+
+        // when
+        let unknown_subcommand = execute(&mut handler, &mut store, "OBJECT FOO key");
+        let missing_key_argument = execute(&mut handler, &mut store, "OBJECT ENCODING");
+
+        // then
+        assert_eq!(unknown_subcommand, Error(String::from("ERR unknown subcommand 'FOO'. Try OBJECT HELP.")));
+        assert_eq!(missing_key_argument, Error(String::from("ERR wrong number of arguments for 'object|encoding' command")));
+        assert!(matches!(execute(&mut handler, &mut store, "OBJECT HELP"), Array(_)));
+    }
+}
@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/persist/
+pub struct PersistCommand;
+
+impl Command for PersistCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Integer(store.persist(&args[0]) as i64)
+    }
+}
@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::expire::expire_helper::{get_ttl, TimeUnit};
+use crate::resp::DataType;
+use crate::store::Store;
+
+/// see https://redis.io/commands/pexpiretime/
+pub struct PExpireTimeCommand;
+
+impl Command for PExpireTimeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        get_ttl(args, store, TimeUnit::Milliseconds, true, "pexpiretime")
+    }
+}
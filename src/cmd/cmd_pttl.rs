@@ -0,0 +1,13 @@
+use crate::cmd::command::Command;
+use crate::expire::expire_helper::{get_ttl, TimeUnit};
+use crate::resp::DataType;
+use crate::store::Store;
+
+/// see https://redis.io/commands/pttl/
+pub struct PTTLCommand;
+
+impl Command for PTTLCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        get_ttl(args, store, TimeUnit::Milliseconds, false, "pttl")
+    }
+}
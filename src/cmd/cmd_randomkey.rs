@@ -0,0 +1,16 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/randomkey/
+pub struct RandomKeyCommand;
+
+impl Command for RandomKeyCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match store.random_key() {
+            Some(key) => BulkString(key),
+            None => Null,
+        }
+    }
+}
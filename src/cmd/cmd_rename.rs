@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/rename/
+///
+/// The expiration of the key is kept, an existing new key is overwritten
+pub struct RenameCommand;
+
+impl Command for RenameCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match store.rename(&args[0], &args[1], true) {
+            Ok(_) => SimpleString(String::from("OK")),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
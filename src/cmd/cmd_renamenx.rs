@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/renamenx/
+///
+/// Returns 1 if the key was renamed, 0 if the new key already exists
+pub struct RenameNxCommand;
+
+impl Command for RenameNxCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match store.rename(&args[0], &args[1], false) {
+            Ok(renamed) => Integer(renamed as i64),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::cmd::command::Command;
+use crate::glob::glob_match;
+use crate::object_type_encoding::get_type_name;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error};
+use crate::store::Store;
+
+/// see https://redis.io/commands/scan/
+///
+/// SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]
+/// COUNT is the number of keys visited per call (default 10), MATCH and TYPE filter the visited keys,
+/// so a call can return fewer keys or none even if the iteration is not complete
+pub struct ScanCommand;
+
+const DEFAULT_COUNT: usize = 10;
+
+impl Command for ScanCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let cursor = match args[0].parse::<u64>() {
+            Ok(cursor) => cursor,
+            Err(_) => return Error(String::from("ERR invalid cursor")),
+        };
+
+        let mut pattern: Option<&str> = None;
+        let mut count = DEFAULT_COUNT;
+        let mut type_name: Option<&str> = None;
+
+        let mut i = 1;
+        while i < args.len() {
+            let value = match args.get(i + 1) {
+                Some(value) => value,
+                None => return Error(String::from("ERR syntax error")),
+            };
+
+            match args[i].to_uppercase().as_str() {
+                "MATCH" => pattern = Some(value),
+                "COUNT" => {
+                    count = match value.parse::<usize>() {
+                        Ok(count) if count > 0 => count,
+                        _ => return Error(String::from("ERR value is not an integer or out of range")),
+                    };
+                }
+                "TYPE" => type_name = Some(value),
+                _ => return Error(String::from("ERR syntax error")),
+            }
+            i += 2;
+        }
+
+        let (next_cursor, keys) = store.scan(cursor, count);
+        let keys: Vec<DataType> = keys.into_iter()
+            .filter(|key| pattern.is_none_or(|pattern| glob_match(pattern, key)))
+            .filter(|key| type_name.is_none_or(|type_name| {
+                store.get_data().get(key).is_some_and(|store_object| get_type_name(store_object.type_encoding).eq_ignore_ascii_case(type_name))
+            }))
+            .map(BulkString)
+            .collect();
+
+        Array(vec![BulkString(next_cursor.to_string()), Array(keys)])
+    }
+}
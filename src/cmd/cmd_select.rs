@@ -0,0 +1,23 @@
+use crate::cmd::command::{parse_db_index, Command};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/select/
+///
+/// The selected database is kept per client connection
+pub struct SelectCommand;
+
+impl Command for SelectCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let db = match parse_db_index(&args[0]) {
+            Ok(db) => db,
+            Err(err) => return err,
+        };
+
+        match store.select_db(db) {
+            Ok(()) => SimpleString(String::from("OK")),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
@@ -1,49 +1,82 @@
 use crate::cmd::command::Command;
-use crate::object_type_encoding::{get_string_encoding, OBJ_TYPE_STRING};
 use crate::resp::DataType;
-use crate::resp::DataType::{Error, SimpleString};
-use crate::store::{Store, ObjectValue};
+use crate::resp::DataType::{Error, Null, SimpleString};
+use crate::store::Store;
+use crate::string::string_helper::{get_string, parse_expiration, put_string, to_bulk_string, Expiration};
 
 /// see https://redis.io/commands/set/
+///
+/// SET key value [NX | XX] [GET] [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | KEEPTTL]
+/// Without KEEPTTL the expiration of an existing key is removed
 pub struct SetCommand;
 
+#[derive(Debug, Default)]
+struct SetOptions {
+    nx: bool,
+    xx: bool,
+    get: bool,
+    expiration: Option<Expiration>,
+}
+
 impl Command for SetCommand {
     fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
-        if args.len() < 2 {
-            return Error(String::from("ERR wrong number of arguments for 'set' command"));
+        let options = match parse_options(&args[2..]) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        let key = &args[0];
+        let old_value = match options.get {
+            true => match get_string(store, key) {
+                Ok(old_value) => old_value,
+                Err(err) => return err,
+            },
+            false => None,
+        };
+
+        let exists = store.contains_key(key);
+        if (options.nx && exists) || (options.xx && !exists) {
+            return if options.get { to_bulk_string(old_value) } else { Null };
+        }
+
+        let expires_at = match options.expiration {
+            Some(Expiration::At(expires_at)) => Some(expires_at),
+            Some(Expiration::KeepTTL) => store.get_expiry(key),
+            Some(Expiration::Persist) | None => None,
+        };
+        put_string(store, key, args[1].clone(), expires_at);
+
+        match options.get {
+            true => to_bulk_string(old_value),
+            false => SimpleString(String::from("OK")),
         }
+    }
+}
 
-        let key = args[0].clone();
-        let value = args[1].clone();
-
-        let mut expiration_duration_ms = -1;
-        let mut i = 2;
-
-        while i < args.len() {
-            let arg = args[i].clone();
-
-            if arg == "EX" {
-                if i + 1 >= args.len() {
-                    return Error(String::from("ERR syntax error"));
-                }
-
-                let duration = args[i + 1].clone();
-                match duration.parse::<i64>() {
-                    Ok(duration_sec) => {
-                        expiration_duration_ms = duration_sec * 1000;
-                        i += 2;
-                    }
-                    Err(_) => {
-                        return Error(String::from("ERR value is not an integer or out of range"));
-                    }
-                }
-            } else {
-                return Error(String::from("ERR syntax error"));
+fn parse_options(args: &[String]) -> Result<SetOptions, DataType> {
+    let syntax_error = || Error(String::from("ERR syntax error"));
+    let mut options = SetOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        if let Some(expires_at) = parse_expiration(&args[i], args.get(i + 1), "set") {
+            if options.expiration.is_some() {
+                return Err(syntax_error());
             }
+            options.expiration = Some(Expiration::At(expires_at?));
+            i += 2;
+            continue;
         }
 
-        let string_encoding = get_string_encoding(&value);
-        store.put(&key, ObjectValue::String(value), expiration_duration_ms, OBJ_TYPE_STRING | string_encoding);
-        return SimpleString(String::from("OK"));
+        match args[i].to_uppercase().as_str() {
+            "NX" if !options.xx => options.nx = true,
+            "XX" if !options.nx => options.xx = true,
+            "GET" => options.get = true,
+            "KEEPTTL" if options.expiration.is_none() => options.expiration = Some(Expiration::KeepTTL),
+            _ => return Err(syntax_error()),
+        }
+        i += 1;
     }
+
+    Ok(options)
 }
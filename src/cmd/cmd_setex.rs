@@ -0,0 +1,19 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::SimpleString;
+use crate::store::Store;
+use crate::string::string_helper::{parse_expiration, put_string};
+
+/// see https://redis.io/commands/setex/
+pub struct SetExCommand;
+
+impl Command for SetExCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let expires_at = match parse_expiration("EX", args.get(1), "setex").expect("EX is an expiration option") {
+            Ok(expires_at) => expires_at,
+            Err(err) => return err,
+        };
+        put_string(store, &args[0], args[2].clone(), Some(expires_at));
+        SimpleString(String::from("OK"))
+    }
+}
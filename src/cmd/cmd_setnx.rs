@@ -0,0 +1,18 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+use crate::string::string_helper::put_string;
+
+/// see https://redis.io/commands/setnx/
+pub struct SetNxCommand;
+
+impl Command for SetNxCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if store.contains_key(&args[0]) {
+            return Integer(0);
+        }
+        put_string(store, &args[0], args[1].clone(), None);
+        Integer(1)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::cmd::command::{Command, WRONG_TYPE_ERROR};
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_STRING};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::{ObjectValue, Store};
+use crate::string_value::{StringValue, STRING_MAX_SIZE};
+
+/// see https://redis.io/commands/setrange/
+///
+/// Overwriting converts int and embstr values to raw strings
+pub struct SetRangeCommand;
+
+impl Command for SetRangeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let offset = match args[1].parse::<i64>() {
+            Ok(offset) if offset < 0 => return Error(String::from("ERR offset is out of range")),
+            Ok(offset) => offset as usize,
+            Err(_) => return Error(String::from("ERR value is not an integer or out of range")),
+        };
+
+        return match store.get_mut(&args[0]) {
+            Some(store_object) => {
+                let string = match store_object.get_value_mut() {
+                    ObjectValue::String(string) => string,
+                    _ => return Error(String::from(WRONG_TYPE_ERROR)),
+                };
+                let length = match string.set_range(offset, &args[2]) {
+                    Ok(length) => length,
+                    Err(err) => return Error(format!("ERR {}", err)),
+                };
+                store_object.type_encoding = OBJ_TYPE_STRING | string.get_encoding();
+                Integer(length as i64)
+            }
+            None => {
+                // an empty value does not create the key
+                if args[2].is_empty() {
+                    return Integer(0);
+                }
+                if offset + args[2].len() > STRING_MAX_SIZE {
+                    return Error(String::from("ERR string exceeds maximum allowed size (proto-max-bulk-len)"));
+                }
+
+                let mut value = StringValue::Raw(String::new());
+                let length = value.set_range(offset, &args[2]).unwrap_or_default();
+                store.put(&args[0], ObjectValue::String(value), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+                Integer(length as i64)
+            }
+        };
+    }
+}
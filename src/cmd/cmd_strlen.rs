@@ -0,0 +1,19 @@
+use crate::cmd::command::{Command, WRONG_TYPE_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/strlen/
+pub struct StrLenCommand;
+
+impl Command for StrLenCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match store.get(&args[0]) {
+            Some(store_object) => match store_object.value.as_ref() {
+                ObjectValue::String(string) => Integer(string.get_length() as i64),
+                _ => Error(String::from(WRONG_TYPE_ERROR)),
+            },
+            None => Integer(0),
+        }
+    }
+}
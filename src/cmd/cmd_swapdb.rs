@@ -0,0 +1,21 @@
+use crate::cmd::command::{parse_db_index, Command};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/swapdb/
+pub struct SwapDbCommand;
+
+impl Command for SwapDbCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let (first, second) = match (parse_db_index(&args[0]), parse_db_index(&args[1])) {
+            (Ok(first), Ok(second)) => (first, second),
+            (Err(err), _) | (_, Err(err)) => return err,
+        };
+
+        match store.swap_dbs(first, second) {
+            Ok(()) => SimpleString(String::from("OK")),
+            Err(err) => Error(format!("ERR {}", err)),
+        }
+    }
+}
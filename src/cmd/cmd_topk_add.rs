@@ -0,0 +1,25 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_top_k, TOPK_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/topk.add/
+pub struct TopKAddCommand;
+
+impl Command for TopKAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let top_k = match get_top_k(store, &args[0]) {
+            Ok(Some(top_k)) => top_k,
+            Ok(None) => return Error(String::from(TOPK_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        // returns the items which got expelled from the top k list
+        let results = args[1..].iter()
+            .map(|item| top_k.increment_by(item, 1).map_or(Null, BulkString))
+            .collect();
+
+        Array(results)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_top_k, TOPK_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/topk.count/
+pub struct TopKCountCommand;
+
+impl Command for TopKCountCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let top_k = match get_top_k(store, &args[0]) {
+            Ok(Some(top_k)) => top_k,
+            Ok(None) => return Error(String::from(TOPK_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        Array(args[1..].iter().map(|item| Integer(top_k.count(item) as i64)).collect())
+    }
+}
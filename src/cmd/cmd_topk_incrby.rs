@@ -0,0 +1,39 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_top_k, TOPK_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Null};
+use crate::store::Store;
+
+/// see https://redis.io/commands/topk.incrby/
+pub struct TopKIncrByCommand;
+
+// every increment is applied one by one to decay other items, so we limit it like redis does
+const MAX_INCREMENT: u64 = 100000;
+
+impl Command for TopKIncrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() % 2 != 1 {
+            return Error(String::from("ERR wrong number of arguments for 'topk.incrby' command"));
+        }
+
+        let mut increments = Vec::new();
+        for pair in args[1..].chunks(2) {
+            match pair[1].parse::<u64>() {
+                Ok(increment) if (1..=MAX_INCREMENT).contains(&increment) => increments.push((pair[0].as_str(), increment)),
+                _ => return Error(format!("ERR TopK: increment must be an integer greater or equal to 1 and less than or equal to {}", MAX_INCREMENT)),
+            }
+        }
+
+        let top_k = match get_top_k(store, &args[0]) {
+            Ok(Some(top_k)) => top_k,
+            Ok(None) => return Error(String::from(TOPK_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let results = increments.into_iter()
+            .map(|(item, increment)| top_k.increment_by(item, increment).map_or(Null, BulkString))
+            .collect();
+
+        Array(results)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_top_k, TOPK_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer, SimpleString};
+use crate::store::Store;
+
+/// see https://redis.io/commands/topk.info/
+pub struct TopKInfoCommand;
+
+impl Command for TopKInfoCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_top_k(store, &args[0]) {
+            Ok(Some(top_k)) => Array(vec![
+                SimpleString(String::from("k")),
+                Integer(top_k.get_k() as i64),
+                SimpleString(String::from("width")),
+                Integer(top_k.get_width() as i64),
+                SimpleString(String::from("depth")),
+                Integer(top_k.get_depth() as i64),
+                SimpleString(String::from("decay")),
+                BulkString(top_k.get_decay().to_string()),
+            ]),
+            Ok(None) => Error(String::from(TOPK_KEY_NOT_FOUND_ERROR)),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::cmd::command::Command;
+use crate::probabilistic::probabilistic_helper::{get_top_k, TOPK_KEY_NOT_FOUND_ERROR};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::store::Store;
+
+/// see https://redis.io/commands/topk.list/
+pub struct TopKListCommand;
+
+impl Command for TopKListCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.is_empty() || args.len() > 2 {
+            return Error(String::from("ERR wrong number of arguments for 'topk.list' command"));
+        }
+
+        let with_count = match args.get(1) {
+            Some(option) if option.eq_ignore_ascii_case("WITHCOUNT") => true,
+            Some(_) => return Error(String::from("ERR syntax error")),
+            None => false,
+        };
+
+        let top_k = match get_top_k(store, &args[0]) {
+            Ok(Some(top_k)) => top_k,
+            Ok(None) => return Error(String::from(TOPK_KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        };
+
+        let mut response = Vec::new();
+        for (item, count) in top_k.list() {
+            response.push(BulkString(item));
+            if with_count {
+                response.push(Integer(count as i64));
+            }
+        }
+        Array(response)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_TOPK};
+use crate::probabilistic::top_k::TopK;
+use crate::probabilistic::probabilistic_helper::get_top_k;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// Restores a top-k persisted in the AOF file.
+/// Redis only persists top-k structures in RDB files, so there is no redis counterpart of this command (the arguments follow BF.LOADCHUNK)
+pub struct TopKLoadChunkCommand;
+
+impl Command for TopKLoadChunkCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args[1].parse::<i64>().is_err() {
+            return Error(String::from("ERR invalid iterator"));
+        }
+
+        if let Err(err) = get_top_k(store, &args[0]) {
+            return err;
+        }
+
+        let filter = match TopK::restore(&args[2]) {
+            Ok(filter) => filter,
+            Err(_) => return Error(String::from("ERR received bad data")),
+        };
+
+        store.put(&args[0], ObjectValue::TopK(Box::new(filter)), -1, OBJ_TYPE_TOPK | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,56 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_TOPK};
+use crate::probabilistic::probabilistic_helper::{get_top_k, parse_integer, TOPK_KEY_EXISTS_ERROR};
+use crate::probabilistic::top_k::{TopK, DEFAULT_DECAY, DEFAULT_DEPTH, DEFAULT_WIDTH};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/topk.reserve/
+pub struct TopKReserveCommand;
+
+impl Command for TopKReserveCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() != 2 && args.len() != 5 {
+            return Error(String::from("ERR wrong number of arguments for 'topk.reserve' command"));
+        }
+
+        let k = match parse_integer(&args[1], "ERR TopK: invalid k") {
+            Ok(k) if k > 0 => k,
+            Ok(_) => return Error(String::from("ERR TopK: invalid k")),
+            Err(err) => return err,
+        };
+
+        let mut width = DEFAULT_WIDTH;
+        let mut depth = DEFAULT_DEPTH;
+        let mut decay = DEFAULT_DECAY;
+        if args.len() == 5 {
+            width = match parse_integer(&args[2], "ERR TopK: invalid width") {
+                Ok(width) if width > 0 => width,
+                Ok(_) => return Error(String::from("ERR TopK: invalid width")),
+                Err(err) => return err,
+            };
+            depth = match parse_integer(&args[3], "ERR TopK: invalid depth") {
+                Ok(depth) if depth > 0 => depth,
+                Ok(_) => return Error(String::from("ERR TopK: invalid depth")),
+                Err(err) => return err,
+            };
+            decay = match args[4].parse::<f64>() {
+                Ok(decay) if decay > 0.0 && decay <= 1.0 => decay,
+                _ => return Error(String::from("ERR TopK: invalid decay value. must be '<= 1' & '> 0'")),
+            };
+        }
+        if width.checked_mul(depth).is_none() {
+            return Error(String::from("ERR TopK: invalid width/depth"));
+        }
+
+        match get_top_k(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(TOPK_KEY_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        store.put(&args[0], ObjectValue::TopK(Box::new(TopK::new(k, width, depth, decay))), -1, OBJ_TYPE_TOPK | OBJ_ENCODING_RAW);
+        SimpleString(String::from("OK"))
+    }
+}
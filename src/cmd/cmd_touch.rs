@@ -0,0 +1,15 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/touch/
+///
+/// Updates the access time of the keys and returns the number of existing keys
+pub struct TouchCommand;
+
+impl Command for TouchCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        Integer(args.iter().filter(|key| store.touch(key)).count() as i64)
+    }
+}
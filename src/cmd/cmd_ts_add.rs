@@ -0,0 +1,37 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+use crate::timeseries::timeseries_helper::{add_sample, create_time_series, get_time_series, parse_create_options, parse_timestamp, parse_value};
+
+/// see https://redis.io/commands/ts.add/
+pub struct TsAddCommand;
+
+impl Command for TsAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let timestamp = match parse_timestamp(&args[1]) {
+            Ok(timestamp) => timestamp,
+            Err(err) => return err,
+        };
+        let value = match parse_value(&args[2]) {
+            Ok(value) => value,
+            Err(err) => return err,
+        };
+        // the options are used to create the series if it does not exist yet
+        let options = match parse_create_options(&args[3..], true) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        match get_time_series(store, &args[0]) {
+            Ok(Some(_)) => {}
+            Ok(None) => create_time_series(store, &args[0], &options),
+            Err(err) => return err,
+        }
+
+        match add_sample(store, &args[0], timestamp, value, options.on_duplicate) {
+            Ok(_) => Integer(timestamp),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+use crate::timeseries::timeseries_helper::{create_time_series, get_time_series, parse_create_options, KEY_EXISTS_ERROR};
+
+/// see https://redis.io/commands/ts.create/
+pub struct TsCreateCommand;
+
+impl Command for TsCreateCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let options = match parse_create_options(&args[1..], false) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        match get_time_series(store, &args[0]) {
+            Ok(Some(_)) => return Error(String::from(KEY_EXISTS_ERROR)),
+            Ok(None) => {}
+            Err(err) => return err,
+        }
+
+        create_time_series(store, &args[0], &options);
+        SimpleString(String::from("OK"))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, SimpleString};
+use crate::store::Store;
+use crate::timeseries::time_series::CompactionRule;
+use crate::timeseries::timeseries_helper::{get_time_series, parse_aggregation, KEY_NOT_FOUND_ERROR};
+
+/// see https://redis.io/commands/ts.createrule/
+pub struct TsCreateRuleCommand;
+
+impl Command for TsCreateRuleCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if !args[2].eq_ignore_ascii_case("AGGREGATION") {
+            return Error(String::from("ERR syntax error"));
+        }
+        let (aggregation, bucket_duration) = match parse_aggregation(&args[3], &args[4]) {
+            Ok(aggregation) => aggregation,
+            Err(err) => return err,
+        };
+
+        let source_key = args[0].clone();
+        let destination_key = args[1].clone();
+        if source_key == destination_key {
+            return Error(String::from("ERR TSDB: the source key and destination key should be different"));
+        }
+
+        match get_time_series(store, &source_key) {
+            Ok(Some(_)) => {}
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        }
+
+        // a destination can only have one source and must not be the source of other rules, which prevents cycles
+        match get_time_series(store, &destination_key) {
+            Ok(Some(destination)) if destination.source_key.is_some() => return Error(String::from("ERR TSDB: the destination key already has a src rule")),
+            Ok(Some(destination)) if !destination.rules.is_empty() => return Error(String::from("ERR TSDB: the destination key already has a dst rule")),
+            Ok(Some(destination)) => destination.source_key = Some(source_key.clone()),
+            Ok(None) => return Error(String::from(KEY_NOT_FOUND_ERROR)),
+            Err(err) => return err,
+        }
+
+        if let Ok(Some(source)) = get_time_series(store, &source_key) {
+            source.rules.push(CompactionRule::new(&destination_key, aggregation, bucket_duration));
+        }
+
+        SimpleString(String::from("OK"))
+    }
+}
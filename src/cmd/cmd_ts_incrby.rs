@@ -0,0 +1,64 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+use crate::timeseries::time_series::DuplicatePolicy;
+use crate::timeseries::timeseries_helper::{add_sample, create_time_series, get_time_series, parse_create_options, parse_timestamp, parse_value};
+
+/// see https://redis.io/commands/ts.incrby/
+pub struct TsIncrByCommand;
+
+impl Command for TsIncrByCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let increment = match parse_value(&args[1]) {
+            Ok(increment) => increment,
+            Err(err) => return err,
+        };
+
+        // TIMESTAMP is the only option which is not an option of TS.CREATE
+        let mut timestamp = None;
+        let mut create_args = Vec::new();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i].eq_ignore_ascii_case("TIMESTAMP") && i + 1 < args.len() {
+                timestamp = match parse_timestamp(&args[i + 1]) {
+                    Ok(timestamp) => Some(timestamp),
+                    Err(err) => return err,
+                };
+                i += 2;
+            } else {
+                create_args.push(args[i].clone());
+                i += 1;
+            }
+        }
+
+        let options = match parse_create_options(&create_args, false) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        let series = match get_time_series(store, &args[0]) {
+            Ok(series) => series,
+            Err(err) => return err,
+        };
+
+        let timestamp = timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let value = match series.map(|series| series.get_last_sample()) {
+            Some(Some((last_timestamp, _))) if timestamp < last_timestamp => {
+                return Error(String::from("ERR TSDB: timestamp must be equal to or higher than the maximum existing timestamp"));
+            }
+            Some(Some((_, last_value))) => last_value + increment,
+            Some(None) => increment,
+            None => {
+                create_time_series(store, &args[0], &options);
+                increment
+            }
+        };
+
+        // the latest sample is replaced, independent of the duplicate policy
+        match add_sample(store, &args[0], timestamp, value, Some(DuplicatePolicy::Last)) {
+            Ok(_) => Integer(timestamp),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Integer};
+use crate::store::Store;
+use crate::timeseries::timeseries_helper::{add_sample, parse_timestamp, parse_value};
+
+/// see https://redis.io/commands/ts.madd/
+pub struct TsMAddCommand;
+
+impl Command for TsMAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() % 3 != 0 {
+            return Error(String::from("ERR wrong number of arguments for 'ts.madd' command"));
+        }
+
+        // every sample is added independently, so a failing sample does not affect the others
+        let results = args.chunks(3)
+            .map(|sample| {
+                let timestamp = parse_timestamp(&sample[1])?;
+                let value = parse_value(&sample[2])?;
+                add_sample(store, &sample[0], timestamp, value, None)?;
+                Ok(timestamp)
+            })
+            .map(|result: Result<i64, DataType>| result.map_or_else(|err| err, Integer))
+            .collect();
+
+        Array(results)
+    }
+}
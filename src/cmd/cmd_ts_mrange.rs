@@ -0,0 +1,52 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString};
+use crate::store::{ObjectValue, Store};
+use crate::timeseries::timeseries_helper::{parse_range_options, parse_range_timestamp, query_range, samples_to_response};
+
+/// see https://redis.io/commands/ts.mrange/
+pub struct TsMRangeCommand;
+
+impl Command for TsMRangeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let from = match parse_range_timestamp(&args[0]) {
+            Ok(from) => from,
+            Err(err) => return err,
+        };
+        let to = match parse_range_timestamp(&args[1]) {
+            Ok(to) => to,
+            Err(err) => return err,
+        };
+        let options = match parse_range_options(&args[2..], true) {
+            Ok(options) => options,
+            Err(err) => return err,
+        };
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut results = Vec::new();
+        for (key, store_object) in store.get_data().iter() {
+            let series = match store_object.value.as_ref() {
+                ObjectValue::TimeSeries(series) => series,
+                _ => continue,
+            };
+            if store.get_expiry(key).is_some_and(|expires_at| expires_at <= now) {
+                continue;
+            }
+            if !options.filters.iter().all(|filter| filter.matches(series)) {
+                continue;
+            }
+
+            let labels = match options.with_labels {
+                true => series.labels.iter().map(|(label, value)| Array(vec![BulkString(label.clone()), BulkString(value.clone())])).collect(),
+                false => Vec::new(),
+            };
+
+            let samples = query_range(series, from, to, &options, false);
+            results.push((key.clone(), Array(vec![BulkString(key.clone()), Array(labels), samples_to_response(samples)])));
+        }
+
+        // the store has no order, so we sort the series by key to have a stable response
+        results.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+        Array(results.into_iter().map(|(_, result)| result).collect())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::Store;
+use crate::timeseries::timeseries_helper::{get_time_series, parse_range_options, parse_range_timestamp, query_range, samples_to_response, KEY_NOT_FOUND_ERROR};
+
+/// see https://redis.io/commands/ts.range/
+pub struct TsRangeCommand;
+
+impl Command for TsRangeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        range(args, store, false)
+    }
+}
+
+/// Shared implementation of TS.RANGE and TS.REVRANGE
+pub fn range(args: &[String], store: &mut Store, reverse: bool) -> DataType {
+    let from = match parse_range_timestamp(&args[1]) {
+        Ok(from) => from,
+        Err(err) => return err,
+    };
+    let to = match parse_range_timestamp(&args[2]) {
+        Ok(to) => to,
+        Err(err) => return err,
+    };
+    let options = match parse_range_options(&args[3..], false) {
+        Ok(options) => options,
+        Err(err) => return err,
+    };
+
+    match get_time_series(store, &args[0]) {
+        Ok(Some(series)) => samples_to_response(query_range(series, from, to, &options, reverse)),
+        Ok(None) => Error(String::from(KEY_NOT_FOUND_ERROR)),
+        Err(err) => err,
+    }
+}
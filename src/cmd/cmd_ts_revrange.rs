@@ -0,0 +1,13 @@
+use crate::cmd::cmd_ts_range::range;
+use crate::cmd::command::Command;
+use crate::resp::DataType;
+use crate::store::Store;
+
+/// see https://redis.io/commands/ts.revrange/
+pub struct TsRevRangeCommand;
+
+impl Command for TsRevRangeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        range(args, store, true)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::cmd::command::Command;
+use crate::object_type_encoding::get_type_name;
+use crate::resp::DataType;
+use crate::resp::DataType::SimpleString;
+use crate::store::Store;
+
+/// see https://redis.io/commands/type/
+pub struct TypeCommand;
+
+impl Command for TypeCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if !store.contains_key(&args[0]) {
+            return SimpleString(String::from("none"));
+        }
+
+        match store.get_data().get(&args[0]) {
+            Some(store_object) => SimpleString(get_type_name(store_object.type_encoding).to_string()),
+            None => SimpleString(String::from("none")),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::cmd::command::Command;
+use crate::lazy_free::free_object;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/unlink/
+///
+/// Like DEL, but large values are freed on a background thread, so the event loop is not blocked
+pub struct UnlinkCommand;
+
+impl Command for UnlinkCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let mut unlinked = 0;
+        for key in args.iter() {
+            // expired keys are removed by the check, but do not count as unlinked
+            if !store.contains_key(key) {
+                continue;
+            }
+            if let Some(store_object) = store.remove(key) {
+                free_object(store_object);
+                unlinked += 1;
+            }
+        }
+
+        Integer(unlinked)
+    }
+}
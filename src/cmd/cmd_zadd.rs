@@ -0,0 +1,41 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::{get_sorted_set_mut, parse_float};
+use crate::object_type_encoding::{OBJ_ENCODING_SKIPLIST, OBJ_TYPE_ZSET};
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::sorted_set::SortedSet;
+use crate::store::{ObjectValue, Store};
+
+/// see https://redis.io/commands/zadd/
+///
+/// ZADD key score member [score member ...]
+/// GEO keys are sorted sets, the AOF rewrite stores them with ZADD
+pub struct ZAddCommand;
+
+impl Command for ZAddCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        if args.len() < 3 || args.len() % 2 == 0 {
+            return Error(String::from("ERR wrong number of arguments for 'zadd' command"));
+        }
+
+        // validate all scores first, so we do not add only a part of the members
+        let mut members = Vec::with_capacity(args.len() / 2);
+        for element in args[1..].chunks(2) {
+            match parse_float(&element[0]) {
+                Ok(score) => members.push((element[1].clone(), score)),
+                Err(err) => return err,
+            }
+        }
+
+        let key = &args[0];
+        match get_sorted_set_mut(store, key) {
+            Ok(Some(_)) => {}
+            Ok(None) => store.put(key, ObjectValue::SortedSet(Box::new(SortedSet::new())), -1, OBJ_TYPE_ZSET | OBJ_ENCODING_SKIPLIST),
+            Err(err) => return err,
+        }
+        let sorted_set = get_sorted_set_mut(store, key).unwrap().expect("Sorted set not found");
+
+        let added = members.iter().filter(|(member, score)| sorted_set.insert(member, *score)).count();
+        Integer(added as i64)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::get_sorted_set;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/zcard/
+pub struct ZCardCommand;
+
+impl Command for ZCardCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        match get_sorted_set(store, &args[0]) {
+            Ok(sorted_set) => Integer(sorted_set.map_or(0, |sorted_set| sorted_set.len()) as i64),
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::cmd::command::Command;
+use crate::geo::geo_helper::get_sorted_set_mut;
+use crate::resp::DataType;
+use crate::resp::DataType::Integer;
+use crate::store::Store;
+
+/// see https://redis.io/commands/zrem/
+pub struct ZRemCommand;
+
+impl Command for ZRemCommand {
+    fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType {
+        let sorted_set = match get_sorted_set_mut(store, &args[0]) {
+            Ok(Some(sorted_set)) => sorted_set,
+            Ok(None) => return Integer(0),
+            Err(err) => return err,
+        };
+
+        let removed = args[1..].iter().filter(|member| sorted_set.remove(member).is_some()).count();
+
+        // empty sorted sets are not kept
+        if sorted_set.is_empty() {
+            store.remove(&args[0]);
+        }
+
+        Integer(removed as i64)
+    }
+}
@@ -3,16 +3,143 @@ use std::str::FromStr;
 use crate::cmd::cmd_bgrewriteaof::BgRewriteAofCommand;
 use crate::cmd::cmd_del::DelCommand;
 use crate::cmd::cmd_expire::ExpireCommand;
+use crate::cmd::cmd_geoadd::GeoAddCommand;
+use crate::cmd::cmd_geodist::GeoDistCommand;
+use crate::cmd::cmd_geohash::GeoHashCommand;
+use crate::cmd::cmd_geopos::GeoPosCommand;
+use crate::cmd::cmd_geosearch::GeoSearchCommand;
+use crate::cmd::cmd_geosearchstore::GeoSearchStoreCommand;
 use crate::cmd::cmd_get::GetCommand;
 use crate::cmd::cmd_incr::IncrCommand;
 use crate::cmd::cmd_info::InfoCommand;
 use crate::cmd::cmd_ping::PingCommand;
 use crate::cmd::cmd_set::SetCommand;
 use crate::cmd::cmd_ttl::TTLCommand;
-use crate::cmd::command::SimpleCommand::{BGREWRITEAOF, DEL, EXPIRE, GET, INCR, INFO, PING, SET, TTL};
+use crate::cmd::cmd_zadd::ZAddCommand;
+use crate::cmd::cmd_zcard::ZCardCommand;
+use crate::cmd::cmd_zrem::ZRemCommand;
+use crate::cmd::cmd_json_set::JsonSetCommand;
+use crate::cmd::cmd_json_get::JsonGetCommand;
+use crate::cmd::cmd_json_del::JsonDelCommand;
+use crate::cmd::cmd_json_mget::JsonMGetCommand;
+use crate::cmd::cmd_json_type::JsonTypeCommand;
+use crate::cmd::cmd_json_numincrby::JsonNumIncrByCommand;
+use crate::cmd::cmd_json_strappend::JsonStrAppendCommand;
+use crate::cmd::cmd_json_arrappend::JsonArrAppendCommand;
+use crate::cmd::cmd_json_arrinsert::JsonArrInsertCommand;
+use crate::cmd::cmd_json_arrpop::JsonArrPopCommand;
+use crate::cmd::cmd_json_arrlen::JsonArrLenCommand;
+use crate::cmd::cmd_json_objkeys::JsonObjKeysCommand;
+use crate::cmd::cmd_bf_reserve::BfReserveCommand;
+use crate::cmd::cmd_bf_add::BfAddCommand;
+use crate::cmd::cmd_bf_madd::BfMAddCommand;
+use crate::cmd::cmd_bf_exists::BfExistsCommand;
+use crate::cmd::cmd_bf_mexists::BfMExistsCommand;
+use crate::cmd::cmd_bf_info::BfInfoCommand;
+use crate::cmd::cmd_bf_scandump::BfScanDumpCommand;
+use crate::cmd::cmd_bf_loadchunk::BfLoadChunkCommand;
+use crate::cmd::cmd_cf_reserve::CfReserveCommand;
+use crate::cmd::cmd_cf_add::CfAddCommand;
+use crate::cmd::cmd_cf_addnx::CfAddNxCommand;
+use crate::cmd::cmd_cf_del::CfDelCommand;
+use crate::cmd::cmd_cf_exists::CfExistsCommand;
+use crate::cmd::cmd_cf_count::CfCountCommand;
+use crate::cmd::cmd_cf_scandump::CfScanDumpCommand;
+use crate::cmd::cmd_cf_loadchunk::CfLoadChunkCommand;
+use crate::cmd::cmd_cf_info::CfInfoCommand;
+use crate::cmd::cmd_cms_initbydim::CmsInitByDimCommand;
+use crate::cmd::cmd_cms_initbyprob::CmsInitByProbCommand;
+use crate::cmd::cmd_cms_incrby::CmsIncrByCommand;
+use crate::cmd::cmd_cms_query::CmsQueryCommand;
+use crate::cmd::cmd_cms_merge::CmsMergeCommand;
+use crate::cmd::cmd_cms_info::CmsInfoCommand;
+use crate::cmd::cmd_cms_loadchunk::CmsLoadChunkCommand;
+use crate::cmd::cmd_topk_reserve::TopKReserveCommand;
+use crate::cmd::cmd_topk_add::TopKAddCommand;
+use crate::cmd::cmd_topk_incrby::TopKIncrByCommand;
+use crate::cmd::cmd_topk_query::TopKQueryCommand;
+use crate::cmd::cmd_topk_list::TopKListCommand;
+use crate::cmd::cmd_topk_info::TopKInfoCommand;
+use crate::cmd::cmd_topk_loadchunk::TopKLoadChunkCommand;
+use crate::cmd::cmd_memory::MemoryCommand;
+use crate::cmd::cmd_topk_count::TopKCountCommand;
+use crate::cmd::cmd_ts_create::TsCreateCommand;
+use crate::cmd::cmd_ts_add::TsAddCommand;
+use crate::cmd::cmd_ts_madd::TsMAddCommand;
+use crate::cmd::cmd_ts_incrby::TsIncrByCommand;
+use crate::cmd::cmd_ts_range::TsRangeCommand;
+use crate::cmd::cmd_ts_revrange::TsRevRangeCommand;
+use crate::cmd::cmd_ts_mrange::TsMRangeCommand;
+use crate::cmd::cmd_ts_createrule::TsCreateRuleCommand;
+use crate::cmd::cmd_hset::HSetCommand;
+use crate::cmd::cmd_hget::HGetCommand;
+use crate::cmd::cmd_hdel::HDelCommand;
+use crate::cmd::cmd_hgetall::HGetAllCommand;
+use crate::cmd::cmd_ft_create::FtCreateCommand;
+use crate::cmd::cmd_ft_search::FtSearchCommand;
+use crate::cmd::cmd_ft_info::FtInfoCommand;
+use crate::cmd::cmd_ft_dropindex::FtDropIndexCommand;
+use crate::cmd::cmd_ft_aggregate::FtAggregateCommand;
+use crate::cmd::cmd_select::SelectCommand;
+use crate::cmd::cmd_move::MoveCommand;
+use crate::cmd::cmd_swapdb::SwapDbCommand;
+use crate::cmd::cmd_flushdb::FlushDbCommand;
+use crate::cmd::cmd_dbsize::DbSizeCommand;
+use crate::cmd::cmd_keys::KeysCommand;
+use crate::cmd::cmd_scan::ScanCommand;
+use crate::cmd::cmd_exists::ExistsCommand;
+use crate::cmd::cmd_type::TypeCommand;
+use crate::cmd::cmd_rename::RenameCommand;
+use crate::cmd::cmd_renamenx::RenameNxCommand;
+use crate::cmd::cmd_copy::CopyCommand;
+use crate::cmd::cmd_randomkey::RandomKeyCommand;
+use crate::cmd::cmd_touch::TouchCommand;
+use crate::cmd::cmd_unlink::UnlinkCommand;
+use crate::cmd::cmd_object::ObjectCommand;
+use crate::cmd::cmd_append::AppendCommand;
+use crate::cmd::cmd_setrange::SetRangeCommand;
+use crate::cmd::cmd_config::ConfigCommand;
+use crate::cmd::cmd_pexpire::PExpireCommand;
+use crate::cmd::cmd_expireat::ExpireAtCommand;
+use crate::cmd::cmd_pexpireat::PExpireAtCommand;
+use crate::cmd::cmd_persist::PersistCommand;
+use crate::cmd::cmd_pttl::PTTLCommand;
+use crate::cmd::cmd_expiretime::ExpireTimeCommand;
+use crate::cmd::cmd_pexpiretime::PExpireTimeCommand;
+use crate::cmd::cmd_setnx::SetNxCommand;
+use crate::cmd::cmd_setex::SetExCommand;
+use crate::cmd::cmd_psetex::PSetExCommand;
+use crate::cmd::cmd_getset::GetSetCommand;
+use crate::cmd::cmd_getdel::GetDelCommand;
+use crate::cmd::cmd_getex::GetExCommand;
+use crate::cmd::cmd_mset::MSetCommand;
+use crate::cmd::cmd_msetnx::MSetNxCommand;
+use crate::cmd::cmd_mget::MGetCommand;
+use crate::cmd::cmd_strlen::StrLenCommand;
+use crate::cmd::cmd_getrange::GetRangeCommand;
+use crate::cmd::cmd_incrby::IncrByCommand;
+use crate::cmd::cmd_decr::DecrCommand;
+use crate::cmd::cmd_decrby::DecrByCommand;
+use crate::cmd::cmd_incrbyfloat::IncrByFloatCommand;
+use crate::cmd::cmd_lcs::LcsCommand;
+use crate::cmd::cmd_command::CommandCommand;
+use crate::cmd::cmd_client::ClientCommand;
+use crate::cmd::command::SimpleCommand::{BGREWRITEAOF, DEL, EXPIRE, GEOADD, GEODIST, GEOHASH, GEOPOS, GEOSEARCH, GEOSEARCHSTORE, GET, INCR, INFO, PING, SET, TTL, ZADD, ZCARD, ZREM, JSONSET, JSONGET, JSONDEL, JSONMGET, JSONTYPE, JSONNUMINCRBY, JSONSTRAPPEND, JSONARRAPPEND, JSONARRINSERT, JSONARRPOP, JSONARRLEN, JSONOBJKEYS, BFRESERVE, BFADD, BFMADD, BFEXISTS, BFMEXISTS, BFINFO, BFSCANDUMP, BFLOADCHUNK, CFRESERVE, CFADD, CFADDNX, CFDEL, CFEXISTS, CFCOUNT, CFSCANDUMP, CFLOADCHUNK, CFINFO, CMSINITBYDIM, CMSINITBYPROB, CMSINCRBY, CMSQUERY, CMSMERGE, CMSINFO, CMSLOADCHUNK, TOPKRESERVE, TOPKADD, TOPKINCRBY, TOPKQUERY, TOPKLIST, TOPKINFO, TOPKLOADCHUNK, MEMORY, TOPKCOUNT, TSCREATE, TSADD, TSMADD, TSINCRBY, TSRANGE, TSREVRANGE, TSMRANGE, TSCREATERULE, HSET, HGET, HDEL, HGETALL, FTCREATE, FTSEARCH, FTINFO, FTDROPINDEX, FTAGGREGATE, SELECT, MOVE, SWAPDB, FLUSHDB, DBSIZE, KEYS, SCAN, EXISTS, TYPE, RENAME, RENAMENX, COPY, RANDOMKEY, TOUCH, UNLINK, OBJECT, APPEND, SETRANGE, CONFIG, PEXPIRE, EXPIREAT, PEXPIREAT, PERSIST, PTTL, EXPIRETIME, PEXPIRETIME, SETNX, SETEX, PSETEX, GETSET, GETDEL, GETEX, MSET, MSETNX, MGET, STRLEN, GETRANGE, INCRBY, DECR, DECRBY, INCRBYFLOAT, LCS, COMMAND, CLIENT};
 use crate::resp::DataType;
 use crate::store::Store;
 
+pub const WRONG_TYPE_ERROR: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+pub const OOM_ERROR: &str = "OOM command not allowed when used memory > 'maxmemory'.";
+
+/// Parses the index of a logical database, used by SELECT, MOVE and SWAPDB
+pub fn parse_db_index(value: &str) -> Result<usize, DataType> {
+    match value.parse::<i64>() {
+        Ok(index) if index >= 0 => Ok(index as usize),
+        Ok(_) => Err(DataType::Error(String::from("ERR DB index is out of range"))),
+        Err(_) => Err(DataType::Error(String::from("ERR value is not an integer or out of range"))),
+    }
+}
+
 pub trait Command {
     fn execute(&self, args: &mut Vec<String>, store: &mut Store) -> DataType;
 }
@@ -28,12 +155,127 @@ pub enum SimpleCommand {
     BGREWRITEAOF,
     INCR,
     INFO,
+    ZADD,
+    ZREM,
+    ZCARD,
+    GEOADD,
+    GEODIST,
+    GEOHASH,
+    GEOPOS,
+    GEOSEARCH,
+    GEOSEARCHSTORE,
+    JSONSET,
+    JSONGET,
+    JSONDEL,
+    JSONMGET,
+    JSONTYPE,
+    JSONNUMINCRBY,
+    JSONSTRAPPEND,
+    JSONARRAPPEND,
+    JSONARRINSERT,
+    JSONARRPOP,
+    JSONARRLEN,
+    JSONOBJKEYS,
+    BFRESERVE,
+    BFADD,
+    BFMADD,
+    BFEXISTS,
+    BFMEXISTS,
+    BFINFO,
+    BFSCANDUMP,
+    BFLOADCHUNK,
+    CFRESERVE,
+    CFADD,
+    CFADDNX,
+    CFDEL,
+    CFEXISTS,
+    CFCOUNT,
+    CFSCANDUMP,
+    CFLOADCHUNK,
+    CFINFO,
+    CMSINITBYDIM,
+    CMSINITBYPROB,
+    CMSINCRBY,
+    CMSQUERY,
+    CMSMERGE,
+    CMSINFO,
+    CMSLOADCHUNK,
+    TOPKRESERVE,
+    TOPKADD,
+    TOPKINCRBY,
+    TOPKQUERY,
+    TOPKLIST,
+    TOPKINFO,
+    TOPKLOADCHUNK,
+    MEMORY,
+    TOPKCOUNT,
+    TSCREATE,
+    TSADD,
+    TSMADD,
+    TSINCRBY,
+    TSRANGE,
+    TSREVRANGE,
+    TSMRANGE,
+    TSCREATERULE,
+    HSET,
+    HGET,
+    HDEL,
+    HGETALL,
+    FTCREATE,
+    FTSEARCH,
+    FTINFO,
+    FTDROPINDEX,
+    FTAGGREGATE,
+    SELECT,
+    MOVE,
+    SWAPDB,
+    FLUSHDB,
+    DBSIZE,
+    KEYS,
+    SCAN,
+    EXISTS,
+    TYPE,
+    RENAME,
+    RENAMENX,
+    COPY,
+    RANDOMKEY,
+    TOUCH,
+    UNLINK,
+    OBJECT,
+    APPEND,
+    SETRANGE,
+    CONFIG,
+    PEXPIRE,
+    EXPIREAT,
+    PEXPIREAT,
+    PERSIST,
+    PTTL,
+    EXPIRETIME,
+    PEXPIRETIME,
+    SETNX,
+    SETEX,
+    PSETEX,
+    GETSET,
+    GETDEL,
+    GETEX,
+    MSET,
+    MSETNX,
+    MGET,
+    STRLEN,
+    GETRANGE,
+    INCRBY,
+    DECR,
+    DECRBY,
+    INCRBYFLOAT,
+    LCS,
+    COMMAND,
+    CLIENT,
 }
 
 impl FromStr for SimpleCommand {
     type Err = ();
     fn from_str(input: &str) -> Result<SimpleCommand, Self::Err> {
-        match input {
+        match input.to_uppercase().as_str() {
             "PING" => Ok(PING),
             "SET" => Ok(SET),
             "GET" => Ok(GET),
@@ -43,6 +285,121 @@ impl FromStr for SimpleCommand {
             "BGREWRITEAOF" => Ok(BGREWRITEAOF),
             "INCR" => Ok(INCR),
             "INFO" => Ok(INFO),
+            "ZADD" => Ok(ZADD),
+            "ZREM" => Ok(ZREM),
+            "ZCARD" => Ok(ZCARD),
+            "GEOADD" => Ok(GEOADD),
+            "GEODIST" => Ok(GEODIST),
+            "GEOHASH" => Ok(GEOHASH),
+            "GEOPOS" => Ok(GEOPOS),
+            "GEOSEARCH" => Ok(GEOSEARCH),
+            "GEOSEARCHSTORE" => Ok(GEOSEARCHSTORE),
+            "JSON.SET" => Ok(JSONSET),
+            "JSON.GET" => Ok(JSONGET),
+            "JSON.DEL" => Ok(JSONDEL),
+            "JSON.MGET" => Ok(JSONMGET),
+            "JSON.TYPE" => Ok(JSONTYPE),
+            "JSON.NUMINCRBY" => Ok(JSONNUMINCRBY),
+            "JSON.STRAPPEND" => Ok(JSONSTRAPPEND),
+            "JSON.ARRAPPEND" => Ok(JSONARRAPPEND),
+            "JSON.ARRINSERT" => Ok(JSONARRINSERT),
+            "JSON.ARRPOP" => Ok(JSONARRPOP),
+            "JSON.ARRLEN" => Ok(JSONARRLEN),
+            "JSON.OBJKEYS" => Ok(JSONOBJKEYS),
+            "BF.RESERVE" => Ok(BFRESERVE),
+            "BF.ADD" => Ok(BFADD),
+            "BF.MADD" => Ok(BFMADD),
+            "BF.EXISTS" => Ok(BFEXISTS),
+            "BF.MEXISTS" => Ok(BFMEXISTS),
+            "BF.INFO" => Ok(BFINFO),
+            "BF.SCANDUMP" => Ok(BFSCANDUMP),
+            "BF.LOADCHUNK" => Ok(BFLOADCHUNK),
+            "CF.RESERVE" => Ok(CFRESERVE),
+            "CF.ADD" => Ok(CFADD),
+            "CF.ADDNX" => Ok(CFADDNX),
+            "CF.DEL" => Ok(CFDEL),
+            "CF.EXISTS" => Ok(CFEXISTS),
+            "CF.COUNT" => Ok(CFCOUNT),
+            "CF.SCANDUMP" => Ok(CFSCANDUMP),
+            "CF.LOADCHUNK" => Ok(CFLOADCHUNK),
+            "CF.INFO" => Ok(CFINFO),
+            "CMS.INITBYDIM" => Ok(CMSINITBYDIM),
+            "CMS.INITBYPROB" => Ok(CMSINITBYPROB),
+            "CMS.INCRBY" => Ok(CMSINCRBY),
+            "CMS.QUERY" => Ok(CMSQUERY),
+            "CMS.MERGE" => Ok(CMSMERGE),
+            "CMS.INFO" => Ok(CMSINFO),
+            "CMS.LOADCHUNK" => Ok(CMSLOADCHUNK),
+            "TOPK.RESERVE" => Ok(TOPKRESERVE),
+            "TOPK.ADD" => Ok(TOPKADD),
+            "TOPK.INCRBY" => Ok(TOPKINCRBY),
+            "TOPK.QUERY" => Ok(TOPKQUERY),
+            "TOPK.LIST" => Ok(TOPKLIST),
+            "TOPK.INFO" => Ok(TOPKINFO),
+            "TOPK.LOADCHUNK" => Ok(TOPKLOADCHUNK),
+            "MEMORY" => Ok(MEMORY),
+            "TOPK.COUNT" => Ok(TOPKCOUNT),
+            "TS.CREATE" => Ok(TSCREATE),
+            "TS.ADD" => Ok(TSADD),
+            "TS.MADD" => Ok(TSMADD),
+            "TS.INCRBY" => Ok(TSINCRBY),
+            "TS.RANGE" => Ok(TSRANGE),
+            "TS.REVRANGE" => Ok(TSREVRANGE),
+            "TS.MRANGE" => Ok(TSMRANGE),
+            "TS.CREATERULE" => Ok(TSCREATERULE),
+            "HSET" => Ok(HSET),
+            "HGET" => Ok(HGET),
+            "HDEL" => Ok(HDEL),
+            "HGETALL" => Ok(HGETALL),
+            "FT.CREATE" => Ok(FTCREATE),
+            "FT.SEARCH" => Ok(FTSEARCH),
+            "FT.INFO" => Ok(FTINFO),
+            "FT.DROPINDEX" => Ok(FTDROPINDEX),
+            "FT.AGGREGATE" => Ok(FTAGGREGATE),
+            "SELECT" => Ok(SELECT),
+            "MOVE" => Ok(MOVE),
+            "SWAPDB" => Ok(SWAPDB),
+            "FLUSHDB" => Ok(FLUSHDB),
+            "DBSIZE" => Ok(DBSIZE),
+            "KEYS" => Ok(KEYS),
+            "SCAN" => Ok(SCAN),
+            "EXISTS" => Ok(EXISTS),
+            "TYPE" => Ok(TYPE),
+            "RENAME" => Ok(RENAME),
+            "RENAMENX" => Ok(RENAMENX),
+            "COPY" => Ok(COPY),
+            "RANDOMKEY" => Ok(RANDOMKEY),
+            "TOUCH" => Ok(TOUCH),
+            "UNLINK" => Ok(UNLINK),
+            "OBJECT" => Ok(OBJECT),
+            "APPEND" => Ok(APPEND),
+            "SETRANGE" => Ok(SETRANGE),
+            "CONFIG" => Ok(CONFIG),
+            "PEXPIRE" => Ok(PEXPIRE),
+            "EXPIREAT" => Ok(EXPIREAT),
+            "PEXPIREAT" => Ok(PEXPIREAT),
+            "PERSIST" => Ok(PERSIST),
+            "PTTL" => Ok(PTTL),
+            "EXPIRETIME" => Ok(EXPIRETIME),
+            "PEXPIRETIME" => Ok(PEXPIRETIME),
+            "SETNX" => Ok(SETNX),
+            "SETEX" => Ok(SETEX),
+            "PSETEX" => Ok(PSETEX),
+            "GETSET" => Ok(GETSET),
+            "GETDEL" => Ok(GETDEL),
+            "GETEX" => Ok(GETEX),
+            "MSET" => Ok(MSET),
+            "MSETNX" => Ok(MSETNX),
+            "MGET" => Ok(MGET),
+            "STRLEN" => Ok(STRLEN),
+            "GETRANGE" => Ok(GETRANGE),
+            "INCRBY" => Ok(INCRBY),
+            "DECR" => Ok(DECR),
+            "DECRBY" => Ok(DECRBY),
+            "INCRBYFLOAT" => Ok(INCRBYFLOAT),
+            "LCS" => Ok(LCS),
+            "COMMAND" => Ok(COMMAND),
+            "CLIENT" => Ok(CLIENT),
             _ => Err(()),
         }
     }
@@ -74,6 +431,121 @@ pub fn get_commands() -> HashMap<SimpleCommand, Box<dyn Command>> {
     commands.insert(BGREWRITEAOF, Box::new(BgRewriteAofCommand));
     commands.insert(INCR, Box::new(IncrCommand));
     commands.insert(INFO, Box::new(InfoCommand));
+    commands.insert(ZADD, Box::new(ZAddCommand));
+    commands.insert(ZREM, Box::new(ZRemCommand));
+    commands.insert(ZCARD, Box::new(ZCardCommand));
+    commands.insert(GEOADD, Box::new(GeoAddCommand));
+    commands.insert(GEODIST, Box::new(GeoDistCommand));
+    commands.insert(GEOHASH, Box::new(GeoHashCommand));
+    commands.insert(GEOPOS, Box::new(GeoPosCommand));
+    commands.insert(GEOSEARCH, Box::new(GeoSearchCommand));
+    commands.insert(GEOSEARCHSTORE, Box::new(GeoSearchStoreCommand));
+    commands.insert(JSONSET, Box::new(JsonSetCommand));
+    commands.insert(JSONGET, Box::new(JsonGetCommand));
+    commands.insert(JSONDEL, Box::new(JsonDelCommand));
+    commands.insert(JSONMGET, Box::new(JsonMGetCommand));
+    commands.insert(JSONTYPE, Box::new(JsonTypeCommand));
+    commands.insert(JSONNUMINCRBY, Box::new(JsonNumIncrByCommand));
+    commands.insert(JSONSTRAPPEND, Box::new(JsonStrAppendCommand));
+    commands.insert(JSONARRAPPEND, Box::new(JsonArrAppendCommand));
+    commands.insert(JSONARRINSERT, Box::new(JsonArrInsertCommand));
+    commands.insert(JSONARRPOP, Box::new(JsonArrPopCommand));
+    commands.insert(JSONARRLEN, Box::new(JsonArrLenCommand));
+    commands.insert(JSONOBJKEYS, Box::new(JsonObjKeysCommand));
+    commands.insert(BFRESERVE, Box::new(BfReserveCommand));
+    commands.insert(BFADD, Box::new(BfAddCommand));
+    commands.insert(BFMADD, Box::new(BfMAddCommand));
+    commands.insert(BFEXISTS, Box::new(BfExistsCommand));
+    commands.insert(BFMEXISTS, Box::new(BfMExistsCommand));
+    commands.insert(BFINFO, Box::new(BfInfoCommand));
+    commands.insert(BFSCANDUMP, Box::new(BfScanDumpCommand));
+    commands.insert(BFLOADCHUNK, Box::new(BfLoadChunkCommand));
+    commands.insert(CFRESERVE, Box::new(CfReserveCommand));
+    commands.insert(CFADD, Box::new(CfAddCommand));
+    commands.insert(CFADDNX, Box::new(CfAddNxCommand));
+    commands.insert(CFDEL, Box::new(CfDelCommand));
+    commands.insert(CFEXISTS, Box::new(CfExistsCommand));
+    commands.insert(CFCOUNT, Box::new(CfCountCommand));
+    commands.insert(CFSCANDUMP, Box::new(CfScanDumpCommand));
+    commands.insert(CFLOADCHUNK, Box::new(CfLoadChunkCommand));
+    commands.insert(CFINFO, Box::new(CfInfoCommand));
+    commands.insert(CMSINITBYDIM, Box::new(CmsInitByDimCommand));
+    commands.insert(CMSINITBYPROB, Box::new(CmsInitByProbCommand));
+    commands.insert(CMSINCRBY, Box::new(CmsIncrByCommand));
+    commands.insert(CMSQUERY, Box::new(CmsQueryCommand));
+    commands.insert(CMSMERGE, Box::new(CmsMergeCommand));
+    commands.insert(CMSINFO, Box::new(CmsInfoCommand));
+    commands.insert(CMSLOADCHUNK, Box::new(CmsLoadChunkCommand));
+    commands.insert(TOPKRESERVE, Box::new(TopKReserveCommand));
+    commands.insert(TOPKADD, Box::new(TopKAddCommand));
+    commands.insert(TOPKINCRBY, Box::new(TopKIncrByCommand));
+    commands.insert(TOPKQUERY, Box::new(TopKQueryCommand));
+    commands.insert(TOPKLIST, Box::new(TopKListCommand));
+    commands.insert(TOPKINFO, Box::new(TopKInfoCommand));
+    commands.insert(TOPKLOADCHUNK, Box::new(TopKLoadChunkCommand));
+    commands.insert(MEMORY, Box::new(MemoryCommand));
+    commands.insert(TOPKCOUNT, Box::new(TopKCountCommand));
+    commands.insert(TSCREATE, Box::new(TsCreateCommand));
+    commands.insert(TSADD, Box::new(TsAddCommand));
+    commands.insert(TSMADD, Box::new(TsMAddCommand));
+    commands.insert(TSINCRBY, Box::new(TsIncrByCommand));
+    commands.insert(TSRANGE, Box::new(TsRangeCommand));
+    commands.insert(TSREVRANGE, Box::new(TsRevRangeCommand));
+    commands.insert(TSMRANGE, Box::new(TsMRangeCommand));
+    commands.insert(TSCREATERULE, Box::new(TsCreateRuleCommand));
+    commands.insert(HSET, Box::new(HSetCommand));
+    commands.insert(HGET, Box::new(HGetCommand));
+    commands.insert(HDEL, Box::new(HDelCommand));
+    commands.insert(HGETALL, Box::new(HGetAllCommand));
+    commands.insert(FTCREATE, Box::new(FtCreateCommand));
+    commands.insert(FTSEARCH, Box::new(FtSearchCommand));
+    commands.insert(FTINFO, Box::new(FtInfoCommand));
+    commands.insert(FTDROPINDEX, Box::new(FtDropIndexCommand));
+    commands.insert(FTAGGREGATE, Box::new(FtAggregateCommand));
+    commands.insert(SELECT, Box::new(SelectCommand));
+    commands.insert(MOVE, Box::new(MoveCommand));
+    commands.insert(SWAPDB, Box::new(SwapDbCommand));
+    commands.insert(FLUSHDB, Box::new(FlushDbCommand));
+    commands.insert(DBSIZE, Box::new(DbSizeCommand));
+    commands.insert(KEYS, Box::new(KeysCommand));
+    commands.insert(SCAN, Box::new(ScanCommand));
+    commands.insert(EXISTS, Box::new(ExistsCommand));
+    commands.insert(TYPE, Box::new(TypeCommand));
+    commands.insert(RENAME, Box::new(RenameCommand));
+    commands.insert(RENAMENX, Box::new(RenameNxCommand));
+    commands.insert(COPY, Box::new(CopyCommand));
+    commands.insert(RANDOMKEY, Box::new(RandomKeyCommand));
+    commands.insert(TOUCH, Box::new(TouchCommand));
+    commands.insert(UNLINK, Box::new(UnlinkCommand));
+    commands.insert(OBJECT, Box::new(ObjectCommand));
+    commands.insert(APPEND, Box::new(AppendCommand));
+    commands.insert(SETRANGE, Box::new(SetRangeCommand));
+    commands.insert(CONFIG, Box::new(ConfigCommand));
+    commands.insert(PEXPIRE, Box::new(PExpireCommand));
+    commands.insert(EXPIREAT, Box::new(ExpireAtCommand));
+    commands.insert(PEXPIREAT, Box::new(PExpireAtCommand));
+    commands.insert(PERSIST, Box::new(PersistCommand));
+    commands.insert(PTTL, Box::new(PTTLCommand));
+    commands.insert(EXPIRETIME, Box::new(ExpireTimeCommand));
+    commands.insert(PEXPIRETIME, Box::new(PExpireTimeCommand));
+    commands.insert(SETNX, Box::new(SetNxCommand));
+    commands.insert(SETEX, Box::new(SetExCommand));
+    commands.insert(PSETEX, Box::new(PSetExCommand));
+    commands.insert(GETSET, Box::new(GetSetCommand));
+    commands.insert(GETDEL, Box::new(GetDelCommand));
+    commands.insert(GETEX, Box::new(GetExCommand));
+    commands.insert(MSET, Box::new(MSetCommand));
+    commands.insert(MSETNX, Box::new(MSetNxCommand));
+    commands.insert(MGET, Box::new(MGetCommand));
+    commands.insert(STRLEN, Box::new(StrLenCommand));
+    commands.insert(GETRANGE, Box::new(GetRangeCommand));
+    commands.insert(INCRBY, Box::new(IncrByCommand));
+    commands.insert(DECR, Box::new(DecrCommand));
+    commands.insert(DECRBY, Box::new(DecrByCommand));
+    commands.insert(INCRBYFLOAT, Box::new(IncrByFloatCommand));
+    commands.insert(LCS, Box::new(LcsCommand));
+    commands.insert(COMMAND, Box::new(CommandCommand));
+    commands.insert(CLIENT, Box::new(ClientCommand));
 
     return commands;
 }
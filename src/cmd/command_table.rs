@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+
+/// Describes every command, like the command table of Redis (see https://redis.io/commands/command/)
+///
+/// The CommandHandler looks up the spec of a request case-insensitively and checks its arity before the command runs,
+/// so commands can rely on the minimal number of arguments. The flags decide e.g. whether a command is rejected above maxmemory.
+///
+/// arity       number of arguments including the command name, a negative arity -n means at least n arguments
+/// first_key   position of the first key (the command name is at 0), 0 if the command has no keys
+/// last_key    position of the last key, negative positions count from the end (-1 is the last argument)
+/// step        distance between two keys, e.g. 2 for MSET key value [key value ...]
+
+// Command flags
+pub const CMD_WRITE: u16 = 0b0000_0001;
+pub const CMD_READONLY: u16 = 0b0000_0010;
+// the command may increase the used memory and is rejected if memory can not be freed below maxmemory
+pub const CMD_DENYOOM: u16 = 0b0000_0100;
+pub const CMD_ADMIN: u16 = 0b0000_1000;
+pub const CMD_FAST: u16 = 0b0001_0000;
+pub const CMD_NOSCRIPT: u16 = 0b0010_0000;
+// the keys can not be found with first_key, last_key and step, see get_movable_keys
+pub const CMD_MOVABLE_KEYS: u16 = 0b0100_0000;
+
+const FLAG_NAMES: [(u16, &str); 7] = [
+    (CMD_WRITE, "write"),
+    (CMD_READONLY, "readonly"),
+    (CMD_DENYOOM, "denyoom"),
+    (CMD_ADMIN, "admin"),
+    (CMD_FAST, "fast"),
+    (CMD_NOSCRIPT, "noscript"),
+    (CMD_MOVABLE_KEYS, "movablekeys"),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub arity: i32,
+    pub flags: u16,
+    pub first_key: i32,
+    pub last_key: i32,
+    pub step: i32,
+    // group of the command in the Redis documentation, also decides the data type ACL category
+    pub group: &'static str,
+    pub summary: &'static str,
+}
+
+/// Builds the entries of the command table, commands without flags or keys leave them at 0
+impl CommandSpec {
+    const fn new(name: &'static str, arity: i32) -> CommandSpec {
+        CommandSpec { name, arity, flags: 0, first_key: 0, last_key: 0, step: 0, group: "", summary: "" }
+    }
+
+    const fn flags(mut self, flags: u16) -> CommandSpec {
+        self.flags = flags;
+        self
+    }
+
+    const fn keys(mut self, first_key: i32, last_key: i32, step: i32) -> CommandSpec {
+        self.first_key = first_key;
+        self.last_key = last_key;
+        self.step = step;
+        self
+    }
+
+    const fn doc(mut self, group: &'static str, summary: &'static str) -> CommandSpec {
+        self.group = group;
+        self.summary = summary;
+        self
+    }
+}
+
+pub const COMMAND_TABLE: &[CommandSpec] = &[
+    // generic
+    CommandSpec::new("del", -2).flags(CMD_WRITE).keys(1, -1, 1).doc("generic", "Deletes one or more keys."),
+    CommandSpec::new("unlink", -2).flags(CMD_WRITE | CMD_FAST).keys(1, -1, 1).doc("generic", "Asynchronously deletes one or more keys."),
+    CommandSpec::new("exists", -2).flags(CMD_READONLY | CMD_FAST).keys(1, -1, 1).doc("generic", "Determines whether one or more keys exist."),
+    CommandSpec::new("touch", -2).flags(CMD_READONLY | CMD_FAST).keys(1, -1, 1).doc("generic", "Returns the number of existing keys out of those specified after updating the time they were last accessed."),
+    CommandSpec::new("type", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("generic", "Determines the type of value stored at a key."),
+    CommandSpec::new("keys", 2).flags(CMD_READONLY).doc("generic", "Returns all key names that match a pattern."),
+    CommandSpec::new("scan", -2).flags(CMD_READONLY).doc("generic", "Iterates over the key names in the database."),
+    CommandSpec::new("randomkey", 1).flags(CMD_READONLY).doc("generic", "Returns a random key name from the database."),
+    CommandSpec::new("rename", 3).flags(CMD_WRITE).keys(1, 2, 1).doc("generic", "Renames a key and overwrites the destination."),
+    CommandSpec::new("renamenx", 3).flags(CMD_WRITE | CMD_FAST).keys(1, 2, 1).doc("generic", "Renames a key only when the target key name doesn't exist."),
+    CommandSpec::new("copy", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 2, 1).doc("generic", "Copies the value of a key to a new key."),
+    CommandSpec::new("move", 3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Moves a key to another database."),
+    CommandSpec::new("object", -2).flags(CMD_READONLY).keys(2, 2, 1).doc("generic", "Returns the internal encoding, idle time or access frequency of a Redis object."),
+    CommandSpec::new("expire", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Sets the expiration time of a key in seconds."),
+    CommandSpec::new("pexpire", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Sets the expiration time of a key in milliseconds."),
+    CommandSpec::new("expireat", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Sets the expiration time of a key to a Unix timestamp."),
+    CommandSpec::new("pexpireat", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Sets the expiration time of a key to a Unix milliseconds timestamp."),
+    CommandSpec::new("persist", 2).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("generic", "Removes the expiration time of a key."),
+    CommandSpec::new("ttl", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("generic", "Returns the expiration time in seconds of a key."),
+    CommandSpec::new("pttl", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("generic", "Returns the expiration time in milliseconds of a key."),
+    CommandSpec::new("expiretime", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("generic", "Returns the expiration time of a key as a Unix timestamp."),
+    CommandSpec::new("pexpiretime", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("generic", "Returns the expiration time of a key as a Unix milliseconds timestamp."),
+    // string
+    CommandSpec::new("set", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("string", "Sets the string value of a key, ignoring its type. The key is created if it doesn't exist."),
+    CommandSpec::new("setnx", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Set the string value of a key only when the key doesn't exist."),
+    CommandSpec::new("setex", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("string", "Sets the string value and expiration time of a key. Creates the key if it doesn't exist."),
+    CommandSpec::new("psetex", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("string", "Sets both string value and expiration time in milliseconds of a key. The key is created if it doesn't exist."),
+    CommandSpec::new("getset", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Returns the previous string value of a key after setting it to a new value."),
+    CommandSpec::new("get", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("string", "Returns the string value of a key."),
+    CommandSpec::new("getdel", 2).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("string", "Returns the string value of a key after deleting the key."),
+    CommandSpec::new("getex", -2).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("string", "Returns the string value of a key after setting its expiration time."),
+    CommandSpec::new("mset", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, -1, 2).doc("string", "Atomically creates or modifies the string values of one or more keys."),
+    CommandSpec::new("msetnx", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, -1, 2).doc("string", "Atomically modifies the string values of one or more keys only when all keys don't exist."),
+    CommandSpec::new("mget", -2).flags(CMD_READONLY | CMD_FAST).keys(1, -1, 1).doc("string", "Atomically returns the string values of one or more keys."),
+    CommandSpec::new("strlen", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("string", "Returns the length of a string value."),
+    CommandSpec::new("getrange", 4).flags(CMD_READONLY).keys(1, 1, 1).doc("string", "Returns a substring of the string stored at a key."),
+    CommandSpec::new("setrange", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("string", "Overwrites a part of a string value with another by an offset. Creates the key if it doesn't exist."),
+    CommandSpec::new("append", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Appends a string to the value of a key. Creates the key if it doesn't exist."),
+    CommandSpec::new("incr", 2).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Increments the integer value of a key by one. Uses 0 as initial value if the key doesn't exist."),
+    CommandSpec::new("incrby", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Increments the integer value of a key by a number. Uses 0 as initial value if the key doesn't exist."),
+    CommandSpec::new("decr", 2).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Decrements the integer value of a key by one. Uses 0 as initial value if the key doesn't exist."),
+    CommandSpec::new("decrby", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Decrements a number from the integer value of a key. Uses 0 as initial value if the key doesn't exist."),
+    CommandSpec::new("incrbyfloat", 3).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("string", "Increment the floating point value of a key by a number. Uses 0 as initial value if the key doesn't exist."),
+    CommandSpec::new("lcs", -3).flags(CMD_READONLY).keys(1, 2, 1).doc("string", "Finds the longest common substring."),
+    // hash
+    CommandSpec::new("hset", -4).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("hash", "Creates or modifies the value of a field in a hash."),
+    CommandSpec::new("hget", 3).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("hash", "Returns the value of a field in a hash."),
+    CommandSpec::new("hdel", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("hash", "Deletes one or more fields and their values from a hash. Deletes the hash if no fields remain."),
+    CommandSpec::new("hgetall", 2).flags(CMD_READONLY).keys(1, 1, 1).doc("hash", "Returns all fields and values in a hash."),
+    // sorted set
+    CommandSpec::new("zadd", -4).flags(CMD_WRITE | CMD_DENYOOM | CMD_FAST).keys(1, 1, 1).doc("sorted-set", "Adds one or more members to a sorted set, or updates their scores. Creates the key if it doesn't exist."),
+    CommandSpec::new("zrem", -3).flags(CMD_WRITE | CMD_FAST).keys(1, 1, 1).doc("sorted-set", "Removes one or more members from a sorted set. Deletes the sorted set if all members were removed."),
+    CommandSpec::new("zcard", 2).flags(CMD_READONLY | CMD_FAST).keys(1, 1, 1).doc("sorted-set", "Returns the number of members in a sorted set."),
+    // geo
+    CommandSpec::new("geoadd", -5).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("geo", "Adds one or more members to a geospatial index. The key is created if it doesn't exist."),
+    CommandSpec::new("geodist", -4).flags(CMD_READONLY).keys(1, 1, 1).doc("geo", "Returns the distance between two members of a geospatial index."),
+    CommandSpec::new("geohash", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("geo", "Returns members from a geospatial index as geohash strings."),
+    CommandSpec::new("geopos", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("geo", "Returns the longitude and latitude of members from a geospatial index."),
+    CommandSpec::new("geosearch", -7).flags(CMD_READONLY).keys(1, 1, 1).doc("geo", "Queries a geospatial index for members inside an area of a box or a circle."),
+    CommandSpec::new("geosearchstore", -8).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 2, 1).doc("geo", "Queries a geospatial index for members inside an area of a box or a circle, optionally stores the result."),
+    // server and connection
+    CommandSpec::new("ping", -1).flags(CMD_FAST).doc("connection", "Returns the server's liveliness response."),
+    CommandSpec::new("select", 2).flags(CMD_FAST).doc("connection", "Changes the selected database."),
+    CommandSpec::new("client", -2).flags(CMD_NOSCRIPT).doc("connection", "Identifies, lists, kills and pauses client connections."),
+    CommandSpec::new("info", -1).doc("server", "Returns information and statistics about the server."),
+    CommandSpec::new("config", -2).flags(CMD_ADMIN | CMD_NOSCRIPT).doc("server", "Returns or sets the effective values of configuration parameters."),
+    CommandSpec::new("memory", -2).flags(CMD_READONLY | CMD_MOVABLE_KEYS).doc("server", "Returns memory usage details and statistics."),
+    CommandSpec::new("bgrewriteaof", 1).flags(CMD_ADMIN | CMD_NOSCRIPT).doc("server", "Asynchronously rewrites the append-only file to disk."),
+    CommandSpec::new("swapdb", 3).flags(CMD_WRITE | CMD_FAST).doc("server", "Swaps two Redis databases."),
+    CommandSpec::new("flushdb", -1).flags(CMD_WRITE).doc("server", "Removes all keys from the current database."),
+    CommandSpec::new("dbsize", 1).flags(CMD_READONLY | CMD_FAST).doc("server", "Returns the number of keys in the database."),
+    CommandSpec::new("command", -1).doc("server", "Returns detailed information about all commands."),
+    // transactions
+    CommandSpec::new("multi", 1).flags(CMD_NOSCRIPT | CMD_FAST).doc("transactions", "Starts a transaction."),
+    CommandSpec::new("exec", 1).flags(CMD_NOSCRIPT).doc("transactions", "Executes all commands in a transaction."),
+    CommandSpec::new("discard", 1).flags(CMD_NOSCRIPT | CMD_FAST).doc("transactions", "Discards a transaction."),
+    // json
+    CommandSpec::new("json.set", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("json", "Sets or updates the JSON value at a path."),
+    CommandSpec::new("json.get", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("json", "Gets the value at one or more paths in JSON serialized form."),
+    CommandSpec::new("json.del", -2).flags(CMD_WRITE).keys(1, 1, 1).doc("json", "Deletes a value."),
+    CommandSpec::new("json.mget", -3).flags(CMD_READONLY).keys(1, -2, 1).doc("json", "Returns the values at a path from one or more keys."),
+    CommandSpec::new("json.type", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("json", "Returns the type of the JSON value at path."),
+    CommandSpec::new("json.numincrby", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("json", "Increments the numeric value at path by a value."),
+    CommandSpec::new("json.strappend", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("json", "Appends a string to a JSON string value at path."),
+    CommandSpec::new("json.arrappend", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("json", "Append one or more JSON values into the array at path after the last element in it."),
+    CommandSpec::new("json.arrinsert", -5).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("json", "Inserts the JSON scalar(s) value at the specified index in the array at path."),
+    CommandSpec::new("json.arrpop", -2).flags(CMD_WRITE).keys(1, 1, 1).doc("json", "Removes and returns the element at the specified index in the array at path."),
+    CommandSpec::new("json.arrlen", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("json", "Returns the length of the array at path."),
+    CommandSpec::new("json.objkeys", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("json", "Returns the JSON keys of the object at path."),
+    // bloom filter
+    CommandSpec::new("bf.reserve", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("bf", "Creates a new Bloom Filter."),
+    CommandSpec::new("bf.add", 3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("bf", "Adds an item to a Bloom Filter."),
+    CommandSpec::new("bf.madd", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("bf", "Adds one or more items to a Bloom Filter. A filter will be created if it does not exist."),
+    CommandSpec::new("bf.exists", 3).flags(CMD_READONLY).keys(1, 1, 1).doc("bf", "Checks whether an item exists in a Bloom Filter."),
+    CommandSpec::new("bf.mexists", -3).flags(CMD_READONLY).keys(1, 1, 1).doc("bf", "Checks whether one or more items exist in a Bloom Filter."),
+    CommandSpec::new("bf.info", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("bf", "Returns information about a Bloom Filter."),
+    CommandSpec::new("bf.scandump", 3).flags(CMD_READONLY).keys(1, 1, 1).doc("bf", "Begins an incremental save of the bloom filter."),
+    CommandSpec::new("bf.loadchunk", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("bf", "Restores a filter previously saved using SCANDUMP."),
+    // cuckoo filter
+    CommandSpec::new("cf.reserve", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cf", "Creates a new Cuckoo Filter."),
+    CommandSpec::new("cf.add", 3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cf", "Adds an item to a Cuckoo Filter."),
+    CommandSpec::new("cf.addnx", 3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cf", "Adds an item to a Cuckoo Filter if the item did not exist previously."),
+    CommandSpec::new("cf.del", 3).flags(CMD_WRITE).keys(1, 1, 1).doc("cf", "Deletes an item from a Cuckoo Filter."),
+    CommandSpec::new("cf.exists", 3).flags(CMD_READONLY).keys(1, 1, 1).doc("cf", "Checks if one item exists in a Cuckoo Filter."),
+    CommandSpec::new("cf.count", 3).flags(CMD_READONLY).keys(1, 1, 1).doc("cf", "Return the number of times an item might be in a Cuckoo Filter."),
+    CommandSpec::new("cf.scandump", 3).flags(CMD_READONLY).keys(1, 1, 1).doc("cf", "Begins an incremental save of the bloom filter."),
+    CommandSpec::new("cf.loadchunk", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cf", "Restores a filter previously saved using SCANDUMP."),
+    CommandSpec::new("cf.info", 2).flags(CMD_READONLY).keys(1, 1, 1).doc("cf", "Returns information about a Cuckoo Filter."),
+    // count-min sketch
+    CommandSpec::new("cms.initbydim", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cms", "Initializes a Count-Min Sketch to dimensions specified by user."),
+    CommandSpec::new("cms.initbyprob", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cms", "Initializes a Count-Min Sketch to accommodate requested tolerances."),
+    CommandSpec::new("cms.incrby", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cms", "Increases the count of one or more items by increment."),
+    CommandSpec::new("cms.query", -3).flags(CMD_READONLY).keys(1, 1, 1).doc("cms", "Returns the count for one or more items in a sketch."),
+    CommandSpec::new("cms.merge", -4).flags(CMD_WRITE | CMD_DENYOOM | CMD_MOVABLE_KEYS).keys(1, 1, 1).doc("cms", "Merges several sketches into one sketch."),
+    CommandSpec::new("cms.info", 2).flags(CMD_READONLY).keys(1, 1, 1).doc("cms", "Returns information about a sketch."),
+    CommandSpec::new("cms.loadchunk", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("cms", "Restores a sketch previously saved using its serialized form."),
+    // top-k
+    CommandSpec::new("topk.reserve", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("topk", "Initializes a TopK with specified parameters."),
+    CommandSpec::new("topk.add", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("topk", "Increases the count of one or more items by increment."),
+    CommandSpec::new("topk.incrby", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("topk", "Increases the count of one or more items by increment."),
+    CommandSpec::new("topk.query", -3).flags(CMD_READONLY).keys(1, 1, 1).doc("topk", "Checks whether one or more items are in a sketch."),
+    CommandSpec::new("topk.count", -3).flags(CMD_READONLY).keys(1, 1, 1).doc("topk", "Return the count for one or more items are in a sketch."),
+    CommandSpec::new("topk.list", -2).flags(CMD_READONLY).keys(1, 1, 1).doc("topk", "Return full list of items in Top K list."),
+    CommandSpec::new("topk.info", 2).flags(CMD_READONLY).keys(1, 1, 1).doc("topk", "Returns information about a sketch."),
+    CommandSpec::new("topk.loadchunk", 4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("topk", "Restores a sketch previously saved using its serialized form."),
+    // time series
+    CommandSpec::new("ts.create", -2).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("timeseries", "Create a new time series."),
+    CommandSpec::new("ts.add", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("timeseries", "Append a sample to a time series."),
+    CommandSpec::new("ts.madd", -4).flags(CMD_WRITE | CMD_DENYOOM).keys(1, -1, 3).doc("timeseries", "Append new samples to one or more time series."),
+    CommandSpec::new("ts.incrby", -3).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 1, 1).doc("timeseries", "Increase the value of the sample with the maximum existing timestamp, or create a new sample with a value equal to the value of the sample with the maximum existing timestamp with a given increment."),
+    CommandSpec::new("ts.range", -4).flags(CMD_READONLY).keys(1, 1, 1).doc("timeseries", "Query a range in forward direction."),
+    CommandSpec::new("ts.revrange", -4).flags(CMD_READONLY).keys(1, 1, 1).doc("timeseries", "Query a range in reverse direction."),
+    CommandSpec::new("ts.mrange", -5).flags(CMD_READONLY).doc("timeseries", "Query a range across multiple time series by filters in forward direction."),
+    CommandSpec::new("ts.createrule", 6).flags(CMD_WRITE | CMD_DENYOOM).keys(1, 2, 1).doc("timeseries", "Create a compaction rule."),
+    // search
+    CommandSpec::new("ft.create", -5).flags(CMD_WRITE | CMD_DENYOOM).doc("search", "Creates an index with the given spec."),
+    CommandSpec::new("ft.search", -3).flags(CMD_READONLY).doc("search", "Searches the index with a textual query, returning either documents or just ids."),
+    CommandSpec::new("ft.info", 2).flags(CMD_READONLY).doc("search", "Returns information and statistics on the index."),
+    CommandSpec::new("ft.dropindex", -2).flags(CMD_WRITE).doc("search", "Deletes the index."),
+    CommandSpec::new("ft.aggregate", -3).flags(CMD_READONLY).doc("search", "Run a search query on an index and perform aggregate transformations on the results."),
+];
+
+lazy_static! {
+    static ref COMMANDS_BY_NAME: HashMap<&'static str, &'static CommandSpec> = {
+        COMMAND_TABLE.iter().map(|spec| (spec.name, spec)).collect()
+    };
+}
+
+/// Looks up the spec of a command, the name is case-insensitive
+pub fn lookup_command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS_BY_NAME.get(name.to_lowercase().as_str()).copied()
+}
+
+impl CommandSpec {
+    pub fn has_flag(&self, flag: u16) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Whether the command accepts the number of arguments, including the command name
+    pub fn check_arity(&self, argc: usize) -> bool {
+        match self.arity {
+            arity if arity < 0 => argc >= arity.unsigned_abs() as usize,
+            arity => argc == arity as usize,
+        }
+    }
+
+    pub fn get_flag_names(&self) -> Vec<&'static str> {
+        FLAG_NAMES.iter()
+            .filter(|(flag, _)| self.has_flag(*flag))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// ACL categories derived from the flags and the group, like Redis does for commands without explicit categories
+    pub fn get_acl_categories(&self) -> Vec<&'static str> {
+        let mut categories = Vec::new();
+        if self.has_flag(CMD_WRITE) {
+            categories.push("@write");
+        }
+        if self.has_flag(CMD_READONLY) {
+            categories.push("@read");
+        }
+        if self.has_flag(CMD_ADMIN) {
+            categories.push("@admin");
+            categories.push("@dangerous");
+        }
+        categories.push(if self.has_flag(CMD_FAST) { "@fast" } else { "@slow" });
+
+        let group_category = match self.group {
+            "generic" => Some("@keyspace"),
+            "string" => Some("@string"),
+            "hash" => Some("@hash"),
+            "sorted-set" => Some("@sortedset"),
+            "geo" => Some("@geo"),
+            "connection" => Some("@connection"),
+            "transactions" => Some("@transaction"),
+            "json" => Some("@json"),
+            "bf" => Some("@bloom"),
+            "cf" => Some("@cuckoo"),
+            "cms" => Some("@cms"),
+            "topk" => Some("@topk"),
+            "timeseries" => Some("@timeseries"),
+            "search" => Some("@search"),
+            _ => None,
+        };
+        categories.extend(group_category);
+        categories
+    }
+
+    /// Positions of the keys in the arguments of a request, the command name is at position 0.
+    /// The arguments must satisfy the arity of the command
+    pub fn get_key_positions(&self, args: &[String]) -> Vec<usize> {
+        if self.has_flag(CMD_MOVABLE_KEYS) {
+            return self.get_movable_keys(args);
+        }
+        if self.first_key == 0 {
+            return Vec::new();
+        }
+
+        let last_key = match self.last_key {
+            last_key if last_key < 0 => args.len() as i32 + last_key,
+            last_key => last_key.min(args.len() as i32 - 1),
+        };
+        (self.first_key..=last_key)
+            .step_by(self.step as usize)
+            .map(|position| position as usize)
+            .collect()
+    }
+
+    fn get_movable_keys(&self, args: &[String]) -> Vec<usize> {
+        match self.name {
+            // CMS.MERGE destination numKeys source [source ...] [WEIGHTS weight [weight ...]]
+            "cms.merge" => {
+                let number_of_sources = args[2].parse::<usize>().unwrap_or(0).min(args.len() - 3);
+                std::iter::once(1).chain(3..3 + number_of_sources).collect()
+            }
+            // only MEMORY USAGE key [SAMPLES count] has a key
+            "memory" if args.len() > 2 && args[1].eq_ignore_ascii_case("USAGE") => vec![2],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::command::{get_commands, SimpleCommand};
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+    use crate::cmd::transaction::TransactionCommand;
+    use crate::resp::DataType::Error;
+    use crate::store::Store;
+
+    fn to_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_every_command_has_a_spec() {
+        // given
+        let commands = get_commands();
+
+        // when
+        let dispatched = COMMAND_TABLE.iter()
+            .filter(|spec| spec.name.parse::<SimpleCommand>().is_ok())
+            .count();
+        let transaction = COMMAND_TABLE.iter()
+            .filter(|spec| spec.name.parse::<TransactionCommand>().is_ok())
+            .count();
+
+        // then
+        assert_eq!(dispatched, commands.len());
+        assert_eq!(dispatched + transaction, COMMAND_TABLE.len());
+        assert_eq!(COMMANDS_BY_NAME.len(), COMMAND_TABLE.len());
+    }
+
+    #[test]
+    fn test_lookup_command() {
+        assert_eq!(lookup_command("GET").map(|spec| spec.name), Some("get"));
+        assert_eq!(lookup_command("Json.Set").map(|spec| spec.name), Some("json.set"));
+        assert!(lookup_command("unknown").is_none());
+    }
+
+    #[test]
+    fn test_check_arity() {
+        // given
+        let get = lookup_command("get").unwrap();
+        let set = lookup_command("set").unwrap();
+
+        // then
+        assert!(get.check_arity(2));
+        assert!(!get.check_arity(1) && !get.check_arity(3));
+        assert!(set.check_arity(3) && set.check_arity(6));
+        assert!(!set.check_arity(2));
+    }
+
+    #[test]
+    fn test_arity_is_checked_before_execution() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+
+        // when
+        let append = execute(&mut handler, &mut store, "APPEND key");
+        let touch = execute(&mut handler, &mut store, "TOUCH");
+        let dbsize = execute(&mut handler, &mut store, "DBSIZE extra");
+
+        // then
+        assert_eq!(append, Error(String::from("ERR wrong number of arguments for 'append' command")));
+        assert_eq!(touch, Error(String::from("ERR wrong number of arguments for 'touch' command")));
+        assert_eq!(dbsize, Error(String::from("ERR wrong number of arguments for 'dbsize' command")));
+    }
+
+    #[test]
+    fn test_get_key_positions() {
+        let key_positions = |args: &str| lookup_command(args.split_whitespace().next().unwrap()).unwrap().get_key_positions(&to_args(args));
+
+        assert_eq!(key_positions("GET key"), vec![1]);
+        assert_eq!(key_positions("MSET a 1 b 2"), vec![1, 3]);
+        assert_eq!(key_positions("DEL a b c"), vec![1, 2, 3]);
+        assert_eq!(key_positions("JSON.MGET a b $"), vec![1, 2]);
+        assert_eq!(key_positions("OBJECT ENCODING key"), vec![2]);
+        assert_eq!(key_positions("CMS.MERGE dest 2 a b WEIGHTS 1 2"), vec![1, 3, 4]);
+        assert_eq!(key_positions("MEMORY USAGE key SAMPLES 5"), vec![2]);
+        assert!(key_positions("MEMORY STATS").is_empty());
+        assert!(key_positions("OBJECT HELP").is_empty());
+        assert!(key_positions("PING").is_empty());
+    }
+
+    #[test]
+    fn test_get_acl_categories() {
+        assert_eq!(lookup_command("get").unwrap().get_acl_categories(), vec!["@read", "@fast", "@string"]);
+        assert_eq!(lookup_command("config").unwrap().get_acl_categories(), vec!["@admin", "@dangerous", "@slow"]);
+    }
+}
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
+use std::time::Instant;
 use DataType::Error;
-use crate::client::ClientConnection;
+use crate::client::{ClientConnection, PauseMode};
 
-use crate::cmd::command::{Command, get_commands, is_simple_command, SimpleCommand};
+use crate::cmd::command::{Command, get_commands, is_simple_command, OOM_ERROR, SimpleCommand};
+use crate::cmd::command_table::{CMD_DENYOOM, CMD_WRITE, CommandSpec, lookup_command};
 use crate::cmd::transaction::{is_transaction_command, TransactionCommand};
 use crate::resp::{DataType, RESPParser};
-use crate::resp::DataType::{BulkString, SimpleString};
+use crate::resp::DataType::{Array, BulkString, Integer, Null, SimpleString};
 use crate::store::Store;
 
 const OK: &str = "OK";
@@ -31,17 +33,49 @@ impl CommandHandler {
 
     /// Handle commands in a pipeline
     pub fn handle_bulk(&mut self, connection: &mut ClientConnection, store: &mut Store) {
-        let mut cmd_requests = self.parser.decode_next_bulk(&mut connection.stream).expect("Can not decode data type");
+        let cmd_requests = self.parser.decode_next_bulk(&mut connection.stream).expect("Can not decode data type");
         println!("Received commands: {:?}", cmd_requests);
 
+        connection.pending_requests_size += cmd_requests.iter().map(get_encoded_size).sum::<usize>();
+        connection.pending_requests.extend(cmd_requests);
+        if let Some(client) = store.get_clients_mut().get_mut(connection.id) {
+            client.last_interaction = Instant::now();
+        }
+        self.handle_pending_requests(connection, store);
+    }
+
+    /// Executes the requests of the connection in order, until a request has to wait for the end of a client pause
+    pub fn handle_pending_requests(&mut self, connection: &mut ClientConnection, store: &mut Store) {
+        // the store is shared by all connections, so the database and the client of this connection are selected before its commands run
+        store.select_db(connection.selected_db).expect("Selected database does not exist");
+        store.get_clients_mut().set_current_client(Some(connection.id));
+
         let mut results = Vec::new();
-        for cmd_request in cmd_requests.drain(..) {
+        while let Some(cmd_request) = connection.pending_requests.front() {
+            if is_paused(cmd_request, connection, store) || is_killed(connection, store) {
+                break;
+            }
+            let cmd_request = connection.pending_requests.pop_front().unwrap();
+            connection.pending_requests_size -= get_encoded_size(&cmd_request);
+            update_client_info(connection, store);
+
             if cmd_request.as_array().is_none() {
                 results.push(Error(NOT_SUPPORTED_COMMAND.to_string()));
                 continue;
             }
 
             let request = cmd_request.as_array().unwrap();
+            // invalid commands are rejected right away, also inside of a transaction
+            let spec = match lookup_spec(request) {
+                Ok(spec) => spec,
+                Err(err) => {
+                    results.push(err);
+                    continue;
+                }
+            };
+            if let Some(client) = store.get_clients_mut().get_current_client_mut() {
+                client.last_command = spec.name.to_string();
+            }
             let command = &request[0];
 
             if let Some(transaction_cmd) = is_transaction_command(command) {
@@ -56,8 +90,18 @@ impl CommandHandler {
             }
         }
 
-        self.parser.write_to_stream(&mut connection.stream, results);
+        connection.selected_db = store.get_selected_db();
+        update_client_info(connection, store);
+        store.get_clients_mut().set_current_client(None);
+
+        if results.is_empty() {
+            return;
+        }
+        let written_bytes = self.parser.write_to_stream(&mut connection.stream, results);
         self.parser.flush_stream(&mut connection.stream);
+        if let Some(client) = store.get_clients_mut().get_mut(connection.id) {
+            client.total_net_out += written_bytes as u64;
+        }
     }
 
     pub fn handle_simple_command_request(&mut self, cmd_request: DataType, store: &mut Store) -> DataType {
@@ -66,9 +110,10 @@ impl CommandHandler {
         }
 
         let request = cmd_request.as_array().unwrap();
-        if request.len() < 1 {
-            return Error(NOT_SUPPORTED_COMMAND.to_string());
-        }
+        let spec = match lookup_spec(request) {
+            Ok(spec) => spec,
+            Err(err) => return err,
+        };
 
         if is_simple_command(&request[0]).is_none() {
             return Error(NOT_SUPPORTED_COMMAND.to_string());
@@ -81,6 +126,11 @@ impl CommandHandler {
             return Error(WRONG_ARGUMENT_TYPE.to_string());
         }
 
+        // keys are evicted before the command runs, so it can use the freed memory
+        if spec.has_flag(CMD_DENYOOM) && !store.perform_evictions() {
+            return Error(OOM_ERROR.to_string());
+        }
+
         return self.execute_simple_command(&command, &mut args.unwrap(), store);
     }
 
@@ -139,3 +189,72 @@ impl CommandHandler {
         Some(result)
     }
 }
+
+/// Looks up the spec of the requested command (case-insensitive) and checks its number of arguments,
+/// so commands are only executed with at least the arguments they need
+fn lookup_spec(request: &[DataType]) -> Result<&'static CommandSpec, DataType> {
+    let spec = match request.first() {
+        Some(BulkString(name)) | Some(SimpleString(name)) => lookup_command(name),
+        _ => None,
+    };
+    let spec = match spec {
+        Some(spec) => spec,
+        None => return Err(Error(NOT_SUPPORTED_COMMAND.to_string())),
+    };
+
+    if !spec.check_arity(request.len()) {
+        return Err(Error(format!("ERR wrong number of arguments for '{}' command", spec.name)));
+    }
+    Ok(spec)
+}
+
+/// While clients are paused, all requests wait or with CLIENT PAUSE WRITE only write commands.
+/// EXEC waits if the transaction contains a write command, queueing commands is always allowed
+fn is_paused(cmd_request: &DataType, connection: &ClientConnection, store: &Store) -> bool {
+    let mode = match store.get_clients().get_pause_mode() {
+        Some(mode) => mode,
+        None => return false,
+    };
+    if mode == PauseMode::All {
+        return true;
+    }
+
+    let is_write_command = |request: &DataType| request.as_array()
+        .and_then(|request| lookup_spec(request).ok())
+        .is_some_and(|spec| spec.has_flag(CMD_WRITE));
+    let command = match cmd_request.as_array().and_then(|request| request.first()) {
+        Some(command) => command,
+        None => return false,
+    };
+    match is_transaction_command(command) {
+        Some(TransactionCommand::EXEC) => connection.cmd_queue.iter().any(is_write_command),
+        Some(_) => false,
+        None => !connection.is_transaction_active && is_write_command(cmd_request),
+    }
+}
+
+/// A killed client does not execute further requests, it is closed after the replies were written
+fn is_killed(connection: &ClientConnection, store: &Store) -> bool {
+    store.get_clients().get(connection.id).is_some_and(|client| client.killed)
+}
+
+fn update_client_info(connection: &ClientConnection, store: &mut Store) {
+    let selected_db = store.get_selected_db();
+    if let Some(client) = store.get_clients_mut().get_mut(connection.id) {
+        client.db = selected_db;
+        client.multi = if connection.is_transaction_active { Some(connection.cmd_queue.len()) } else { None };
+        client.blocked = !connection.pending_requests.is_empty();
+        client.query_buffer_size = connection.pending_requests_size;
+    }
+}
+
+/// Size of the request as sent by the client, e.g. *1\r\n$4\r\nPING\r\n
+fn get_encoded_size(request: &DataType) -> usize {
+    match request {
+        SimpleString(value) | Error(value) => value.len() + 3,
+        BulkString(value) => value.len().to_string().len() + value.len() + 5,
+        Array(values) => values.len().to_string().len() + 3 + values.iter().map(get_encoded_size).sum::<usize>(),
+        Integer(value) => value.to_string().len() + 3,
+        Null => 5,
+    }
+}
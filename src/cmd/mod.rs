@@ -1,6 +1,9 @@
 pub mod handler;
 pub mod command;
+pub mod command_table;
 mod transaction;
+#[cfg(test)]
+pub mod test_helper;
 
 mod cmd_ping;
 mod cmd_set;
@@ -8,6 +11,121 @@ mod cmd_get;
 mod cmd_ttl;
 mod cmd_del;
 mod cmd_expire;
-mod cmd_bgrewriteaof;
+pub mod cmd_bgrewriteaof;
 mod cmd_incr;
 mod cmd_info;
+mod cmd_zadd;
+mod cmd_zrem;
+mod cmd_zcard;
+mod cmd_geoadd;
+mod cmd_geodist;
+mod cmd_geohash;
+mod cmd_geopos;
+mod cmd_geosearch;
+mod cmd_geosearchstore;
+mod cmd_json_set;
+mod cmd_json_get;
+mod cmd_json_del;
+mod cmd_json_mget;
+mod cmd_json_type;
+mod cmd_json_numincrby;
+mod cmd_json_strappend;
+mod cmd_json_arrappend;
+mod cmd_json_arrinsert;
+mod cmd_json_arrpop;
+mod cmd_json_arrlen;
+mod cmd_json_objkeys;
+mod cmd_bf_reserve;
+mod cmd_bf_add;
+mod cmd_bf_madd;
+mod cmd_bf_exists;
+mod cmd_bf_mexists;
+mod cmd_bf_info;
+mod cmd_bf_scandump;
+mod cmd_bf_loadchunk;
+mod cmd_cf_reserve;
+mod cmd_cf_add;
+mod cmd_cf_addnx;
+mod cmd_cf_del;
+mod cmd_cf_exists;
+mod cmd_cf_count;
+mod cmd_cf_scandump;
+mod cmd_cf_loadchunk;
+mod cmd_cf_info;
+mod cmd_cms_initbydim;
+mod cmd_cms_initbyprob;
+mod cmd_cms_incrby;
+mod cmd_cms_query;
+mod cmd_cms_merge;
+mod cmd_cms_info;
+mod cmd_cms_loadchunk;
+mod cmd_topk_reserve;
+mod cmd_topk_add;
+mod cmd_topk_incrby;
+mod cmd_topk_query;
+mod cmd_topk_list;
+mod cmd_topk_info;
+mod cmd_topk_loadchunk;
+mod cmd_memory;
+mod cmd_topk_count;
+mod cmd_ts_create;
+mod cmd_ts_add;
+mod cmd_ts_madd;
+mod cmd_ts_incrby;
+mod cmd_ts_range;
+mod cmd_ts_revrange;
+mod cmd_ts_mrange;
+mod cmd_ts_createrule;
+mod cmd_hset;
+mod cmd_hget;
+mod cmd_hdel;
+mod cmd_hgetall;
+mod cmd_ft_create;
+mod cmd_ft_search;
+mod cmd_ft_info;
+mod cmd_ft_dropindex;
+mod cmd_ft_aggregate;
+mod cmd_select;
+mod cmd_move;
+mod cmd_swapdb;
+mod cmd_flushdb;
+mod cmd_dbsize;
+mod cmd_keys;
+mod cmd_scan;
+mod cmd_exists;
+mod cmd_type;
+mod cmd_rename;
+mod cmd_renamenx;
+mod cmd_copy;
+mod cmd_randomkey;
+mod cmd_touch;
+mod cmd_unlink;
+mod cmd_object;
+mod cmd_append;
+mod cmd_setrange;
+mod cmd_config;
+mod cmd_pexpire;
+mod cmd_expireat;
+mod cmd_pexpireat;
+mod cmd_persist;
+mod cmd_pttl;
+mod cmd_expiretime;
+mod cmd_pexpiretime;
+mod cmd_setnx;
+mod cmd_setex;
+mod cmd_psetex;
+mod cmd_getset;
+mod cmd_getdel;
+mod cmd_getex;
+mod cmd_mset;
+mod cmd_msetnx;
+mod cmd_mget;
+mod cmd_strlen;
+mod cmd_getrange;
+mod cmd_incrby;
+mod cmd_decr;
+mod cmd_decrby;
+mod cmd_incrbyfloat;
+mod cmd_lcs;
+mod cmd_command;
+mod cmd_client;
@@ -0,0 +1,9 @@
+use crate::cmd::handler::CommandHandler;
+use crate::resp::DataType;
+use crate::store::Store;
+
+/// Executes a request like a client would send it, the arguments are separated by whitespace
+pub fn execute(handler: &mut CommandHandler, store: &mut Store, request: &str) -> DataType {
+    let args = request.split_whitespace().map(|arg| DataType::BulkString(arg.to_string())).collect();
+    handler.handle_simple_command_request(DataType::Array(args), store)
+}
@@ -12,7 +12,7 @@ pub enum TransactionCommand {
 impl FromStr for TransactionCommand {
     type Err = ();
     fn from_str(input: &str) -> Result<TransactionCommand, Self::Err> {
-        match input {
+        match input.to_uppercase().as_str() {
             "MULTI" => Ok(TransactionCommand::MULTI),
             "EXEC" => Ok(TransactionCommand::EXEC),
             "DISCARD" => Ok(TransactionCommand::DISCARD),
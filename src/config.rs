@@ -0,0 +1,133 @@
+/// Server configuration, passed as command line arguments (e.g. `kataradb --databases 32`)
+///
+/// databases           number of logical databases, clients select one with SELECT (default 16)
+/// maxmemory           memory limit which triggers eviction, e.g. 100mb or 1gb (default 0, no limit)
+/// maxmemory-policy    which keys are evicted when maxmemory is reached, e.g. allkeys-lru (default noeviction)
+/// active-expire-effort  effort of the active expiration of keys from 1 to 10 (default 1)
+/// active-expire-index   yes to expire keys in the order of an index instead of random sampling (default no)
+
+use crate::active_expiration::{DEFAULT_ACTIVE_EXPIRE_EFFORT, MAX_ACTIVE_EXPIRE_EFFORT};
+use crate::eviction::eviction::EvictionPolicy;
+
+pub const DEFAULT_DATABASES: usize = 16;
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub databases: usize,
+    pub maxmemory: u64,
+    pub maxmemory_policy: EvictionPolicy,
+    pub active_expire_effort: u32,
+    pub active_expire_index: bool,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config {
+            databases: DEFAULT_DATABASES,
+            maxmemory: 0,
+            maxmemory_policy: EvictionPolicy::NoEviction,
+            active_expire_effort: DEFAULT_ACTIVE_EXPIRE_EFFORT,
+            active_expire_index: false,
+        }
+    }
+
+    /// Parses `--name value` pairs, options which are not given keep their default value
+    pub fn parse(args: &[String]) -> Result<Config, String> {
+        let mut config = Config::new();
+
+        for pair in args.chunks(2) {
+            let value = match pair.get(1) {
+                Some(value) => value,
+                None => return Err(format!("Missing value for option {}", pair[0])),
+            };
+
+            match pair[0].to_lowercase().as_str() {
+                "--databases" => {
+                    config.databases = match value.parse::<usize>() {
+                        Ok(databases) if databases > 0 => databases,
+                        _ => return Err(format!("Invalid number of databases: {}", value)),
+                    };
+                }
+                "--maxmemory" => {
+                    config.maxmemory = parse_memory(value).ok_or(format!("Invalid maxmemory: {}", value))?;
+                }
+                "--maxmemory-policy" => {
+                    config.maxmemory_policy = EvictionPolicy::parse(value).ok_or(format!("Invalid maxmemory-policy: {}", value))?;
+                }
+                "--active-expire-effort" => {
+                    config.active_expire_effort = parse_active_expire_effort(value).ok_or(format!("Invalid active-expire-effort: {}", value))?;
+                }
+                "--active-expire-index" => {
+                    config.active_expire_index = parse_bool(value).ok_or(format!("Invalid active-expire-index: {}", value))?;
+                }
+                _ => return Err(format!("Unknown option {}", pair[0])),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parses a yes/no value like Redis does for boolean configuration parameters
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses the effort of the active expiration, which has to be between 1 and 10
+pub fn parse_active_expire_effort(value: &str) -> Option<u32> {
+    value.parse::<u32>().ok().filter(|effort| (DEFAULT_ACTIVE_EXPIRE_EFFORT..=MAX_ACTIVE_EXPIRE_EFFORT).contains(effort))
+}
+
+/// Parses a number of bytes with an optional unit like Redis does, k/m/g are powers of 1000 and kb/mb/gb powers of 1024
+pub fn parse_memory(value: &str) -> Option<u64> {
+    let value = value.to_lowercase();
+    let units: [(&str, u64); 6] = [("kb", 1024), ("mb", 1024 * 1024), ("gb", 1024 * 1024 * 1024), ("k", 1000), ("m", 1000 * 1000), ("g", 1000 * 1000 * 1000)];
+
+    for (unit, multiplier) in units.iter() {
+        if let Some(number) = value.strip_suffix(unit) {
+            return number.parse::<u64>().ok()?.checked_mul(*multiplier);
+        }
+    }
+    value.strip_suffix('b').unwrap_or(&value).parse::<u64>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Config::parse(&[]).unwrap(), Config::new());
+        assert_eq!(Config::parse(&[]).unwrap().databases, DEFAULT_DATABASES);
+        assert_eq!(Config::parse(&to_args("--databases 4")).unwrap(), Config { databases: 4, ..Config::new() });
+        assert_eq!(Config::parse(&to_args("--maxmemory 100mb")).unwrap().maxmemory, 100 * 1024 * 1024);
+        assert!(Config::parse(&to_args("--maxmemory 1tb")).is_err());
+        assert_eq!(Config::parse(&to_args("--maxmemory-policy volatile-lru")).unwrap().maxmemory_policy, EvictionPolicy::VolatileLRU);
+        assert!(Config::parse(&to_args("--maxmemory-policy lru")).is_err());
+        assert_eq!(Config::parse(&to_args("--active-expire-effort 10")).unwrap().active_expire_effort, 10);
+        assert!(Config::parse(&to_args("--active-expire-effort 11")).is_err());
+        assert!(Config::parse(&to_args("--active-expire-index yes")).unwrap().active_expire_index);
+        assert!(Config::parse(&to_args("--active-expire-index true")).is_err());
+        assert!(Config::parse(&to_args("--databases 0")).is_err());
+        assert!(Config::parse(&to_args("--databases")).is_err());
+        assert!(Config::parse(&to_args("--unknown 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("1024"), Some(1024));
+        assert_eq!(parse_memory("2k"), Some(2000));
+        assert_eq!(parse_memory("2KB"), Some(2048));
+        assert_eq!(parse_memory("1g"), Some(1000 * 1000 * 1000));
+        assert_eq!(parse_memory("-1"), None);
+        assert_eq!(parse_memory("mb"), None);
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use rand::Rng;
+
+/// Hash table of the keyspace
+/// see https://github.com/redis/redis/blob/unstable/src/dict.c
+///
+/// The std HashMap offers no access to its buckets, so picking a random key means iterating from the start.
+/// Like Redis we use a chained hash table with a power of two number of buckets, which allows:
+///
+/// random sampling   pick random buckets until a non empty one is found, then a random entry of its chain.
+///                   With at most one entry per bucket on average this is O(1)
+/// stable cursors    SCAN visits the buckets in the order of the reversed bits of the cursor. When the table grows,
+///                   all buckets which were expanded from a visited bucket come before the cursor, so no key is
+///                   returned twice. When the table shrinks, keys may be returned again but never missed
+///
+/// The table doubles when it holds as many keys as buckets and shrinks when less than 1/8 of the buckets are used.
+///
+/// Incremental rehashing
+/// Moving all keys to the resized table at once blocks the server for a long time on large tables. Instead a second
+/// table is allocated and the buckets of the first table are moved one by one: every insert, remove and mutable lookup
+/// moves one bucket and the event loop moves buckets for a fixed time budget while it is idle (see `rehash_for`).
+/// During rehashing lookups consult both tables, new keys are only added to the second table and the rehash index
+/// is the first bucket of the first table which was not moved yet.
+
+const INITIAL_SIZE: usize = 4;
+
+// the table shrinks if it has MIN_FILL times more buckets than keys
+const MIN_FILL: usize = 8;
+// consecutive empty buckets after which sampling continues at another random bucket
+const MAX_EMPTY_BUCKETS: usize = 5;
+// number of buckets moved between two checks of the time budget
+const REHASH_BATCH_SIZE: usize = 100;
+
+lazy_static! {
+    // random keys chosen once per process, like the hash seed of Redis. Fixed keys would let clients pick keys
+    // which all land in the same bucket (hash flooding)
+    static ref HASH_STATE: RandomState = RandomState::new();
+}
+
+type Table<V> = Vec<Vec<(String, V)>>;
+
+pub struct Dict<V> {
+    // the second table is only allocated while rehashing
+    tables: [Table<V>; 2],
+    // next bucket of the first table to move, None if not rehashing
+    rehash_index: Option<usize>,
+    len: usize,
+    // capacity of the buckets of each table and bytes of all keys, tracked so the memory usage is known without
+    // visiting all buckets
+    entry_capacity: [usize; 2],
+    key_bytes: usize,
+}
+
+impl<V> Dict<V> {
+    pub fn new() -> Self {
+        Dict {
+            tables: [Vec::new(), Vec::new()],
+            rehash_index: None,
+            len: 0,
+            entry_capacity: [0, 0],
+            key_bytes: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.find(key).map(|(table, bucket, position)| &self.tables[table][bucket][position].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.rehash(1);
+        let (table, bucket, position) = self.find(key)?;
+        Some(&mut self.tables[table][bucket][position].1)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Inserts the entry and returns the previous value of the key
+    pub fn insert(&mut self, key: String, value: V) -> Option<V> {
+        self.rehash(1);
+        if let Some((table, bucket, position)) = self.find(&key) {
+            return Some(std::mem::replace(&mut self.tables[table][bucket][position].1, value));
+        }
+
+        if !self.is_rehashing() && self.len >= self.tables[0].len() {
+            self.resize((self.len + 1).next_power_of_two().max(INITIAL_SIZE));
+        }
+        // new keys are added to the second table while rehashing, so the first table only shrinks
+        let table_index = self.is_rehashing() as usize;
+        let table = &mut self.tables[table_index];
+        let bucket_index = get_bucket_index(&key, table.len());
+        let bucket = &mut table[bucket_index];
+        let capacity = bucket.capacity();
+        self.key_bytes += key.capacity();
+        bucket.push((key, value));
+        self.entry_capacity[table_index] += bucket.capacity() - capacity;
+        self.len += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.rehash(1);
+        let (table, bucket, position) = self.find(key)?;
+        let (key, value) = self.tables[table][bucket].swap_remove(position);
+        self.key_bytes -= key.capacity();
+        self.len -= 1;
+
+        if !self.is_rehashing() && self.tables[0].len() > INITIAL_SIZE && self.len * MIN_FILL < self.tables[0].len() {
+            self.resize(self.len.next_power_of_two().max(INITIAL_SIZE));
+        }
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &V)> {
+        self.tables.iter().flatten().flat_map(|bucket| bucket.iter().map(|(key, value)| (key, value)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.tables.iter_mut().flatten().flat_map(|bucket| bucket.iter_mut().map(|(_, value)| value))
+    }
+
+    /// Returns a random entry, keys in buckets with a longer chain are slightly less likely to be picked
+    pub fn random_entry(&self) -> Option<(&String, &V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::thread_rng();
+        // the buckets of the first table before the rehash index are empty
+        let first_bucket = self.rehash_index.unwrap_or(0);
+        let bucket_count = self.tables[0].len() + self.tables[1].len();
+        loop {
+            let index = rng.gen_range(first_bucket..bucket_count);
+            let bucket = match index.checked_sub(self.tables[0].len()) {
+                Some(index) => &self.tables[1][index],
+                None => &self.tables[0][index],
+            };
+            if !bucket.is_empty() {
+                let (key, value) = &bucket[rng.gen_range(0..bucket.len())];
+                return Some((key, value));
+            }
+        }
+    }
+
+    /// Returns up to `count` entries of consecutive buckets starting at a random bucket, like dictGetSomeKeys in Redis.
+    /// Faster than `count` calls of `random_entry`, but the entries are less independent of each other.
+    /// Visits at most 10 * `count` buckets, so fewer entries may be returned
+    pub fn sample(&self, count: usize) -> Vec<(&String, &V)> {
+        let mut samples = Vec::new();
+        if self.is_empty() || count == 0 {
+            return samples;
+        }
+
+        let mut rng = rand::thread_rng();
+        let bucket_count = self.tables[0].len().max(self.tables[1].len());
+        let mut index = rng.gen_range(0..bucket_count);
+        let mut empty_buckets = 0;
+
+        for _ in 0..count * 10 {
+            // while rehashing, the bucket index is looked up in both tables
+            let buckets: Vec<&Vec<(String, V)>> = self.tables.iter()
+                .filter_map(|table| table.get(index))
+                .filter(|bucket| !bucket.is_empty())
+                .collect();
+            if buckets.is_empty() {
+                empty_buckets += 1;
+                if empty_buckets >= MAX_EMPTY_BUCKETS && empty_buckets > count {
+                    index = rng.gen_range(0..bucket_count);
+                    empty_buckets = 0;
+                    continue;
+                }
+            } else {
+                empty_buckets = 0;
+                for (key, value) in buckets.into_iter().flatten() {
+                    samples.push((key, value));
+                    if samples.len() == count {
+                        return samples;
+                    }
+                }
+            }
+            index = (index + 1) & (bucket_count - 1);
+        }
+        samples
+    }
+
+    /// Calls `visitor` for all entries of the bucket of the cursor and returns the next cursor, 0 when the iteration is complete.
+    /// Every key which exists during the whole iteration is visited at least once.
+    ///
+    /// While rehashing, the bucket of the smaller table is visited together with all buckets of the larger table
+    /// which it expands to, so the cursor is always a cursor of the smaller table
+    pub fn scan(&self, cursor: u64, mut visitor: impl FnMut(&String, &V)) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        if !self.is_rehashing() {
+            let mask = (self.tables[0].len() - 1) as u64;
+            visit_bucket(&self.tables[0], cursor & mask, &mut visitor);
+            return next_cursor(cursor, mask);
+        }
+
+        let (small, large) = match self.tables[0].len() <= self.tables[1].len() {
+            true => (&self.tables[0], &self.tables[1]),
+            false => (&self.tables[1], &self.tables[0]),
+        };
+        let small_mask = (small.len() - 1) as u64;
+        let large_mask = (large.len() - 1) as u64;
+
+        visit_bucket(small, cursor & small_mask, &mut visitor);
+        let mut cursor = cursor;
+        loop {
+            visit_bucket(large, cursor & large_mask, &mut visitor);
+            cursor = next_cursor(cursor, large_mask);
+            // the bits only present in the mask of the larger table are 0 again after its last expanded bucket
+            if cursor & (small_mask ^ large_mask) == 0 {
+                return cursor;
+            }
+        }
+    }
+
+    pub fn is_rehashing(&self) -> bool {
+        self.rehash_index.is_some()
+    }
+
+    /// Moves up to `steps` buckets to the second table, returns whether the rehashing is still in progress.
+    /// Visits at most 10 empty buckets per step, so a sparse table does not block for long
+    pub fn rehash(&mut self, steps: usize) -> bool {
+        let mut index = match self.rehash_index {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let [old_table, new_table] = &mut self.tables;
+        let mut empty_visits = steps * 10;
+        for _ in 0..steps {
+            while index < old_table.len() && old_table[index].is_empty() {
+                index += 1;
+                empty_visits -= 1;
+                if empty_visits == 0 {
+                    self.rehash_index = Some(index);
+                    return true;
+                }
+            }
+            if index == old_table.len() {
+                break;
+            }
+
+            let old_bucket = std::mem::take(&mut old_table[index]);
+            self.entry_capacity[0] -= old_bucket.capacity();
+            for (key, value) in old_bucket {
+                let bucket_index = get_bucket_index(&key, new_table.len());
+                let bucket = &mut new_table[bucket_index];
+                let capacity = bucket.capacity();
+                bucket.push((key, value));
+                self.entry_capacity[1] += bucket.capacity() - capacity;
+            }
+            index += 1;
+        }
+
+        if index < old_table.len() {
+            self.rehash_index = Some(index);
+            return true;
+        }
+        self.tables[0] = std::mem::take(&mut self.tables[1]);
+        // empty buckets of the old table may still have had a capacity
+        self.entry_capacity = [self.entry_capacity[1], 0];
+        self.rehash_index = None;
+        false
+    }
+
+    /// Rehashes in batches until the rehashing is complete or the budget is used, returns whether it is still in progress
+    pub fn rehash_for(&mut self, budget: Duration) -> bool {
+        let start = Instant::now();
+        while self.rehash(REHASH_BATCH_SIZE) {
+            if start.elapsed() >= budget {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Bytes used by the buckets and entries of the table without the heap memory of keys and values
+    pub fn get_memory_usage(&self) -> u64 {
+        self.get_table_memory_usage(0) + self.get_table_memory_usage(1)
+    }
+
+    /// Bytes of the table which is being rehashed and freed afterwards, 0 if not rehashing
+    pub fn get_rehashing_memory_usage(&self) -> u64 {
+        match self.is_rehashing() {
+            true => self.get_table_memory_usage(0),
+            false => 0,
+        }
+    }
+
+    /// Heap memory of all keys
+    pub fn get_keys_memory_usage(&self) -> u64 {
+        self.key_bytes as u64
+    }
+
+    fn get_table_memory_usage(&self, table: usize) -> u64 {
+        (self.tables[table].len() * std::mem::size_of::<Vec<(String, V)>>() + self.entry_capacity[table] * std::mem::size_of::<(String, V)>()) as u64
+    }
+
+    fn find(&self, key: &str) -> Option<(usize, usize, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let tables = if self.is_rehashing() { 2 } else { 1 };
+        for table in 0..tables {
+            let bucket = get_bucket_index(key, self.tables[table].len());
+            if let Some(position) = self.tables[table][bucket].iter().position(|(entry_key, _)| entry_key == key) {
+                return Some((table, bucket, position));
+            }
+        }
+        None
+    }
+
+    /// Allocates the table with `size` buckets, the entries are moved incrementally unless the dict is empty
+    fn resize(&mut self, size: usize) {
+        let table = (0..size).map(|_| Vec::new()).collect();
+        if self.tables[0].is_empty() || self.is_empty() {
+            self.tables[0] = table;
+            self.entry_capacity[0] = 0;
+            return;
+        }
+        self.tables[1] = table;
+        self.rehash_index = Some(0);
+    }
+}
+
+fn visit_bucket<V>(table: &Table<V>, index: u64, visitor: &mut impl FnMut(&String, &V)) {
+    for (key, value) in table[index as usize].iter() {
+        visitor(key, value);
+    }
+}
+
+/// Increments the reversed cursor, the bits above the mask are set so the carry passes through them
+fn next_cursor(cursor: u64, mask: u64) -> u64 {
+    (cursor | !mask).reverse_bits().wrapping_add(1).reverse_bits()
+}
+
+fn get_bucket_index(key: &str, bucket_count: usize) -> usize {
+    (get_key_hash(key) & (bucket_count as u64 - 1)) as usize
+}
+
+/// Hash of the key which determines its bucket, it has to be stable for the lifetime of the process so cursors stay valid
+fn get_key_hash(key: &str) -> u64 {
+    HASH_STATE.hash_one(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_resize() {
+        // given
+        let mut dict = Dict::new();
+
+        // when
+        for i in 0..100 {
+            dict.insert(format!("key:{}", i), i);
+        }
+        let replaced = dict.insert(String::from("key:1"), 1000);
+        for i in 10..100 {
+            dict.remove(&format!("key:{}", i));
+        }
+
+        // then
+        assert_eq!(replaced, Some(1));
+        assert_eq!(dict.len(), 10);
+        assert_eq!(dict.get("key:1"), Some(&1000));
+        assert_eq!(dict.get("key:50"), None);
+        assert_eq!(dict.iter().count(), 10);
+        while dict.rehash(1) {}
+        assert_eq!(dict.tables[0].len(), 16);
+    }
+
+    #[test]
+    fn test_incremental_rehashing() {
+        // given
+        let mut dict = Dict::new();
+        for i in 0..64 {
+            dict.insert(format!("key:{}", i), i);
+        }
+
+        // when
+        // the 65th key starts moving the 64 buckets to a table with 128 buckets
+        dict.insert(String::from("key:64"), 64);
+
+        // then
+        assert!(dict.is_rehashing());
+        assert_eq!(dict.tables[1].len(), 128);
+        assert!(dict.get_rehashing_memory_usage() > 0);
+        assert!((0..65).all(|i| dict.get(&format!("key:{}", i)) == Some(&i)));
+        assert_eq!(dict.remove("key:0"), Some(0));
+        assert!(!dict.rehash_for(Duration::from_millis(100)));
+        assert!(!dict.is_rehashing());
+        assert_eq!(dict.tables[0].len(), 128);
+        assert_eq!(dict.len(), 64);
+        assert!((1..65).all(|i| dict.get(&format!("key:{}", i)) == Some(&i)));
+    }
+
+    #[test]
+    fn test_memory_usage_is_tracked() {
+        // given
+        let mut dict = Dict::new();
+        let table_memory_usage = |dict: &Dict<i32>| -> u64 {
+            dict.tables.iter().flatten()
+                .map(|bucket| (std::mem::size_of::<Vec<(String, i32)>>() + bucket.capacity() * std::mem::size_of::<(String, i32)>()) as u64)
+                .sum()
+        };
+
+        // when
+        for i in 0..100 {
+            dict.insert(format!("key:{}", i), i);
+        }
+        let while_rehashing = (dict.is_rehashing(), dict.get_memory_usage(), table_memory_usage(&dict));
+        for i in 0..95 {
+            dict.remove(&format!("key:{}", i));
+        }
+        dict.rehash_for(Duration::from_millis(100));
+
+        // then
+        assert!(while_rehashing.0);
+        assert_eq!(while_rehashing.1, while_rehashing.2);
+        assert_eq!(dict.get_memory_usage(), table_memory_usage(&dict));
+        assert_eq!(dict.get_keys_memory_usage(), dict.keys().map(|key| key.capacity() as u64).sum::<u64>());
+    }
+
+    #[test]
+    fn test_sample() {
+        // given
+        let mut dict = Dict::new();
+        for i in 0..1000 {
+            dict.insert(format!("key:{}", i), i);
+        }
+
+        // when
+        let samples = dict.sample(20);
+        let mut random_keys = std::collections::HashSet::new();
+        for _ in 0..100 {
+            random_keys.insert(dict.random_entry().unwrap().0.clone());
+        }
+
+        // then
+        assert_eq!(samples.len(), 20);
+        // with 1000 keys, 100 random picks are expected to return about 95 distinct keys
+        assert!(random_keys.len() > 50);
+        assert!(Dict::<u64>::new().random_entry().is_none());
+    }
+
+    #[test]
+    fn test_scan_while_rehashing() {
+        // given
+        let mut dict = Dict::new();
+        for i in 0..65 {
+            dict.insert(format!("key:{}", i), i);
+        }
+
+        // when
+        let mut visited = Vec::new();
+        let mut cursor = 0;
+        loop {
+            assert!(dict.is_rehashing());
+            cursor = dict.scan(cursor, |key, _| visited.push(key.clone()));
+            if cursor == 0 {
+                break;
+            }
+        }
+
+        // then
+        for i in 0..65 {
+            assert_eq!(visited.iter().filter(|key| **key == format!("key:{}", i)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_scan_while_growing() {
+        // given
+        let mut dict = Dict::new();
+        for i in 0..10 {
+            dict.insert(format!("key:{}", i), i);
+        }
+
+        // when
+        let mut visited = Vec::new();
+        let mut cursor = dict.scan(0, |key, _| visited.push(key.clone()));
+        for i in 10..100 {
+            dict.insert(format!("key:{}", i), i);
+        }
+        while cursor != 0 {
+            cursor = dict.scan(cursor, |key, _| visited.push(key.clone()));
+        }
+
+        // then
+        for i in 0..10 {
+            assert_eq!(visited.iter().filter(|key| **key == format!("key:{}", i)).count(), 1);
+        }
+    }
+}
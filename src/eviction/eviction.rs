@@ -1,46 +1,164 @@
+use crate::allocator::get_allocated_bytes;
+use crate::eviction::lfu_eviction_strategy::{LFUConfiguration, LFUEvictionStrategy};
+use crate::eviction::lru_eviction_strategy::LRUEvictionStrategy;
+use crate::eviction::random_eviction_strategy::RandomEvictionStrategy;
+use crate::eviction::ttl_eviction_strategy::TTLEvictionStrategy;
 use crate::store::Store;
 
 /// see https://redis.io/docs/reference/eviction/
+///
+/// Keys are evicted as long as the memory allocated by the server exceeds maxmemory.
+/// The policy decides which keys are evicted, allkeys policies consider all keys and volatile policies only keys with an expiration.
+/// With noeviction (or if no key can be evicted) commands which need memory are rejected with an OOM error.
 
-pub struct EvictionManagerConfiguration {
-    pub keys_limit: u64,
-    pub eviction_ratio: f64,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvictionPolicy {
+    NoEviction,
+    AllKeysLRU,
+    AllKeysLFU,
+    AllKeysRandom,
+    VolatileLRU,
+    VolatileLFU,
+    VolatileRandom,
+    VolatileTTL,
 }
 
-impl EvictionManagerConfiguration {
-    pub fn get_keys_to_remove(&self) -> u64 {
-        return (self.keys_limit as f64 * self.eviction_ratio) as u64;
+const EVICTION_POLICIES: [EvictionPolicy; 8] = [
+    EvictionPolicy::NoEviction,
+    EvictionPolicy::AllKeysLRU,
+    EvictionPolicy::AllKeysLFU,
+    EvictionPolicy::AllKeysRandom,
+    EvictionPolicy::VolatileLRU,
+    EvictionPolicy::VolatileLFU,
+    EvictionPolicy::VolatileRandom,
+    EvictionPolicy::VolatileTTL,
+];
+
+impl EvictionPolicy {
+    /// Name of the policy as used by the maxmemory-policy configuration
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            EvictionPolicy::NoEviction => "noeviction",
+            EvictionPolicy::AllKeysLRU => "allkeys-lru",
+            EvictionPolicy::AllKeysLFU => "allkeys-lfu",
+            EvictionPolicy::AllKeysRandom => "allkeys-random",
+            EvictionPolicy::VolatileLRU => "volatile-lru",
+            EvictionPolicy::VolatileLFU => "volatile-lfu",
+            EvictionPolicy::VolatileRandom => "volatile-random",
+            EvictionPolicy::VolatileTTL => "volatile-ttl",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<EvictionPolicy> {
+        EVICTION_POLICIES.iter().find(|policy| policy.get_name().eq_ignore_ascii_case(name)).copied()
+    }
+
+    /// Whether the access bits of the objects store access frequencies instead of access times
+    pub fn is_lfu(&self) -> bool {
+        matches!(self, EvictionPolicy::AllKeysLFU | EvictionPolicy::VolatileLFU)
+    }
+
+    /// Whether only keys with an expiration are evicted
+    pub fn is_volatile(&self) -> bool {
+        matches!(self, EvictionPolicy::VolatileLRU | EvictionPolicy::VolatileLFU | EvictionPolicy::VolatileRandom | EvictionPolicy::VolatileTTL)
     }
 }
 
+pub const DEFAULT_MAXMEMORY_SAMPLES: usize = 5;
+
+pub struct EvictionManagerConfiguration {
+    // maximum number of bytes allocated by the server, 0 disables eviction
+    pub maxmemory: u64,
+    pub policy: EvictionPolicy,
+    pub lfu: LFUConfiguration,
+    // number of keys sampled per eviction, more samples approximate LRU/LFU better but cost more CPU
+    pub samples: usize,
+}
+
 pub struct EvictionManager {
-    pub strategy: Box<dyn EvictionStrategy>,
+    // no strategy with the noeviction policy
+    pub strategy: Option<Box<dyn EvictionStrategy>>,
     pub config: EvictionManagerConfiguration,
 }
 
 pub trait EvictionStrategy {
+    /// Evicts the best candidate for eviction of the selected database
     fn evict(&mut self, config: &EvictionManagerConfiguration, store: &mut Store) -> Result<(), String>;
 }
 
 impl EvictionManager {
-    pub fn new(config: EvictionManagerConfiguration, strategy: Box<dyn EvictionStrategy>) -> EvictionManager {
+    pub fn new(config: EvictionManagerConfiguration) -> EvictionManager {
+        let strategy = create_strategy(config.policy);
         return EvictionManager {
             config,
             strategy
-        }
+        };
     }
 
+    /// Changes the policy at runtime, the state of the previous strategy (e.g. the LRU pool) is dropped
+    pub fn set_policy(&mut self, policy: EvictionPolicy) {
+        self.strategy = create_strategy(policy);
+        self.config.policy = policy;
+    }
+
+    /// Evicts keys until the used memory is below maxmemory or there is no key left to evict.
+    /// Keys of the selected database are evicted first, then the keys of the other databases
     pub fn evict(&mut self, store: &mut Store) {
+        let strategy = match self.strategy.as_mut() {
+            Some(strategy) => strategy,
+            None => return,
+        };
+
         println!("Evicting keys...");
-        match self.strategy.evict(&self.config, store) {
-            Ok(_) => {}
-            Err(err) => {
-                eprintln!("Error while evicting keys: {}", err);
+        let selected_db = store.get_selected_db();
+        let db_count = store.get_db_count();
+
+        for db in (0..db_count).map(|offset| (selected_db + offset) % db_count) {
+            store.select_db(db).expect("Database does not exist");
+
+            while self.config.maxmemory > 0 && get_allocated_bytes() > self.config.maxmemory {
+                let number_of_keys = store.get_data().len();
+                if number_of_keys == 0 {
+                    break;
+                }
+
+                if let Err(err) = strategy.evict(&self.config, store) {
+                    eprintln!("Error while evicting keys: {}", err);
+                    break;
+                }
+                if store.get_data().len() == number_of_keys {
+                    break;
+                }
             }
         }
+
+        store.select_db(selected_db).expect("Database does not exist");
     }
 
-    pub fn ready_for_evict(&self, store: &Store) -> bool {
-        store.get_data().len() as u64 >= self.config.keys_limit
+    pub fn ready_for_evict(&self, _: &Store) -> bool {
+        self.config.maxmemory > 0 && get_allocated_bytes() > self.config.maxmemory
+    }
+}
+
+fn create_strategy(policy: EvictionPolicy) -> Option<Box<dyn EvictionStrategy>> {
+    match policy {
+        EvictionPolicy::NoEviction => None,
+        EvictionPolicy::AllKeysLRU | EvictionPolicy::VolatileLRU => Some(Box::new(LRUEvictionStrategy::new(policy.is_volatile()))),
+        EvictionPolicy::AllKeysLFU | EvictionPolicy::VolatileLFU => Some(Box::new(LFUEvictionStrategy::new(policy.is_volatile()))),
+        EvictionPolicy::AllKeysRandom | EvictionPolicy::VolatileRandom => Some(Box::new(RandomEvictionStrategy::new(policy.is_volatile()))),
+        EvictionPolicy::VolatileTTL => Some(Box::new(TTLEvictionStrategy::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(EvictionPolicy::parse("volatile-ttl"), Some(EvictionPolicy::VolatileTTL));
+        assert_eq!(EvictionPolicy::parse("ALLKEYS-LRU"), Some(EvictionPolicy::AllKeysLRU));
+        assert_eq!(EvictionPolicy::parse("lru"), None);
+        assert!(EVICTION_POLICIES.iter().all(|policy| EvictionPolicy::parse(policy.get_name()) == Some(*policy)));
     }
 }
@@ -0,0 +1,90 @@
+/// Pool of the best candidates for eviction, see evictionPoolPopulate in Redis
+///
+/// Every eviction samples a few random keys and adds them to the pool if they are better candidates than the keys in the pool,
+/// so the pool keeps the best candidates of all samples taken so far. Entries are keyed by name and database and are looked up
+/// again before a key is evicted, a key which was removed in the meantime is skipped.
+
+pub const EVICTION_POOL_SIZE: usize = 16;
+
+struct EvictionPoolEntry {
+    key: String,
+    db: usize,
+    // higher values are better candidates, e.g. the idle time of the key
+    score: u64,
+}
+
+pub struct EvictionPool {
+    // ordered ascending by score, the best candidate is the last entry
+    entries: Vec<EvictionPoolEntry>,
+}
+
+impl EvictionPool {
+    pub fn new() -> Self {
+        EvictionPool {
+            entries: Vec::with_capacity(EVICTION_POOL_SIZE),
+        }
+    }
+
+    /// Adds the key if the pool is not full or the key is a better candidate than the worst entry
+    pub fn insert(&mut self, key: String, db: usize, score: u64) {
+        // a key which is sampled again replaces its entry with the current score
+        self.entries.retain(|entry| entry.key != key || entry.db != db);
+
+        let mut position = self.entries.partition_point(|entry| entry.score < score);
+        if self.entries.len() >= EVICTION_POOL_SIZE {
+            if position == 0 {
+                return;
+            }
+            self.entries.remove(0);
+            position -= 1;
+        }
+        self.entries.insert(position, EvictionPoolEntry { key, db, score });
+    }
+
+    /// Removes and returns the best candidate of the database
+    pub fn pop(&mut self, db: usize) -> Option<String> {
+        let position = self.entries.iter().rposition(|entry| entry.db == db)?;
+        Some(self.entries.remove(position).key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pop_best_candidate() {
+        // given
+        let mut pool = EvictionPool::new();
+        pool.insert(String::from("a"), 0, 10);
+        pool.insert(String::from("b"), 0, 30);
+        pool.insert(String::from("c"), 0, 20);
+        pool.insert(String::from("d"), 1, 40);
+        pool.insert(String::from("a"), 0, 50);
+
+        // then
+        assert_eq!(pool.pop(0), Some(String::from("a")));
+        assert_eq!(pool.pop(0), Some(String::from("b")));
+        assert_eq!(pool.pop(0), Some(String::from("c")));
+        assert_eq!(pool.pop(0), None);
+        assert_eq!(pool.pop(1), Some(String::from("d")));
+    }
+
+    #[test]
+    fn test_full_pool_keeps_best_candidates() {
+        // given
+        let mut pool = EvictionPool::new();
+        for score in 0..EVICTION_POOL_SIZE as u64 {
+            pool.insert(format!("key:{}", score), 0, score + 10);
+        }
+
+        // when
+        pool.insert(String::from("worse"), 0, 1);
+        pool.insert(String::from("better"), 0, 100);
+
+        // then
+        assert_eq!(pool.entries.len(), EVICTION_POOL_SIZE);
+        assert_eq!(pool.pop(0), Some(String::from("better")));
+        assert!(pool.entries.iter().all(|entry| entry.key != "worse" && entry.key != "key:0"));
+    }
+}
@@ -0,0 +1,162 @@
+use rand::Rng;
+
+use crate::eviction::eviction::{EvictionManagerConfiguration, EvictionStrategy};
+use crate::eviction::eviction_pool::EvictionPool;
+use crate::store::Store;
+
+/// Approximate LFU eviction strategy, see https://redis.io/docs/reference/eviction/#the-new-lfu-mode
+///
+/// With an LFU policy the access time bits of an object store the access frequency instead, like Redis does:
+///
+///      16 bits      8 bits
+/// +----------------+--------+
+/// + Last decr time | LOG_C  |
+/// +----------------+--------+
+///
+/// LOG_C is a logarithmic counter, the more accesses a key already has the less likely an access increments it.
+/// The last decrement time is the time in minutes when the counter was decremented the last time,
+/// every lfu-decay-time minutes without an access the counter is decremented by one.
+/// Sampled keys are added to the eviction pool and the key with the lowest counter is evicted.
+
+// counter of new keys, so new keys are not evicted before they had the chance to be accessed
+pub const LFU_INIT_VAL: u8 = 5;
+pub const DEFAULT_LFU_LOG_FACTOR: u32 = 10;
+pub const DEFAULT_LFU_DECAY_TIME: u64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LFUConfiguration {
+    // higher values need more accesses to saturate the counter
+    pub log_factor: u32,
+    // minutes after which the counter is decremented, 0 never decrements the counter
+    pub decay_time: u64,
+}
+
+impl LFUConfiguration {
+    pub fn new() -> Self {
+        LFUConfiguration {
+            log_factor: DEFAULT_LFU_LOG_FACTOR,
+            decay_time: DEFAULT_LFU_DECAY_TIME,
+        }
+    }
+}
+
+/// Access bits of a new object
+pub fn get_initial_access() -> u32 {
+    (get_time_in_minutes() as u32) << 8 | LFU_INIT_VAL as u32
+}
+
+/// Decrements the counter by the elapsed decay periods and increments it for this access
+pub fn update_access(access: u32, config: &LFUConfiguration) -> u32 {
+    let counter = get_decayed_counter(access, config);
+    let counter = log_increment(counter, config.log_factor);
+    (get_time_in_minutes() as u32) << 8 | counter as u32
+}
+
+/// Counter of the object after the decay of the time since the last decrement, see OBJECT FREQ
+pub fn get_decayed_counter(access: u32, config: &LFUConfiguration) -> u8 {
+    let last_decrement_time = (access >> 8) as u16;
+    let counter = (access & 0xFF) as u8;
+
+    let periods = if config.decay_time > 0 {
+        get_minutes_elapsed(last_decrement_time) / config.decay_time
+    } else {
+        0
+    };
+    counter.saturating_sub(periods.min(u8::MAX as u64) as u8)
+}
+
+/// Increments the counter with a probability of 1 / ((counter - LFU_INIT_VAL) * log_factor + 1)
+fn log_increment(counter: u8, log_factor: u32) -> u8 {
+    if counter == u8::MAX {
+        return counter;
+    }
+
+    let base = counter.saturating_sub(LFU_INIT_VAL) as f64;
+    let probability = 1.0 / (base * log_factor as f64 + 1.0);
+    if rand::thread_rng().gen::<f64>() < probability {
+        return counter + 1;
+    }
+    counter
+}
+
+/// Unix time in minutes, wrapping every 45 days like the 16 bits in Redis
+fn get_time_in_minutes() -> u16 {
+    ((chrono::Utc::now().timestamp() / 60) & 0xFFFF) as u16
+}
+
+fn get_minutes_elapsed(last_decrement_time: u16) -> u64 {
+    get_time_in_minutes().wrapping_sub(last_decrement_time) as u64
+}
+
+pub struct LFUEvictionStrategy {
+    pool: EvictionPool,
+    volatile: bool,
+}
+
+impl LFUEvictionStrategy {
+    pub fn new(volatile: bool) -> Self {
+        LFUEvictionStrategy {
+            pool: EvictionPool::new(),
+            volatile,
+        }
+    }
+}
+
+impl EvictionStrategy for LFUEvictionStrategy {
+    fn evict(&mut self, config: &EvictionManagerConfiguration, store: &mut Store) -> Result<(), String> {
+        let db = store.get_selected_db();
+        for key in store.sample_keys(config.samples, self.volatile) {
+            if let Some(store_object) = store.get_data().get(&key) {
+                // keys with lower counters are better candidates
+                let counter = get_decayed_counter(store_object.last_accessed_at, &config.lfu);
+                self.pool.insert(key, db, (u8::MAX - counter) as u64);
+            }
+        }
+
+        while let Some(key) = self.pool.pop(db) {
+            if store.is_evictable(&key, self.volatile) {
+                println!("Evicted key: {}", key);
+                store.remove(&key);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_increment() {
+        // given
+        let mut counter = LFU_INIT_VAL;
+
+        // when
+        for _ in 0..1000 {
+            counter = log_increment(counter, DEFAULT_LFU_LOG_FACTOR);
+        }
+
+        // then
+        // with a log factor of 10, 1000 accesses are expected to reach a counter of about 18
+        assert!(counter > LFU_INIT_VAL && counter < 40);
+        assert_eq!(log_increment(u8::MAX, 0), u8::MAX);
+        assert_eq!(log_increment(0, DEFAULT_LFU_LOG_FACTOR), 1);
+    }
+
+    #[test]
+    fn test_decay() {
+        // given
+        let config = LFUConfiguration::new();
+        let accessed_now = get_initial_access();
+        let accessed_10_minutes_ago = (get_time_in_minutes().wrapping_sub(10) as u32) << 8 | 20;
+
+        // then
+        assert_eq!(get_decayed_counter(accessed_now, &config), LFU_INIT_VAL);
+        assert_eq!(get_decayed_counter(accessed_10_minutes_ago, &config), 10);
+        assert_eq!(get_decayed_counter(accessed_10_minutes_ago, &LFUConfiguration { log_factor: 10, decay_time: 0 }), 20);
+        assert_eq!(get_decayed_counter(accessed_10_minutes_ago, &LFUConfiguration { log_factor: 10, decay_time: 5 }), 18);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::eviction::eviction::{EvictionManagerConfiguration, EvictionStrategy};
+use crate::eviction::eviction_pool::EvictionPool;
+use crate::store::Store;
+
+/// Approximate LRU eviction strategy
+/// N keys get selected randomly and are added to the pool if they were idle longer than the keys in the pool.
+/// Then we evict the key with the longest idle time from the pool.
+/// With a volatile policy only keys with an expiration are sampled.
+///
+/// Objects store the access time as 24 bit LRU clock with a resolution of one second, like Redis does.
+/// The clock wraps after 194 days, so longer idle times can not be told apart.
+
+pub const LRU_CLOCK_MAX: u32 = (1 << 24) - 1;
+pub const LRU_CLOCK_RESOLUTION_MS: u64 = 1000;
+
+/// Current time as LRU clock
+pub fn get_lru_clock() -> u32 {
+    ((chrono::Utc::now().timestamp_millis() as u64 / LRU_CLOCK_RESOLUTION_MS) & LRU_CLOCK_MAX as u64) as u32
+}
+
+/// Milliseconds since the LRU clock had the given value, the clock may have wrapped once since
+pub fn get_idle_time_ms(lru_clock: u32) -> u64 {
+    let now = get_lru_clock();
+    let ticks = if now >= lru_clock {
+        now - lru_clock
+    } else {
+        now + (LRU_CLOCK_MAX - lru_clock)
+    };
+    ticks as u64 * LRU_CLOCK_RESOLUTION_MS
+}
+
+pub struct LRUEvictionStrategy {
+    pool: EvictionPool,
+    volatile: bool,
+}
+
+impl LRUEvictionStrategy {
+    pub fn new(volatile: bool) -> Self {
+        LRUEvictionStrategy {
+            pool: EvictionPool::new(),
+            volatile,
+        }
+    }
+
+    fn populate_pool(&mut self, config: &EvictionManagerConfiguration, store: &mut Store) {
+        let db = store.get_selected_db();
+        for key in store.sample_keys(config.samples, self.volatile) {
+            if let Some(store_object) = store.get_data().get(&key) {
+                let idle_time = get_idle_time_ms(store_object.last_accessed_at);
+                self.pool.insert(key, db, idle_time);
+            }
+        }
+    }
+}
+
+impl EvictionStrategy for LRUEvictionStrategy {
+    fn evict(&mut self, config: &EvictionManagerConfiguration, store: &mut Store) -> Result<(), String> {
+        self.populate_pool(config, store);
+
+        // it is possible that keys in the pool were removed by the user in the meantime
+        while let Some(key) = self.pool.pop(store.get_selected_db()) {
+            if store.is_evictable(&key, self.volatile) {
+                println!("Evicting key: {}", key);
+                store.remove(&key);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_time() {
+        // given
+        let now = get_lru_clock();
+
+        // then
+        assert!(get_idle_time_ms(now) <= LRU_CLOCK_RESOLUTION_MS);
+        assert!(get_idle_time_ms((now + LRU_CLOCK_MAX - 10) & LRU_CLOCK_MAX) >= 10 * LRU_CLOCK_RESOLUTION_MS);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod eviction;
-pub mod simple_eviction_strategy;
-pub mod all_keys_random_eviction_strategy;
-pub mod all_keys_lru_eviction_strategy;
+pub mod random_eviction_strategy;
+pub mod lru_eviction_strategy;
+pub mod ttl_eviction_strategy;
+pub mod lfu_eviction_strategy;
+pub mod eviction_pool;
@@ -0,0 +1,24 @@
+use crate::eviction::eviction::{EvictionManagerConfiguration, EvictionStrategy};
+use crate::store::Store;
+
+/// Evicts a random key, with a volatile policy a random key with an expiration
+pub struct RandomEvictionStrategy {
+    volatile: bool,
+}
+
+impl RandomEvictionStrategy {
+    pub fn new(volatile: bool) -> Self {
+        RandomEvictionStrategy { volatile }
+    }
+}
+
+impl EvictionStrategy for RandomEvictionStrategy {
+    fn evict(&mut self, _: &EvictionManagerConfiguration, store: &mut Store) -> Result<(), String> {
+        if let Some(key) = store.sample_keys(1, self.volatile).pop() {
+            println!("Evicted key: {}", key);
+            store.remove(&key);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::eviction::eviction::{EvictionManagerConfiguration, EvictionStrategy};
+use crate::eviction::eviction_pool::EvictionPool;
+use crate::store::Store;
+
+/// Evicts the key with the nearest expiration, sampled keys with an expiration are added to the eviction pool, see volatile-ttl
+pub struct TTLEvictionStrategy {
+    pool: EvictionPool,
+}
+
+impl TTLEvictionStrategy {
+    pub fn new() -> Self {
+        TTLEvictionStrategy {
+            pool: EvictionPool::new(),
+        }
+    }
+}
+
+impl EvictionStrategy for TTLEvictionStrategy {
+    fn evict(&mut self, config: &EvictionManagerConfiguration, store: &mut Store) -> Result<(), String> {
+        let db = store.get_selected_db();
+        for key in store.sample_keys(config.samples, true) {
+            if let Some(expires_at) = store.get_expiry(&key) {
+                // keys which expire earlier are better candidates
+                self.pool.insert(key, db, u64::MAX - expires_at.max(0) as u64);
+            }
+        }
+
+        while let Some(key) = self.pool.pop(db) {
+            if store.is_evictable(&key, true) {
+                println!("Evicted key: {}", key);
+                store.remove(&key);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::resp::DataType;
+use crate::resp::DataType::{Error, Integer};
+use crate::store::Store;
+
+/// Shared functions of the EXPIRE (EXPIRE, PEXPIRE, EXPIREAT, PEXPIREAT) and TTL (TTL, PTTL, EXPIRETIME, PEXPIRETIME) commands
+///
+/// Expirations are stored as unix time in milliseconds, the commands only differ in the unit of the time
+/// and whether it is relative to now or absolute. An expiration in the past deletes the key, as Redis does.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeUnit {
+    Seconds,
+    Milliseconds,
+}
+
+impl TimeUnit {
+    fn to_milliseconds(self, time: i64) -> Option<i64> {
+        match self {
+            TimeUnit::Seconds => time.checked_mul(1000),
+            TimeUnit::Milliseconds => Some(time),
+        }
+    }
+}
+
+/// NX, XX, GT and LT options of the EXPIRE commands, a key without expiration counts as infinite TTL for GT and LT
+#[derive(Debug, Default, PartialEq)]
+struct ExpireCondition {
+    nx: bool,
+    xx: bool,
+    gt: bool,
+    lt: bool,
+}
+
+impl ExpireCondition {
+    fn parse(options: &[String]) -> Result<ExpireCondition, String> {
+        let mut condition = ExpireCondition::default();
+        for option in options {
+            match option.to_uppercase().as_str() {
+                "NX" => condition.nx = true,
+                "XX" => condition.xx = true,
+                "GT" => condition.gt = true,
+                "LT" => condition.lt = true,
+                _ => return Err(format!("ERR Unsupported option {}", option)),
+            }
+        }
+
+        if condition.nx && (condition.xx || condition.gt || condition.lt) {
+            return Err(String::from("ERR NX and XX, GT or LT options at the same time are not compatible"));
+        }
+        if condition.gt && condition.lt {
+            return Err(String::from("ERR GT and LT options at the same time are not compatible"));
+        }
+        Ok(condition)
+    }
+
+    /// Whether the new expiration is set given the current expiration of the key
+    fn is_met(&self, current: Option<i64>, expires_at: i64) -> bool {
+        match current {
+            Some(current) => !self.nx && (!self.gt || expires_at > current) && (!self.lt || expires_at < current),
+            None => !self.xx && !self.gt,
+        }
+    }
+}
+
+/// Sets the expiration of the key, `absolute` expirations are unix timestamps instead of a time relative to now.
+/// Returns 1 if the expiration was set or the key was deleted, 0 if the key does not exist or the condition is not met
+pub fn expire(args: &[String], store: &mut Store, unit: TimeUnit, absolute: bool, command_name: &str) -> DataType {
+    if args.len() < 2 {
+        return Error(format!("ERR wrong number of arguments for '{}' command", command_name));
+    }
+
+    let time = match args[1].parse::<i64>() {
+        Ok(time) => time,
+        Err(_) => return Error(String::from("ERR value is not an integer or out of range")),
+    };
+    let condition = match ExpireCondition::parse(&args[2..]) {
+        Ok(condition) => condition,
+        Err(err) => return Error(err),
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let expires_at = match unit.to_milliseconds(time).and_then(|time| if absolute { Some(time) } else { time.checked_add(now) }) {
+        Some(expires_at) => expires_at,
+        None => return Error(format!("ERR invalid expire time in '{}' command", command_name)),
+    };
+
+    let key = &args[0];
+    if !store.contains_key(key) || !condition.is_met(store.get_expiry(key), expires_at) {
+        return Integer(0);
+    }
+
+    if expires_at <= now {
+        store.remove(key);
+    } else {
+        store.set_expiry(key, expires_at);
+    }
+    Integer(1)
+}
+
+/// Returns the remaining time to live, or with `absolute` the unix time at which the key expires.
+/// Returns -2 if the key does not exist and -1 if it has no expiration
+pub fn get_ttl(args: &[String], store: &mut Store, unit: TimeUnit, absolute: bool, command_name: &str) -> DataType {
+    if args.len() != 1 {
+        return Error(format!("ERR wrong number of arguments for '{}' command", command_name));
+    }
+
+    let key = &args[0];
+    if !store.contains_key(key) {
+        return Integer(-2);
+    }
+
+    let expires_at = match store.get_expiry(key) {
+        Some(expires_at) => expires_at,
+        None => return Integer(-1),
+    };
+    let ttl = if absolute { expires_at } else { (expires_at - chrono::Utc::now().timestamp_millis()).max(0) };
+
+    match unit {
+        TimeUnit::Seconds => Integer((ttl + 500) / 1000),
+        TimeUnit::Milliseconds => Integer(ttl),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        assert_eq!(ExpireCondition::parse(&to_args("xx gt")), Ok(ExpireCondition { xx: true, gt: true, ..ExpireCondition::default() }));
+        assert!(ExpireCondition::parse(&to_args("NX XX")).is_err());
+        assert!(ExpireCondition::parse(&to_args("GT LT")).is_err());
+        assert!(ExpireCondition::parse(&to_args("PX")).is_err());
+    }
+
+    #[test]
+    fn test_condition_is_met() {
+        // given
+        let always = ExpireCondition::default();
+        let nx = ExpireCondition { nx: true, ..ExpireCondition::default() };
+        let xx = ExpireCondition { xx: true, ..ExpireCondition::default() };
+        let gt = ExpireCondition { gt: true, ..ExpireCondition::default() };
+        let lt = ExpireCondition { lt: true, ..ExpireCondition::default() };
+
+        // then
+        assert!(always.is_met(Some(100), 50) && always.is_met(None, 50));
+        assert!(nx.is_met(None, 50) && !nx.is_met(Some(100), 50));
+        assert!(!xx.is_met(None, 50) && xx.is_met(Some(100), 50));
+        // a key without expiration has an infinite TTL, which is never less than the new TTL
+        assert!(!gt.is_met(None, 50) && gt.is_met(Some(100), 150) && !gt.is_met(Some(100), 100));
+        assert!(lt.is_met(None, 50) && lt.is_met(Some(100), 50) && !lt.is_met(Some(100), 150));
+    }
+}
@@ -0,0 +1 @@
+pub mod expire_helper;
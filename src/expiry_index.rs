@@ -0,0 +1,77 @@
+use std::collections::BTreeSet;
+
+/// Keys with an expiration ordered by their expiration time
+///
+/// With millions of keys with an expiration, random sampling finds few expired keys per sample once most keys are
+/// still valid, so expired keys stay in memory for a long time. The index keeps the keys ordered by `expires_at`
+/// (like the radix tree of keys ordered by time which Redis uses for client timeouts), so the active expiration cycle
+/// deletes exactly the keys which are due in O(log n) per expired key, without visiting keys which are still valid.
+///
+/// The index is optional (see active-expire-index), as it costs an additional copy of every key with an expiration.
+
+pub struct ExpiryIndex {
+    entries: BTreeSet<(i64, String)>,
+    // bytes of the copied keys, tracked so the memory usage is known without visiting all entries
+    key_bytes: usize,
+}
+
+impl ExpiryIndex {
+    pub fn new() -> Self {
+        ExpiryIndex {
+            entries: BTreeSet::new(),
+            key_bytes: 0,
+        }
+    }
+
+    pub fn insert(&mut self, key: &str, expires_at: i64) {
+        if self.entries.insert((expires_at, String::from(key))) {
+            self.key_bytes += key.len();
+        }
+    }
+
+    /// Removes the key, `expires_at` has to be the expiration the key was inserted with
+    pub fn remove(&mut self, key: &str, expires_at: i64) {
+        if self.entries.remove(&(expires_at, String::from(key))) {
+            self.key_bytes -= key.len();
+        }
+    }
+
+    /// Returns up to `count` keys which expire at or before `now`, the keys which expired first are returned first
+    pub fn get_expired_keys(&self, now: i64, count: usize) -> Vec<String> {
+        self.entries.iter()
+            .take_while(|(expires_at, _)| *expires_at <= now)
+            .take(count)
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    /// Estimated number of bytes used by the entries and the copies of the keys
+    pub fn get_memory_usage(&self) -> u64 {
+        (self.entries.len() * std::mem::size_of::<(i64, String)>() + self.key_bytes) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_expired_keys() {
+        // given
+        let mut index = ExpiryIndex::new();
+        index.insert("later", 300);
+        index.insert("first", 100);
+        index.insert("second", 200);
+        index.insert("removed", 50);
+
+        // when
+        index.remove("removed", 50);
+
+        // then
+        assert_eq!(index.get_expired_keys(200, 10), vec![String::from("first"), String::from("second")]);
+        assert_eq!(index.get_expired_keys(200, 1), vec![String::from("first")]);
+        assert!(index.get_expired_keys(99, 10).is_empty());
+        assert_eq!(index.entries.len(), 3);
+        assert_eq!(index.get_memory_usage(), (3 * std::mem::size_of::<(i64, String)>() + "laterfirstsecond".len()) as u64);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::geo::geohash::is_valid_coordinate;
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::sorted_set::SortedSet;
+use crate::store::{ObjectValue, Store};
+
+/// Shared argument parsing and lookup functions of the GEO commands
+
+/// Parses a distance unit and returns the number of meters per unit
+pub fn parse_unit(unit: &str) -> Result<f64, DataType> {
+    match unit.to_lowercase().as_str() {
+        "m" => Ok(1.0),
+        "km" => Ok(1000.0),
+        "ft" => Ok(0.3048),
+        "mi" => Ok(1609.34),
+        _ => Err(Error(String::from("ERR unsupported unit provided. please use M, KM, FT, MI"))),
+    }
+}
+
+pub fn parse_coordinate(longitude: &str, latitude: &str) -> Result<(f64, f64), DataType> {
+    let longitude = parse_float(longitude)?;
+    let latitude = parse_float(latitude)?;
+
+    if !is_valid_coordinate(longitude, latitude) {
+        return Err(Error(format!("ERR invalid longitude,latitude pair {:.6},{:.6}", longitude, latitude)));
+    }
+
+    Ok((longitude, latitude))
+}
+
+pub fn parse_float(value: &str) -> Result<f64, DataType> {
+    match value.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(Error(String::from("ERR value is not a valid float"))),
+    }
+}
+
+/// Distances are returned with a precision of 4 decimals (like redis does)
+pub fn format_distance(distance_meters: f64, meters_per_unit: f64) -> DataType {
+    DataType::BulkString(format!("{:.4}", distance_meters / meters_per_unit))
+}
+
+pub fn format_coordinate(longitude: f64, latitude: f64) -> DataType {
+    DataType::Array(vec![
+        DataType::BulkString(longitude.to_string()),
+        DataType::BulkString(latitude.to_string()),
+    ])
+}
+
+/// Looks up the sorted set stored at key for reading. Returns an error if the key holds a value of another type
+pub fn get_sorted_set<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a SortedSet>, DataType> {
+    match store.get_ref(key) {
+        Some(store_object) => match store_object.value.as_ref() {
+            ObjectValue::SortedSet(sorted_set) => Ok(Some(sorted_set)),
+            _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Looks up the sorted set stored at key for modification. Returns an error if the key holds a value of another type
+pub fn get_sorted_set_mut<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut SortedSet>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::SortedSet(_))) {
+                Some(ObjectValue::SortedSet(sorted_set)) => Ok(Some(sorted_set)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
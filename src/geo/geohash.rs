@@ -0,0 +1,330 @@
+/// Geohash implementation based on the one used by redis
+/// see: https://github.com/redis/redis/blob/unstable/src/geohash.c
+///
+/// A geohash interleaves the bits of the latitude (even bits) and the longitude (odd bits) of a point.
+/// With 26 bits per coordinate we end up with a 52-bit integer, which can be stored without loss of precision
+/// as the score of a sorted set member. Points that are close to each other share a common prefix.
+///
+/// Latitudes are limited to the range covered by the web mercator projection (EPSG:3857).
+
+pub const GEO_STEP_MAX: u8 = 26;
+
+pub const GEO_LAT_MIN: f64 = -85.05112878;
+pub const GEO_LAT_MAX: f64 = 85.05112878;
+pub const GEO_LONG_MIN: f64 = -180.0;
+pub const GEO_LONG_MAX: f64 = 180.0;
+
+// earth's quadratic mean radius for WGS-84
+pub const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoHashBits {
+    pub bits: u64,
+    pub step: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoHashRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoHashArea {
+    pub hash: GeoHashBits,
+    pub longitude: GeoHashRange,
+    pub latitude: GeoHashRange,
+}
+
+pub struct GeoHashNeighbors {
+    pub north: GeoHashBits,
+    pub east: GeoHashBits,
+    pub west: GeoHashBits,
+    pub south: GeoHashBits,
+    pub north_east: GeoHashBits,
+    pub south_east: GeoHashBits,
+    pub north_west: GeoHashBits,
+    pub south_west: GeoHashBits,
+}
+
+pub fn is_valid_coordinate(longitude: f64, latitude: f64) -> bool {
+    (GEO_LONG_MIN..=GEO_LONG_MAX).contains(&longitude) && (GEO_LAT_MIN..=GEO_LAT_MAX).contains(&latitude)
+}
+
+/// Encodes the coordinate with the given precision (step = number of bits per coordinate)
+pub fn encode(longitude: f64, latitude: f64, step: u8) -> GeoHashBits {
+    encode_with_range(longitude, latitude, GEO_LONG_MIN, GEO_LONG_MAX, GEO_LAT_MIN, GEO_LAT_MAX, step)
+}
+
+/// Encodes the coordinate with full precision, the result can be used as a sorted set score
+pub fn encode_wgs84(longitude: f64, latitude: f64) -> u64 {
+    encode(longitude, latitude, GEO_STEP_MAX).bits
+}
+
+fn encode_with_range(longitude: f64, latitude: f64, long_min: f64, long_max: f64, lat_min: f64, lat_max: f64, step: u8) -> GeoHashBits {
+    let mut lat_offset = (latitude - lat_min) / (lat_max - lat_min);
+    let mut long_offset = (longitude - long_min) / (long_max - long_min);
+
+    // convert to fixed point based on the step size
+    lat_offset *= (1u64 << step) as f64;
+    long_offset *= (1u64 << step) as f64;
+
+    // the max value would overflow the available bits, so we clamp it to the last cell
+    let max_offset = ((1u64 << step) - 1) as f64;
+    let lat_offset = lat_offset.min(max_offset) as u32;
+    let long_offset = long_offset.min(max_offset) as u32;
+
+    GeoHashBits {
+        bits: interleave64(lat_offset, long_offset),
+        step,
+    }
+}
+
+/// Decodes the hash to the area it covers
+pub fn decode(hash: GeoHashBits) -> GeoHashArea {
+    let hash_sep = deinterleave64(hash.bits);
+
+    let lat_scale = GEO_LAT_MAX - GEO_LAT_MIN;
+    let long_scale = GEO_LONG_MAX - GEO_LONG_MIN;
+
+    let ilato = (hash_sep & 0xFFFFFFFF) as u32;
+    let ilono = (hash_sep >> 32) as u32;
+
+    let cells = (1u64 << hash.step) as f64;
+
+    GeoHashArea {
+        hash,
+        latitude: GeoHashRange {
+            min: GEO_LAT_MIN + (ilato as f64 / cells) * lat_scale,
+            max: GEO_LAT_MIN + ((ilato as f64 + 1.0) / cells) * lat_scale,
+        },
+        longitude: GeoHashRange {
+            min: GEO_LONG_MIN + (ilono as f64 / cells) * long_scale,
+            max: GEO_LONG_MIN + ((ilono as f64 + 1.0) / cells) * long_scale,
+        },
+    }
+}
+
+/// Decodes a full precision hash (e.g. a sorted set score) to the (longitude, latitude) at the center of its area
+pub fn decode_wgs84(bits: u64) -> (f64, f64) {
+    let area = decode(GeoHashBits { bits, step: GEO_STEP_MAX });
+
+    let longitude = ((area.longitude.min + area.longitude.max) / 2.0).clamp(GEO_LONG_MIN, GEO_LONG_MAX);
+    let latitude = ((area.latitude.min + area.latitude.max) / 2.0).clamp(GEO_LAT_MIN, GEO_LAT_MAX);
+
+    (longitude, latitude)
+}
+
+/// Converts the coordinate to the standard 11 character geohash string (see https://en.wikipedia.org/wiki/Geohash)
+/// The standard geohash uses a latitude range of [-90, 90] instead of the mercator range we use internally.
+pub fn to_geohash_string(longitude: f64, latitude: f64) -> String {
+    let hash = encode_with_range(longitude, latitude, GEO_LONG_MIN, GEO_LONG_MAX, -90.0, 90.0, GEO_STEP_MAX);
+
+    let mut result = String::with_capacity(11);
+    for i in 0..11 {
+        // we only have 52 bits, but the geohash string has 55 bits, so the last character is always the first one of the alphabet
+        let index = if i == 10 {
+            0
+        } else {
+            (hash.bits >> (52 - ((i + 1) * 5))) & 0x1f
+        };
+        result.push(GEOHASH_ALPHABET[index as usize] as char);
+    }
+
+    result
+}
+
+pub fn neighbors(hash: GeoHashBits) -> GeoHashNeighbors {
+    GeoHashNeighbors {
+        north: move_hash(hash, 0, 1),
+        east: move_hash(hash, 1, 0),
+        west: move_hash(hash, -1, 0),
+        south: move_hash(hash, 0, -1),
+        north_east: move_hash(hash, 1, 1),
+        south_east: move_hash(hash, 1, -1),
+        north_west: move_hash(hash, -1, 1),
+        south_west: move_hash(hash, -1, -1),
+    }
+}
+
+fn move_hash(hash: GeoHashBits, dx: i8, dy: i8) -> GeoHashBits {
+    let mut bits = hash.bits;
+
+    // longitude is stored in the odd bits
+    if dx != 0 {
+        let mut x = bits & 0xaaaaaaaaaaaaaaaa;
+        let y = bits & 0x5555555555555555;
+        let zz = 0x5555555555555555u64 >> (64 - hash.step as u32 * 2);
+
+        if dx > 0 {
+            x = x.wrapping_add(zz + 1);
+        } else {
+            x |= zz;
+            x = x.wrapping_sub(zz + 1);
+        }
+
+        x &= 0xaaaaaaaaaaaaaaaau64 >> (64 - hash.step as u32 * 2);
+        bits = x | y;
+    }
+
+    // latitude is stored in the even bits
+    if dy != 0 {
+        let x = bits & 0xaaaaaaaaaaaaaaaa;
+        let mut y = bits & 0x5555555555555555;
+        let zz = 0xaaaaaaaaaaaaaaaau64 >> (64 - hash.step as u32 * 2);
+
+        if dy > 0 {
+            y = y.wrapping_add(zz + 1);
+        } else {
+            y |= zz;
+            y = y.wrapping_sub(zz + 1);
+        }
+
+        y &= 0x5555555555555555u64 >> (64 - hash.step as u32 * 2);
+        bits = x | y;
+    }
+
+    GeoHashBits { bits, step: hash.step }
+}
+
+/// Estimates the geohash precision that is needed to cover the given radius with the 3x3 neighbor cells
+pub fn estimate_steps_by_radius(range_meters: f64, latitude: f64) -> u8 {
+    if range_meters == 0.0 {
+        return GEO_STEP_MAX;
+    }
+
+    let mut range_meters = range_meters;
+    let mut step: i32 = 1;
+    while range_meters < MERCATOR_MAX {
+        range_meters *= 2.0;
+        step += 1;
+    }
+    // make sure the range is included in most of the base cases
+    step -= 2;
+
+    // cells get narrower towards the poles, so we need bigger cells there
+    if !(-66.0..=66.0).contains(&latitude) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&latitude) {
+            step -= 1;
+        }
+    }
+
+    step.clamp(1, GEO_STEP_MAX as i32) as u8
+}
+
+/// Great-circle distance between two points in meters using the haversine formula
+pub fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let lat1r = lat1.to_radians();
+    let lat2r = lat2.to_radians();
+    let u = ((lat2r - lat1r) / 2.0).sin();
+    let v = ((lon2.to_radians() - lon1.to_radians()) / 2.0).sin();
+
+    let a = u * u + lat1r.cos() * lat2r.cos() * v * v;
+    2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+}
+
+/// Distance between two latitudes on the same meridian in meters
+pub fn latitude_distance(lat1: f64, lat2: f64) -> f64 {
+    EARTH_RADIUS_IN_METERS * (lat2.to_radians() - lat1.to_radians()).abs()
+}
+
+fn interleave64(x: u32, y: u32) -> u64 {
+    const B: [u64; 5] = [0x5555555555555555, 0x3333333333333333, 0x0F0F0F0F0F0F0F0F, 0x00FF00FF00FF00FF, 0x0000FFFF0000FFFF];
+    const S: [u32; 5] = [1, 2, 4, 8, 16];
+
+    let mut x = x as u64;
+    let mut y = y as u64;
+
+    for i in (0..5).rev() {
+        x = (x | (x << S[i])) & B[i];
+        y = (y | (y << S[i])) & B[i];
+    }
+
+    x | (y << 1)
+}
+
+/// Reverses interleave64, the x bits end up in the lower 32 bits and the y bits in the upper 32 bits
+fn deinterleave64(interleaved: u64) -> u64 {
+    const B: [u64; 6] = [0x5555555555555555, 0x3333333333333333, 0x0F0F0F0F0F0F0F0F, 0x00FF00FF00FF00FF, 0x0000FFFF0000FFFF, 0x00000000FFFFFFFF];
+    const S: [u32; 6] = [0, 1, 2, 4, 8, 16];
+
+    let mut x = interleaved;
+    let mut y = interleaved >> 1;
+
+    for i in 0..6 {
+        x = (x | (x >> S[i])) & B[i];
+        y = (y | (y >> S[i])) & B[i];
+    }
+
+    x | (y << 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        // given
+        let (longitude, latitude) = (13.361389, 38.115556);
+
+        // when
+        let bits = encode_wgs84(longitude, latitude);
+        let (decoded_longitude, decoded_latitude) = decode_wgs84(bits);
+
+        // then
+        assert_eq!(bits, 3479099956230698);
+        assert!((decoded_longitude - longitude).abs() < 0.00001);
+        assert!((decoded_latitude - latitude).abs() < 0.00001);
+        assert_eq!(encode_wgs84(decoded_longitude, decoded_latitude), bits);
+    }
+
+    #[test]
+    fn test_to_geohash_string() {
+        // when
+        let palermo = to_geohash_string(13.361389, 38.115556);
+        let catania = to_geohash_string(15.087269, 37.502669);
+
+        // then
+        assert_eq!(palermo, "sqc8b49rny0");
+        assert_eq!(catania, "sqdtr74hyu0");
+    }
+
+    #[test]
+    fn test_distance() {
+        // given
+        let (palermo_longitude, palermo_latitude) = decode_wgs84(encode_wgs84(13.361389, 38.115556));
+        let (catania_longitude, catania_latitude) = decode_wgs84(encode_wgs84(15.087269, 37.502669));
+
+        // when
+        let distance = distance(palermo_longitude, palermo_latitude, catania_longitude, catania_latitude);
+
+        // then
+        assert!((distance - 166274.1516).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        // given
+        let hash = encode(13.361389, 38.115556, 10);
+        let area = decode(hash);
+
+        // when
+        let neighbors = neighbors(hash);
+        let north = decode(neighbors.north);
+        let east = decode(neighbors.east);
+        let south_west = decode(neighbors.south_west);
+
+        // then
+        assert!((north.latitude.min - area.latitude.max).abs() < 1e-9);
+        assert_eq!(north.longitude, area.longitude);
+        assert!((east.longitude.min - area.longitude.max).abs() < 1e-9);
+        assert_eq!(east.latitude, area.latitude);
+        assert!((south_west.latitude.max - area.latitude.min).abs() < 1e-9);
+        assert!((south_west.longitude.max - area.longitude.min).abs() < 1e-9);
+    }
+}
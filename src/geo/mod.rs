@@ -0,0 +1,3 @@
+pub mod geohash;
+pub mod geo_helper;
+pub mod search;
@@ -0,0 +1,248 @@
+use crate::geo::geohash::{decode, decode_wgs84, distance, encode, estimate_steps_by_radius, latitude_distance, neighbors, EARTH_RADIUS_IN_METERS, GeoHashBits, GEO_STEP_MAX};
+use crate::sorted_set::SortedSet;
+
+/// Implements the search of sorted set members within a shape (radius or box) around a center point
+/// see: https://github.com/redis/redis/blob/unstable/src/geohash_helper.c
+///
+/// Instead of checking the distance of every member, we calculate a geohash precision for which the
+/// cell of the center together with its 8 neighbors covers the whole search area.
+/// Every cell corresponds to a continuous score range in the sorted set, so we only need to do 9 range queries
+/// and check the distance of the members within those ranges.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeoShape {
+    Radius { radius_meters: f64 },
+    Box { width_meters: f64, height_meters: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoSearchResult {
+    pub member: String,
+    pub score: f64,
+    pub distance_meters: f64,
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+struct BoundingBox {
+    min_longitude: f64,
+    min_latitude: f64,
+    max_longitude: f64,
+    max_latitude: f64,
+}
+
+/// Returns all members of the sorted set that are located within the shape around the center point (unsorted)
+/// If `limit` is set, the search stops as soon as enough matches were found (the ANY option)
+pub fn search(sorted_set: &SortedSet, longitude: f64, latitude: f64, shape: GeoShape, limit: Option<usize>) -> Vec<GeoSearchResult> {
+    let mut results = Vec::new();
+    let mut visited_cells: Vec<GeoHashBits> = Vec::with_capacity(9);
+
+    for cell in get_cells_to_search(longitude, latitude, shape) {
+        // neighbors can be the same cell for very big steps, we do not want to return members twice
+        if visited_cells.contains(&cell) {
+            continue;
+        }
+        visited_cells.push(cell);
+
+        let (min_score, max_score) = get_score_range(cell);
+        for (member, score) in sorted_set.range_by_score(min_score, max_score) {
+            let (member_longitude, member_latitude) = decode_wgs84(score as u64);
+
+            if let Some(distance_meters) = get_distance_if_in_shape(shape, longitude, latitude, member_longitude, member_latitude) {
+                results.push(GeoSearchResult {
+                    member: member.to_string(),
+                    score,
+                    distance_meters,
+                    longitude: member_longitude,
+                    latitude: member_latitude,
+                });
+
+                if limit.is_some_and(|limit| results.len() >= limit) {
+                    return results;
+                }
+            }
+        }
+    }
+
+    results
+}
+
+fn get_cells_to_search(longitude: f64, latitude: f64, shape: GeoShape) -> Vec<GeoHashBits> {
+    let bounds = get_bounding_box(longitude, latitude, shape);
+    let radius_meters = match shape {
+        GeoShape::Radius { radius_meters } => radius_meters,
+        GeoShape::Box { width_meters, height_meters } => {
+            ((width_meters / 2.0).powi(2) + (height_meters / 2.0).powi(2)).sqrt()
+        }
+    };
+
+    let mut steps = estimate_steps_by_radius(radius_meters, latitude);
+    let mut hash = encode(longitude, latitude, steps);
+    let mut hash_neighbors = neighbors(hash);
+    let mut area = decode(hash);
+
+    // the estimated step is not always enough to cover the whole search area (e.g. if the center is near the edge of its cell)
+    // in this case we reduce the precision by one step which doubles the size of the cells
+    let north = decode(hash_neighbors.north);
+    let south = decode(hash_neighbors.south);
+    let east = decode(hash_neighbors.east);
+    let west = decode(hash_neighbors.west);
+
+    if steps > 1 && (north.latitude.max < bounds.max_latitude
+        || south.latitude.min > bounds.min_latitude
+        || east.longitude.max < bounds.max_longitude
+        || west.longitude.min > bounds.min_longitude) {
+        steps -= 1;
+        hash = encode(longitude, latitude, steps);
+        hash_neighbors = neighbors(hash);
+        area = decode(hash);
+    }
+
+    // exclude neighbors which can not contain any match, as the search area does not reach into them
+    let exclude_north = steps >= 2 && area.latitude.max > bounds.max_latitude;
+    let exclude_south = steps >= 2 && area.latitude.min < bounds.min_latitude;
+    let exclude_east = steps >= 2 && area.longitude.max > bounds.max_longitude;
+    let exclude_west = steps >= 2 && area.longitude.min < bounds.min_longitude;
+
+    let candidates = [
+        (hash_neighbors.north, exclude_north),
+        (hash_neighbors.south, exclude_south),
+        (hash_neighbors.east, exclude_east),
+        (hash_neighbors.west, exclude_west),
+        (hash_neighbors.north_east, exclude_north || exclude_east),
+        (hash_neighbors.north_west, exclude_north || exclude_west),
+        (hash_neighbors.south_east, exclude_south || exclude_east),
+        (hash_neighbors.south_west, exclude_south || exclude_west),
+    ];
+
+    let mut cells = vec![hash];
+    for (cell, excluded) in candidates {
+        if !excluded {
+            cells.push(cell);
+        }
+    }
+
+    cells
+}
+
+fn get_bounding_box(longitude: f64, latitude: f64, shape: GeoShape) -> BoundingBox {
+    let (height_meters, width_meters) = match shape {
+        GeoShape::Radius { radius_meters } => (radius_meters * 2.0, radius_meters * 2.0),
+        GeoShape::Box { width_meters, height_meters } => (height_meters, width_meters),
+    };
+
+    let lat_delta = (height_meters / 2.0 / EARTH_RADIUS_IN_METERS).to_degrees();
+    let long_delta_top = (width_meters / 2.0 / EARTH_RADIUS_IN_METERS / (latitude + lat_delta).to_radians().cos()).to_degrees();
+    let long_delta_bottom = (width_meters / 2.0 / EARTH_RADIUS_IN_METERS / (latitude - lat_delta).to_radians().cos()).to_degrees();
+
+    // the longitude delta is bigger on the side closer to the pole
+    let long_delta = if latitude < 0.0 { long_delta_bottom } else { long_delta_top };
+
+    BoundingBox {
+        min_longitude: longitude - long_delta,
+        min_latitude: latitude - lat_delta,
+        max_longitude: longitude + long_delta,
+        max_latitude: latitude + lat_delta,
+    }
+}
+
+/// Every cell covers the scores of all full precision hashes that start with the bits of the cell
+fn get_score_range(cell: GeoHashBits) -> (f64, f64) {
+    let shift = (GEO_STEP_MAX - cell.step) as u32 * 2;
+    let min = cell.bits << shift;
+    let max = (cell.bits + 1) << shift;
+    (min as f64, max as f64)
+}
+
+fn get_distance_if_in_shape(shape: GeoShape, center_longitude: f64, center_latitude: f64, longitude: f64, latitude: f64) -> Option<f64> {
+    match shape {
+        GeoShape::Radius { radius_meters } => {
+            let distance_meters = distance(center_longitude, center_latitude, longitude, latitude);
+            if distance_meters > radius_meters {
+                return None;
+            }
+            Some(distance_meters)
+        }
+        GeoShape::Box { width_meters, height_meters } => {
+            // the latitude distance is cheaper to calculate, so we check it first
+            if latitude_distance(latitude, center_latitude) > height_meters / 2.0 {
+                return None;
+            }
+            if distance(longitude, latitude, center_longitude, latitude) > width_meters / 2.0 {
+                return None;
+            }
+            Some(distance(center_longitude, center_latitude, longitude, latitude))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geo::geohash::encode_wgs84;
+
+    use super::*;
+
+    fn get_test_set() -> SortedSet {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("Palermo", encode_wgs84(13.361389, 38.115556) as f64);
+        sorted_set.insert("Catania", encode_wgs84(15.087269, 37.502669) as f64);
+        sorted_set.insert("edge1", encode_wgs84(12.758489, 38.788135) as f64);
+        sorted_set.insert("edge2", encode_wgs84(17.241510, 38.788135) as f64);
+        sorted_set
+    }
+
+    #[test]
+    fn test_search_by_radius() {
+        // given
+        let sorted_set = get_test_set();
+
+        // when
+        let mut results = search(&sorted_set, 15.0, 37.0, GeoShape::Radius { radius_meters: 200_000.0 }, None);
+        results.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+
+        // then
+        let members = results.iter().map(|result| result.member.as_str()).collect::<Vec<_>>();
+        assert_eq!(members, vec!["Catania", "Palermo"]);
+        assert!((results[0].distance_meters - 56441.2579).abs() < 0.001);
+        assert!((results[1].distance_meters - 190442.4298).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_search_by_box() {
+        // given
+        let sorted_set = get_test_set();
+
+        // when
+        let mut results = search(&sorted_set, 15.0, 37.0, GeoShape::Box { width_meters: 400_000.0, height_meters: 400_000.0 }, None);
+        results.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+
+        // then
+        let members = results.iter().map(|result| result.member.as_str()).collect::<Vec<_>>();
+        assert_eq!(members, vec!["Catania", "Palermo", "edge2", "edge1"]);
+    }
+
+    #[test]
+    fn test_search_matches_brute_force() {
+        // given
+        let mut sorted_set = SortedSet::new();
+        let mut i = 0;
+        for longitude in (-50..50).map(|x| x as f64 * 0.013) {
+            for latitude in (-50..50).map(|y| 40.0 + y as f64 * 0.009) {
+                sorted_set.insert(&format!("point{}", i), encode_wgs84(longitude, latitude) as f64);
+                i += 1;
+            }
+        }
+
+        for radius_meters in [100.0, 1_000.0, 10_000.0, 50_000.0] {
+            // when
+            let results = search(&sorted_set, 0.1, 40.05, GeoShape::Radius { radius_meters }, None);
+
+            // then
+            let expected = sorted_set.iter().filter(|(_, score)| {
+                let (longitude, latitude) = decode_wgs84(*score as u64);
+                distance(0.1, 40.05, longitude, latitude) <= radius_meters
+            }).count();
+            assert_eq!(results.len(), expected);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+/// Glob-style pattern matching of KEYS and SCAN
+/// see: https://redis.io/commands/keys/
+///
+/// *       any sequence of characters (also empty)
+/// ?       exactly one character
+/// [abc]   one of the characters, `[a-z]` a range and `[^a]` any character except the given ones
+/// \x      the character x, used to match the special characters literally
+///
+/// A `*` is matched by first trying to match the rest of the pattern at the current position and extending the
+/// sequence by one character on failure. Only the last `*` has to be retried, so patterns with many stars
+/// can not cause exponential backtracking.
+
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let mut pattern_pos = 0;
+    let mut text_pos = 0;
+    // position of the last star in the pattern and the text position it currently extends to
+    let mut star: Option<(usize, usize)> = None;
+
+    while text_pos < text.len() {
+        if pattern.get(pattern_pos) == Some(&'*') {
+            star = Some((pattern_pos, text_pos));
+            pattern_pos += 1;
+            continue;
+        }
+
+        if let Some(next_pos) = match_char(&pattern, pattern_pos, text[text_pos]) {
+            pattern_pos = next_pos;
+            text_pos += 1;
+            continue;
+        }
+
+        match star {
+            Some((star_pos, star_text_pos)) => {
+                pattern_pos = star_pos + 1;
+                text_pos = star_text_pos + 1;
+                star = Some((star_pos, text_pos));
+            }
+            None => return false,
+        }
+    }
+
+    while pattern.get(pattern_pos) == Some(&'*') {
+        pattern_pos += 1;
+    }
+    pattern_pos == pattern.len()
+}
+
+/// Matches one character against the pattern element at pos and returns the position of the next element
+fn match_char(pattern: &[char], pos: usize, char: char) -> Option<usize> {
+    match pattern.get(pos)? {
+        '?' => Some(pos + 1),
+        '[' => match_class(pattern, pos, char),
+        '\\' if pos + 1 < pattern.len() => (pattern[pos + 1] == char).then_some(pos + 2),
+        expected => (*expected == char).then_some(pos + 1),
+    }
+}
+
+/// Matches a character class starting at pos, an unterminated class ends with the pattern
+fn match_class(pattern: &[char], pos: usize, char: char) -> Option<usize> {
+    let mut i = pos + 1;
+    let negate = pattern.get(i) == Some(&'^');
+    if negate {
+        i += 1;
+    }
+
+    let mut matched = false;
+    while i < pattern.len() && pattern[i] != ']' {
+        if pattern[i] == '\\' && i + 1 < pattern.len() {
+            matched |= pattern[i + 1] == char;
+            i += 2;
+        } else if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            // reversed ranges like [z-a] are matched like [a-z]
+            let (start, end) = if pattern[i] <= pattern[i + 2] { (pattern[i], pattern[i + 2]) } else { (pattern[i + 2], pattern[i]) };
+            matched |= start <= char && char <= end;
+            i += 3;
+        } else {
+            matched |= pattern[i] == char;
+            i += 1;
+        }
+    }
+
+    let next_pos = if i < pattern.len() { i + 1 } else { i };
+    (matched != negate).then_some(next_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        let expected = [
+            ("*", "", true), ("*", "anything", true), ("h?llo", "hello", true), ("h?llo", "hllo", false),
+            ("h*llo", "hllo", true), ("h*llo", "heeeello", true), ("h*llo", "hello world", false),
+            ("h[ae]llo", "hallo", true), ("h[ae]llo", "hillo", false), ("h[^e]llo", "hallo", true),
+            ("h[^e]llo", "hello", false), ("h[a-b]llo", "hbllo", true), ("h[b-a]llo", "hallo", true),
+            ("h[a-b]llo", "hcllo", false), ("user:\\*", "user:*", true), ("user:\\*", "user:1", false),
+            ("h[\\]]llo", "h]llo", true), ("*:*:name", "user:1:name", true), ("*:*:name", "user:1", false),
+            ("a*b*c*d", "aXbYcZd", true), ("a*a*a*a*b", "aaaaaaaaaaaaaaaaaaaaaaaa", false), ("abc[", "abc", false),
+        ];
+
+        for (pattern, text, is_match) in expected {
+            assert_eq!(glob_match(pattern, text), is_match, "{} against {}", pattern, text);
+        }
+    }
+}
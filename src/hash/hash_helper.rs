@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::object_type_encoding::{OBJ_ENCODING_HT, OBJ_TYPE_HASH};
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::{estimate_from_samples, ObjectValue, Store};
+
+/// Shared lookup functions of the hash (H*) commands
+
+/// Looks up the hash stored at key. Returns an error if the key holds a value of another type
+pub fn get_hash<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut HashMap<String, String>>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::Hash(_))) {
+                Some(ObjectValue::Hash(hash)) => Ok(Some(hash)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn get_or_create_hash<'a>(store: &'a mut Store, key: &str) -> Result<&'a mut HashMap<String, String>, DataType> {
+    if get_hash(store, key)?.is_none() {
+        store.put(key, ObjectValue::Hash(HashMap::new()), -1, OBJ_TYPE_HASH | OBJ_ENCODING_HT);
+    }
+
+    Ok(get_hash(store, key)?.expect("Hash not found after creation"))
+}
+
+/// Estimated number of bytes used by the hash
+pub fn get_hash_memory_usage(hash: &HashMap<String, String>, samples: usize) -> u64 {
+    let entry_size = std::mem::size_of::<(String, String)>();
+    let entry_sizes = hash.iter().map(|(field, value)| (field.capacity() + value.capacity() + entry_size) as u64);
+    estimate_from_samples(entry_sizes, hash.len(), samples) + std::mem::size_of::<HashMap<String, String>>() as u64
+}
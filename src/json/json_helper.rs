@@ -0,0 +1,100 @@
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::json::json_parser::parse_json;
+use crate::json::json_path::{get_at, get_at_mut, JsonPath};
+use crate::json::json_value::JsonValue;
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, Error, Null};
+use crate::store::{ObjectValue, Store};
+
+/// Shared lookup, parsing and response functions of the JSON commands
+
+pub const KEY_NOT_FOUND_ERROR: &str = "ERR could not perform this operation on a key that doesn't exist";
+
+/// Looks up the JSON document stored at key. Returns an error if the key holds a value of another type
+pub fn get_json_document<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut JsonValue>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::Json(_))) {
+                Some(ObjectValue::Json(document)) => Ok(Some(document)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn parse_path(path: &str) -> Result<JsonPath, DataType> {
+    JsonPath::parse(path).map_err(|err| Error(format!("ERR {}", err)))
+}
+
+pub fn parse_value(value: &str) -> Result<JsonValue, DataType> {
+    parse_json(value).map_err(|err| Error(format!("ERR {}", err)))
+}
+
+pub fn path_not_found(path: &JsonPath) -> DataType {
+    Error(format!("ERR Path '{}' does not exist", path.text))
+}
+
+/// Applies the function to the elements matching the path.
+/// The function returns None if the element has a type the operation is not supported for.
+///
+/// For JSONPath paths the function is applied to all matching elements.
+/// For legacy paths it is only applied to the first match and a missing element or a wrong type results in an error.
+pub fn apply_to_path<T, F>(document: &mut JsonValue, path: &JsonPath, expected_type: &str, mut function: F) -> Result<Vec<Option<T>>, DataType>
+where
+    F: FnMut(&mut JsonValue) -> Result<Option<T>, DataType>,
+{
+    let locations = path.find(document);
+
+    if path.is_legacy {
+        let location = match locations.first() {
+            Some(location) => location,
+            None => return Err(path_not_found(path)),
+        };
+
+        let value = get_at_mut(document, location).expect("Location of match not found");
+        let actual_type = value.type_name();
+
+        return match function(value)? {
+            Some(result) => Ok(vec![Some(result)]),
+            None => Err(Error(format!("ERR wrong type of path value - expected {} but found {}", expected_type, actual_type))),
+        };
+    }
+
+    let mut results = Vec::with_capacity(locations.len());
+    for location in locations.iter() {
+        let value = get_at_mut(document, location).expect("Location of match not found");
+        results.push(function(value)?);
+    }
+
+    Ok(results)
+}
+
+/// Legacy paths reply with the single result, JSONPath paths with an array of all results
+pub fn to_response<T, F>(results: Vec<Option<T>>, path: &JsonPath, map: F) -> DataType
+where
+    F: Fn(T) -> DataType,
+{
+    if path.is_legacy {
+        return match results.into_iter().next().flatten() {
+            Some(result) => map(result),
+            None => Null,
+        };
+    }
+
+    Array(results.into_iter().map(|result| result.map(&map).unwrap_or(Null)).collect())
+}
+
+/// Returns the matching elements of the path. Legacy paths return the first match, JSONPath paths an array of all matches
+pub fn get_path_value(document: &JsonValue, path: &JsonPath) -> Result<JsonValue, DataType> {
+    let locations = path.find(document);
+
+    if path.is_legacy {
+        return match locations.first() {
+            Some(location) => Ok(get_at(document, location).expect("Location of match not found").clone()),
+            None => Err(path_not_found(path)),
+        };
+    }
+
+    Ok(JsonValue::Array(locations.iter().map(|location| get_at(document, location).expect("Location of match not found").clone()).collect()))
+}
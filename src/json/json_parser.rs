@@ -0,0 +1,345 @@
+use crate::json::json_value::JsonValue;
+
+/// Parser for JSON documents as defined in https://www.rfc-editor.org/rfc/rfc8259
+///
+/// Architecture
+/// Simple recursive descent parser working on the bytes of the input.
+/// Nesting is limited to protect the server stack from malicious documents.
+
+const MAX_DEPTH: usize = 128;
+
+pub struct JsonParser<'a> {
+    input: &'a [u8],
+    position: usize,
+    depth: usize,
+}
+
+pub fn parse_json(input: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser {
+        input: input.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    if parser.position < parser.input.len() {
+        return Err(parser.error("trailing characters"));
+    }
+
+    Ok(value)
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("expected value")),
+            None => Err(self.error("EOF while parsing a value")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.enter()?;
+        // skip {
+        self.position += 1;
+
+        let mut entries: Vec<(String, JsonValue)> = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            self.depth -= 1;
+            return Ok(JsonValue::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("key must be a string"));
+            }
+            let key = self.parse_string()?;
+
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+
+            // duplicate keys: the last one wins
+            match entries.iter_mut().find(|(entry_key, _)| *entry_key == key) {
+                Some((_, existing)) => *existing = value,
+                None => entries.push((key, value)),
+            }
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => break,
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(JsonValue::Object(entries))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        self.enter()?;
+        // skip [
+        self.position += 1;
+
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            self.depth -= 1;
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => break,
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(JsonValue::Array(values))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        // skip "
+        self.position += 1;
+
+        let mut bytes = Vec::new();
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    match self.next() {
+                        Some(b'"') => bytes.push(b'"'),
+                        Some(b'\\') => bytes.push(b'\\'),
+                        Some(b'/') => bytes.push(b'/'),
+                        Some(b'b') => bytes.push(0x08),
+                        Some(b'f') => bytes.push(0x0c),
+                        Some(b'n') => bytes.push(b'\n'),
+                        Some(b'r') => bytes.push(b'\r'),
+                        Some(b't') => bytes.push(b'\t'),
+                        Some(b'u') => {
+                            let c = self.parse_unicode_escape()?;
+                            let mut buffer = [0; 4];
+                            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                Some(byte) if byte < 0x20 => return Err(self.error("control character found while parsing a string")),
+                Some(byte) => bytes.push(byte),
+                None => return Err(self.error("EOF while parsing a string")),
+            }
+        }
+
+        // the input is a valid utf-8 string and escapes are encoded as utf-8, so this can not fail
+        String::from_utf8(bytes).map_err(|_| self.error("invalid unicode"))
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+
+        // characters outside the basic multilingual plane are encoded as utf-16 surrogate pair
+        if (0xD800..0xDC00).contains(&high) {
+            if self.next() != Some(b'\\') || self.next() != Some(b'u') {
+                return Err(self.error("lone leading surrogate in hex escape"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid surrogate in hex escape"));
+            }
+            let code_point = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+            return char::from_u32(code_point).ok_or_else(|| self.error("invalid unicode code point"));
+        }
+
+        char::from_u32(high).ok_or_else(|| self.error("invalid unicode code point"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        if self.position + 4 > self.input.len() {
+            return Err(self.error("EOF while parsing a string"));
+        }
+
+        let hex = std::str::from_utf8(&self.input[self.position..self.position + 4]).map_err(|_| self.error("invalid escape"))?;
+        let value = u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid escape"))?;
+        self.position += 4;
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.position;
+        let mut is_float = false;
+
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+
+        match self.peek() {
+            Some(b'0') => {
+                self.position += 1;
+            }
+            Some(b'1'..=b'9') => {
+                self.skip_digits();
+            }
+            _ => return Err(self.error("invalid number")),
+        }
+
+        if self.peek() == Some(b'.') {
+            is_float = true;
+            self.position += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+
+        if matches!(self.peek(), Some(b'e') | Some(b'E')) {
+            is_float = true;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+') | Some(b'-')) {
+                self.position += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+
+        // the number only consists of ascii characters
+        let number = std::str::from_utf8(&self.input[start..self.position]).unwrap();
+
+        if !is_float {
+            if let Ok(integer) = number.parse::<i64>() {
+                return Ok(JsonValue::Integer(integer));
+            }
+        }
+
+        match number.parse::<f64>() {
+            Ok(float) if float.is_finite() => Ok(JsonValue::Float(float)),
+            _ => Err(self.error("number out of range")),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, String> {
+        if self.input[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            return Ok(value);
+        }
+
+        Err(self.error("expected value"))
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("recursion limit exceeded"));
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        if self.next() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected as char)));
+        }
+        Ok(())
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek();
+        if byte.is_some() {
+            self.position += 1;
+        }
+        byte
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at position {}", message, self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scalars() {
+        // when
+        let null = parse_json("null").expect("Can not parse JSON");
+        let boolean = parse_json(" true ").expect("Can not parse JSON");
+        let integer = parse_json("-42").expect("Can not parse JSON");
+        let float = parse_json("3.5e2").expect("Can not parse JSON");
+        let string = parse_json(r#""a\"bä😀""#).expect("Can not parse JSON");
+
+        // then
+        assert_eq!(null, JsonValue::Null);
+        assert_eq!(boolean, JsonValue::Bool(true));
+        assert_eq!(integer, JsonValue::Integer(-42));
+        assert_eq!(float, JsonValue::Float(350.0));
+        assert_eq!(string, JsonValue::String(String::from("a\"bä😀")));
+    }
+
+    #[test]
+    fn test_parse_nested() {
+        // when
+        let value = parse_json(r#"{"a": [1, 2.5, {"b": null}], "c": "d"}"#).expect("Can not parse JSON");
+
+        // then
+        let expected = JsonValue::Object(vec![
+            (String::from("a"), JsonValue::Array(vec![
+                JsonValue::Integer(1),
+                JsonValue::Float(2.5),
+                JsonValue::Object(vec![(String::from("b"), JsonValue::Null)]),
+            ])),
+            (String::from("c"), JsonValue::String(String::from("d"))),
+        ]);
+        assert_eq!(value, expected);
+        assert_eq!(value.to_json_string(), r#"{"a":[1,2.5,{"b":null}],"c":"d"}"#);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_json("").is_err());
+        assert!(parse_json("{").is_err());
+        assert!(parse_json("[1,]").is_err());
+        assert!(parse_json("{\"a\" 1}").is_err());
+        assert!(parse_json("01").is_err());
+        assert!(parse_json("1 2").is_err());
+        assert!(parse_json(&"[".repeat(MAX_DEPTH + 1)).is_err());
+    }
+}
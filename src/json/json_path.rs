@@ -0,0 +1,498 @@
+use crate::json::json_value::JsonValue;
+
+/// Implementation of the JSONPath syntax supported by the JSON commands
+/// see: https://redis.io/docs/data-types/json/path/
+///
+/// Supported selectors:
+/// * `$` root element
+/// * `.key` and `['key']` child element
+/// * `.*` and `[*]` all children
+/// * `..key` and `..*` recursive descent
+/// * `[0]`, `[-1]` array index
+/// * `[0,2]`, `['a','b']` union
+/// * `[start:end:step]` array slice
+///
+/// Paths that do not start with `$` use the legacy path syntax (e.g. `.a.b` or `a[0]`).
+/// They support the same selectors, but commands only return the first match instead of an array of all matches.
+/// Filter expressions (`[?(...)]`) are not supported.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>, i64),
+    Union(Vec<PathSegment>),
+    RecursiveKey(String),
+    RecursiveWildcard,
+}
+
+/// A single step to a concrete location within a document
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathElement {
+    Key(String),
+    Index(usize),
+}
+
+pub type Location = Vec<PathElement>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    pub text: String,
+    pub segments: Vec<PathSegment>,
+    pub is_legacy: bool,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<JsonPath, String> {
+        let (is_legacy, rest) = match path.strip_prefix('$') {
+            Some(rest) => (false, rest.to_string()),
+            None => {
+                // legacy paths are relative to the root element, the leading dot is optional
+                if path == "." {
+                    (true, String::new())
+                } else if path.starts_with('.') || path.starts_with('[') {
+                    (true, path.to_string())
+                } else {
+                    (true, format!(".{}", path))
+                }
+            }
+        };
+
+        let segments = PathTokenizer { input: rest.chars().collect(), position: 0 }.parse_segments()?;
+
+        Ok(JsonPath {
+            text: path.to_string(),
+            segments,
+            is_legacy,
+        })
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Splits the path into its parent path and the last key, used to add new keys to objects
+    pub fn split_last_key(&self) -> Option<(JsonPath, String)> {
+        match self.segments.last() {
+            Some(PathSegment::Key(key)) => {
+                let parent = JsonPath {
+                    text: self.text.clone(),
+                    segments: self.segments[..self.segments.len() - 1].to_vec(),
+                    is_legacy: self.is_legacy,
+                };
+                Some((parent, key.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the locations of all elements of the document matching the path
+    pub fn find(&self, root: &JsonValue) -> Vec<Location> {
+        let mut current: Vec<(Location, &JsonValue)> = vec![(Vec::new(), root)];
+
+        for segment in self.segments.iter() {
+            let mut next = Vec::new();
+            for (location, value) in current {
+                select(segment, location, value, &mut next);
+            }
+            current = next;
+        }
+
+        let mut locations: Vec<Location> = Vec::with_capacity(current.len());
+        for (location, _) in current {
+            // recursive descent can match the same element more than once
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+        }
+        locations
+    }
+}
+
+fn select<'a>(segment: &PathSegment, location: Location, value: &'a JsonValue, result: &mut Vec<(Location, &'a JsonValue)>) {
+    match segment {
+        PathSegment::Key(key) => {
+            if let Some(child) = value.get_key(key) {
+                result.push((child_location(&location, PathElement::Key(key.clone())), child));
+            }
+        }
+        PathSegment::Index(index) => {
+            if let JsonValue::Array(values) = value {
+                if let Some(index) = normalize_index(*index, values.len()) {
+                    result.push((child_location(&location, PathElement::Index(index)), &values[index]));
+                }
+            }
+        }
+        PathSegment::Wildcard => {
+            select_children(&location, value, result);
+        }
+        PathSegment::Slice(start, end, step) => {
+            if let JsonValue::Array(values) = value {
+                let len = values.len() as i64;
+                let resolve = |index: i64| if index < 0 { (len + index).max(0) } else { index.min(len) };
+                let start = resolve(start.unwrap_or(0));
+                let end = resolve(end.unwrap_or(len));
+
+                let mut i = start;
+                while i < end {
+                    result.push((child_location(&location, PathElement::Index(i as usize)), &values[i as usize]));
+                    i += step;
+                }
+            }
+        }
+        PathSegment::Union(segments) => {
+            for segment in segments {
+                select(segment, location.clone(), value, result);
+            }
+        }
+        PathSegment::RecursiveKey(key) => {
+            let mut descendants = vec![(location.clone(), value)];
+            select_descendants_of(&location, value, &mut descendants);
+            for (location, value) in descendants {
+                select(&PathSegment::Key(key.clone()), location, value, result);
+            }
+        }
+        PathSegment::RecursiveWildcard => {
+            select_descendants_of(&location, value, result);
+        }
+    }
+}
+
+fn select_children<'a>(location: &Location, value: &'a JsonValue, result: &mut Vec<(Location, &'a JsonValue)>) {
+    match value {
+        JsonValue::Array(values) => {
+            for (i, child) in values.iter().enumerate() {
+                result.push((child_location(location, PathElement::Index(i)), child));
+            }
+        }
+        JsonValue::Object(entries) => {
+            for (key, child) in entries.iter() {
+                result.push((child_location(location, PathElement::Key(key.clone())), child));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn select_descendants_of<'a>(location: &Location, value: &'a JsonValue, result: &mut Vec<(Location, &'a JsonValue)>) {
+    let mut children = Vec::new();
+    select_children(location, value, &mut children);
+
+    for (child_location, child) in children {
+        result.push((child_location.clone(), child));
+        select_descendants_of(&child_location, child, result);
+    }
+}
+
+fn child_location(location: &Location, element: PathElement) -> Location {
+    let mut child = location.clone();
+    child.push(element);
+    child
+}
+
+/// Resolves negative indexes relative to the end of the array
+pub fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    if index < 0 || index >= len as i64 {
+        return None;
+    }
+    Some(index as usize)
+}
+
+pub fn get_at<'a>(root: &'a JsonValue, location: &Location) -> Option<&'a JsonValue> {
+    let mut current = root;
+    for element in location {
+        current = match (element, current) {
+            (PathElement::Key(key), value) => value.get_key(key)?,
+            (PathElement::Index(index), JsonValue::Array(values)) => values.get(*index)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+pub fn get_at_mut<'a>(root: &'a mut JsonValue, location: &Location) -> Option<&'a mut JsonValue> {
+    let mut current = root;
+    for element in location {
+        current = match (element, current) {
+            (PathElement::Key(key), value) => value.get_key_mut(key)?,
+            (PathElement::Index(index), JsonValue::Array(values)) => values.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Removes the element at the location. The root element can not be removed
+pub fn remove_at(root: &mut JsonValue, location: &Location) -> Option<JsonValue> {
+    let (last, parent_location) = location.split_last()?;
+    let parent = get_at_mut(root, &parent_location.to_vec())?;
+
+    match (last, parent) {
+        (PathElement::Key(key), JsonValue::Object(entries)) => {
+            let position = entries.iter().position(|(entry_key, _)| entry_key == key)?;
+            Some(entries.remove(position).1)
+        }
+        (PathElement::Index(index), JsonValue::Array(values)) if *index < values.len() => {
+            Some(values.remove(*index))
+        }
+        _ => None,
+    }
+}
+
+struct PathTokenizer {
+    input: Vec<char>,
+    position: usize,
+}
+
+impl PathTokenizer {
+    fn parse_segments(&mut self) -> Result<Vec<PathSegment>, String> {
+        let mut segments = Vec::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '.' => {
+                    self.position += 1;
+                    if self.peek() == Some('.') {
+                        self.position += 1;
+                        if self.peek() == Some('*') {
+                            self.position += 1;
+                            segments.push(PathSegment::RecursiveWildcard);
+                        } else {
+                            segments.push(PathSegment::RecursiveKey(self.parse_name()?));
+                        }
+                    } else if self.peek() == Some('*') {
+                        self.position += 1;
+                        segments.push(PathSegment::Wildcard);
+                    } else {
+                        segments.push(PathSegment::Key(self.parse_name()?));
+                    }
+                }
+                '[' => {
+                    self.position += 1;
+                    segments.push(self.parse_bracket()?);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+
+        Ok(segments)
+    }
+
+    fn parse_name(&mut self) -> Result<String, String> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if c == '.' || c == '[' {
+                break;
+            }
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Err(self.error());
+        }
+
+        Ok(self.input[start..self.position].iter().collect())
+    }
+
+    fn parse_bracket(&mut self) -> Result<PathSegment, String> {
+        self.skip_whitespace();
+
+        let segment = match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                PathSegment::Wildcard
+            }
+            Some('\'') | Some('"') => {
+                let mut keys = vec![PathSegment::Key(self.parse_quoted()?)];
+                self.skip_whitespace();
+                while self.peek() == Some(',') {
+                    self.position += 1;
+                    self.skip_whitespace();
+                    keys.push(PathSegment::Key(self.parse_quoted()?));
+                    self.skip_whitespace();
+                }
+                to_segment(keys)
+            }
+            Some(c) if c == '-' || c == ':' || c.is_ascii_digit() => {
+                let first = self.parse_optional_int()?;
+                self.skip_whitespace();
+
+                if self.peek() == Some(':') {
+                    self.position += 1;
+                    let end = self.parse_optional_int()?;
+                    let mut step = 1;
+                    if self.peek() == Some(':') {
+                        self.position += 1;
+                        step = self.parse_optional_int()?.unwrap_or(1);
+                    }
+                    if step <= 0 {
+                        return Err(self.error());
+                    }
+                    PathSegment::Slice(first, end, step)
+                } else {
+                    let mut indexes = vec![PathSegment::Index(first.ok_or_else(|| self.error())?)];
+                    while self.peek() == Some(',') {
+                        self.position += 1;
+                        self.skip_whitespace();
+                        indexes.push(PathSegment::Index(self.parse_optional_int()?.ok_or_else(|| self.error())?));
+                        self.skip_whitespace();
+                    }
+                    to_segment(indexes)
+                }
+            }
+            _ => return Err(self.error()),
+        };
+
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            return Err(self.error());
+        }
+        self.position += 1;
+
+        Ok(segment)
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, String> {
+        let quote = self.peek().ok_or_else(|| self.error())?;
+        self.position += 1;
+
+        let mut result = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.position += 1;
+                    result.push(self.peek().ok_or_else(|| self.error())?);
+                }
+                Some(c) if c == quote => break,
+                Some(c) => result.push(c),
+                None => return Err(self.error()),
+            }
+            self.position += 1;
+        }
+        // skip closing quote
+        self.position += 1;
+
+        Ok(result)
+    }
+
+    fn parse_optional_int(&mut self) -> Result<Option<i64>, String> {
+        self.skip_whitespace();
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.position += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Ok(None);
+        }
+
+        let number: String = self.input[start..self.position].iter().collect();
+        number.parse::<i64>().map(Some).map_err(|_| self.error())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek() == Some(' ') {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).cloned()
+    }
+
+    fn error(&self) -> String {
+        format!("JSON Path error: path error at position {}", self.position)
+    }
+}
+
+fn to_segment(mut segments: Vec<PathSegment>) -> PathSegment {
+    if segments.len() == 1 {
+        return segments.remove(0);
+    }
+    PathSegment::Union(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::json_parser::parse_json;
+
+    use super::*;
+
+    fn find_values(path: &str, document: &JsonValue) -> Vec<String> {
+        let path = JsonPath::parse(path).expect("Can not parse path");
+        path.find(document)
+            .iter()
+            .map(|location| get_at(document, location).unwrap().to_json_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_path() {
+        // when
+        let root = JsonPath::parse("$").unwrap();
+        let legacy_root = JsonPath::parse(".").unwrap();
+        let path = JsonPath::parse("$.a['b c'][0][*]..d.*[1:3]").unwrap();
+        let legacy = JsonPath::parse("a.b[-1]").unwrap();
+
+        // then
+        assert!(root.is_root() && !root.is_legacy);
+        assert!(legacy_root.is_root() && legacy_root.is_legacy);
+        assert_eq!(path.segments, vec![
+            PathSegment::Key(String::from("a")),
+            PathSegment::Key(String::from("b c")),
+            PathSegment::Index(0),
+            PathSegment::Wildcard,
+            PathSegment::RecursiveKey(String::from("d")),
+            PathSegment::Wildcard,
+            PathSegment::Slice(Some(1), Some(3), 1),
+        ]);
+        assert!(legacy.is_legacy);
+        assert_eq!(legacy.segments, vec![
+            PathSegment::Key(String::from("a")),
+            PathSegment::Key(String::from("b")),
+            PathSegment::Index(-1),
+        ]);
+        assert!(JsonPath::parse("$.a[").is_err());
+        assert!(JsonPath::parse("$a").is_err());
+        assert!(JsonPath::parse("$[?(@.a>1)]").is_err());
+    }
+
+    #[test]
+    fn test_find() {
+        // given
+        let document = parse_json(r#"{"a": {"b": [1, 2, 3], "c": {"b": "x"}}, "b": true}"#).unwrap();
+
+        // then
+        assert_eq!(find_values("$", &document), vec![document.to_json_string()]);
+        assert_eq!(find_values("$.a.b[1]", &document), vec!["2"]);
+        assert_eq!(find_values("$.a.b[-1]", &document), vec!["3"]);
+        assert_eq!(find_values("$.a.b[0:2]", &document), vec!["1", "2"]);
+        assert_eq!(find_values("$.a.b[0,2]", &document), vec!["1", "3"]);
+        assert_eq!(find_values("$..b", &document), vec!["true", "[1,2,3]", "\"x\""]);
+        assert_eq!(find_values("$.a.*", &document), vec!["[1,2,3]", "{\"b\":\"x\"}"]);
+        assert_eq!(find_values("$['a']['c']", &document), vec!["{\"b\":\"x\"}"]);
+        assert_eq!(find_values("$.missing", &document).len(), 0);
+    }
+
+    #[test]
+    fn test_remove_at() {
+        // given
+        let mut document = parse_json(r#"{"a": [1, 2, 3], "b": 1}"#).unwrap();
+
+        // when
+        let removed_index = remove_at(&mut document, &vec![PathElement::Key(String::from("a")), PathElement::Index(1)]);
+        let removed_key = remove_at(&mut document, &vec![PathElement::Key(String::from("b"))]);
+        let removed_root = remove_at(&mut document, &vec![]);
+
+        // then
+        assert_eq!(removed_index, Some(JsonValue::Integer(2)));
+        assert_eq!(removed_key, Some(JsonValue::Integer(1)));
+        assert_eq!(removed_root, None);
+        assert_eq!(document.to_json_string(), r#"{"a":[1,3]}"#);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::store::estimate_from_samples;
+
+/// In-memory representation of a JSON document
+///
+/// Documents are stored as a parsed tree instead of a string, so that commands like JSON.NUMINCRBY or JSON.ARRAPPEND
+/// can modify a single node without having to parse and serialize the whole document again.
+/// Objects keep the insertion order of their keys, like RedisJSON does.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// Formatting options for serializing a document (see the INDENT, NEWLINE and SPACE options of JSON.GET)
+#[derive(Debug, Clone, Default)]
+pub struct JsonFormat {
+    pub indent: String,
+    pub newline: String,
+    pub space: String,
+}
+
+impl JsonValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "boolean",
+            JsonValue::Integer(_) => "integer",
+            JsonValue::Float(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+
+    pub fn get_key(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn get_key_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter_mut().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of the key, an existing key keeps its position within the object
+    pub fn set_key(&mut self, key: &str, value: JsonValue) -> bool {
+        match self {
+            JsonValue::Object(entries) => {
+                match entries.iter_mut().find(|(entry_key, _)| entry_key == key) {
+                    Some((_, existing)) => *existing = value,
+                    None => entries.push((key.to_string(), value)),
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Estimated number of bytes used by the value including all nested values
+    pub fn get_memory_usage(&self) -> u64 {
+        let nested_size = match self {
+            JsonValue::String(value) => value.capacity() as u64,
+            JsonValue::Array(values) => values.iter().map(|value| value.get_memory_usage()).sum(),
+            JsonValue::Object(entries) => entries.iter().map(|(key, value)| key.capacity() as u64 + std::mem::size_of::<String>() as u64 + value.get_memory_usage()).sum(),
+            _ => 0,
+        };
+        std::mem::size_of::<JsonValue>() as u64 + nested_size
+    }
+
+    /// Estimated number of bytes used by the value, the size of the elements of an array or object
+    /// is extrapolated from the first samples elements, 0 adds up all elements
+    pub fn get_sampled_memory_usage(&self, samples: usize) -> u64 {
+        let nested_size = match self {
+            JsonValue::Array(values) => estimate_from_samples(values.iter().map(|value| value.get_memory_usage()), values.len(), samples),
+            JsonValue::Object(entries) => {
+                let entry_sizes = entries.iter().map(|(key, value)| key.capacity() as u64 + std::mem::size_of::<String>() as u64 + value.get_memory_usage());
+                estimate_from_samples(entry_sizes, entries.len(), samples)
+            }
+            _ => return self.get_memory_usage(),
+        };
+        std::mem::size_of::<JsonValue>() as u64 + nested_size
+    }
+
+    /// Serializes the value to a compact JSON string
+    pub fn to_json_string(&self) -> String {
+        self.to_formatted_json_string(&JsonFormat::default())
+    }
+
+    pub fn to_formatted_json_string(&self, format: &JsonFormat) -> String {
+        let mut result = String::new();
+        self.write_json(&mut result, format, 0);
+        result
+    }
+
+    fn write_json(&self, out: &mut String, format: &JsonFormat, depth: usize) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            JsonValue::Integer(value) => out.push_str(&value.to_string()),
+            JsonValue::Float(value) => out.push_str(&format_float(*value)),
+            JsonValue::String(value) => write_json_string(out, value),
+            JsonValue::Array(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_line_break(out, format, depth + 1);
+                    value.write_json(out, format, depth + 1);
+                }
+                if !values.is_empty() {
+                    write_line_break(out, format, depth);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_line_break(out, format, depth + 1);
+                    write_json_string(out, key);
+                    out.push(':');
+                    out.push_str(&format.space);
+                    value.write_json(out, format, depth + 1);
+                }
+                if !entries.is_empty() {
+                    write_line_break(out, format, depth);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_line_break(out: &mut String, format: &JsonFormat, depth: usize) {
+    out.push_str(&format.newline);
+    for _ in 0..depth {
+        out.push_str(&format.indent);
+    }
+}
+
+/// Floats always contain a decimal point or an exponent, so that they are parsed as floats again
+pub fn format_float(value: f64) -> String {
+    format!("{:?}", value)
+}
+
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
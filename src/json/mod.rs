@@ -0,0 +1,4 @@
+pub mod json_value;
+pub mod json_parser;
+pub mod json_path;
+pub mod json_helper;
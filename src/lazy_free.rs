@@ -0,0 +1,88 @@
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+use lazy_static::lazy_static;
+
+use crate::json::json_value::JsonValue;
+use crate::store::{ObjectValue, StoreObject};
+
+/// Frees removed objects on a background thread, used by UNLINK
+/// see: https://redis.io/commands/unlink/
+///
+/// Dropping a large value (e.g. a big sorted set or JSON document) frees every element and can block the event loop.
+/// Objects above the threshold are sent to a background thread instead, small objects are dropped directly
+/// because sending them costs more than freeing them.
+///
+/// Like lazyfreeGetFreeEffort in Redis, the cost of freeing an object is estimated in constant time by the number of
+/// allocations it frees (e.g. the members of a sorted set), not by its size in bytes. A long string is a single allocation.
+
+const LAZY_FREE_THRESHOLD: usize = 64;
+
+lazy_static! {
+    static ref LAZY_FREE_SENDER: Mutex<Sender<StoreObject>> = {
+        let (sender, receiver) = channel::<StoreObject>();
+        thread::spawn(move || {
+            for store_object in receiver {
+                drop(store_object);
+            }
+        });
+        Mutex::new(sender)
+    };
+}
+
+pub fn free_object(store_object: StoreObject) {
+    if get_free_effort(&store_object) <= LAZY_FREE_THRESHOLD {
+        return;
+    }
+
+    if let Ok(sender) = LAZY_FREE_SENDER.lock() {
+        // if the background thread is gone, the object is dropped here when the send fails
+        let _ = sender.send(store_object);
+    }
+}
+
+/// Estimated number of allocations which are freed with the object
+fn get_free_effort(store_object: &StoreObject) -> usize {
+    match store_object.value.as_ref() {
+        ObjectValue::SortedSet(sorted_set) => sorted_set.len(),
+        ObjectValue::Hash(hash) => hash.len(),
+        ObjectValue::Json(JsonValue::Array(values)) => values.len(),
+        ObjectValue::Json(JsonValue::Object(entries)) => entries.len(),
+        ObjectValue::BloomFilter(filter) => filter.get_number_of_filters() as usize,
+        ObjectValue::CuckooFilter(filter) => filter.get_number_of_filters() as usize,
+        ObjectValue::TopK(top_k) => top_k.get_k() as usize,
+        ObjectValue::TimeSeries(series) => series.get_number_of_samples(),
+        ObjectValue::String(_) | ObjectValue::Json(_) | ObjectValue::CountMinSketch(_) => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_ENCODING_SKIPLIST, OBJ_TYPE_STRING, OBJ_TYPE_ZSET};
+    use crate::sorted_set::SortedSet;
+    use crate::store::Store;
+    use crate::string_value::StringValue;
+
+    #[test]
+    fn test_free_effort() {
+        // given
+        let mut store = Store::new(1);
+        let mut sorted_set = SortedSet::new();
+        for i in 0..100 {
+            sorted_set.insert(&format!("member:{}", i), i as f64);
+        }
+        store.put("zset", ObjectValue::SortedSet(Box::new(sorted_set)), -1, OBJ_TYPE_ZSET | OBJ_ENCODING_SKIPLIST);
+        store.put("string", ObjectValue::String(StringValue::new("a".repeat(1024 * 1024))), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+
+        // when
+        let sorted_set_effort = get_free_effort(&store.get("zset").unwrap());
+        let string_effort = get_free_effort(&store.get("string").unwrap());
+
+        // then
+        assert_eq!(sorted_set_effort, 100);
+        // a string is freed at once, whatever its size
+        assert_eq!(string_effort, 1);
+    }
+}
@@ -9,6 +9,23 @@ mod object_type_encoding;
 mod stats;
 mod signal;
 mod client;
+mod sorted_set;
+mod geo;
+mod json;
+mod probabilistic;
+mod timeseries;
+mod hash;
+mod search;
+mod config;
+mod glob;
+mod lazy_free;
+mod string_value;
+mod string;
+mod shared_integers;
+mod allocator;
+mod dict;
+mod expiry_index;
+mod expire;
 
 fn main() {
     println!("Starting kataradb");
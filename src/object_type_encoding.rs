@@ -1,24 +1,52 @@
 // Object types
 pub const OBJ_TYPE_STRING: u8 = 0b0000_0000;
+pub const OBJ_TYPE_ZSET: u8 = 0b0011_0000;
+pub const OBJ_TYPE_HASH: u8 = 0b0100_0000;
+pub const OBJ_TYPE_JSON: u8 = 0b0101_0000;
+pub const OBJ_TYPE_BLOOM: u8 = 0b0110_0000;
+pub const OBJ_TYPE_CUCKOO: u8 = 0b0111_0000;
+pub const OBJ_TYPE_CMS: u8 = 0b1000_0000;
+pub const OBJ_TYPE_TOPK: u8 = 0b1001_0000;
+pub const OBJ_TYPE_TIMESERIES: u8 = 0b1010_0000;
 
 // Object Encodings
 pub const OBJ_ENCODING_RAW: u8 = 0b0000_0000;
 pub const OBJ_ENCODING_INT: u8 = 0b0000_0001;
+pub const OBJ_ENCODING_HT: u8 = 0b0000_0010;
+pub const OBJ_ENCODING_SKIPLIST: u8 = 0b0000_0111;
 pub const OBJ_ENCODING_EMBSTR: u8 = 0b0000_1000;
 
-pub fn get_string_encoding(value: &String) -> u8 {
-    if value.parse::<i64>().is_ok() {
-        return OBJ_ENCODING_INT;
-    }
+pub fn get_type(type_encoding: u8) -> u8 {
+    return type_encoding & 0b1111_0000;
+}
 
-    // check whether length of string is less than 44 bytes
-    if value.len() < 44 {
-        return OBJ_ENCODING_EMBSTR;
+/// Name of the type as returned by TYPE, module types use the names of the corresponding redis modules
+pub fn get_type_name(type_encoding: u8) -> &'static str {
+    match get_type(type_encoding) {
+        OBJ_TYPE_STRING => "string",
+        OBJ_TYPE_ZSET => "zset",
+        OBJ_TYPE_HASH => "hash",
+        OBJ_TYPE_JSON => "ReJSON-RL",
+        OBJ_TYPE_BLOOM => "MBbloom--",
+        OBJ_TYPE_CUCKOO => "MBbloomCF",
+        OBJ_TYPE_CMS => "CMSk-TYPE",
+        OBJ_TYPE_TOPK => "TopK-TYPE",
+        OBJ_TYPE_TIMESERIES => "TSDB-TYPE",
+        _ => "none",
     }
+}
 
-    return OBJ_ENCODING_RAW;
+pub fn get_encoding(type_encoding: u8) -> u8 {
+    type_encoding & 0b0000_1111
 }
 
-pub fn get_type(type_encoding: u8) -> u8 {
-    return type_encoding & 0b1111_0000;
+/// Name of the encoding as returned by OBJECT ENCODING
+pub fn get_encoding_name(type_encoding: u8) -> &'static str {
+    match get_encoding(type_encoding) {
+        OBJ_ENCODING_INT => "int",
+        OBJ_ENCODING_HT => "hashtable",
+        OBJ_ENCODING_SKIPLIST => "skiplist",
+        OBJ_ENCODING_EMBSTR => "embstr",
+        _ => "raw",
+    }
 }
@@ -0,0 +1,275 @@
+use crate::probabilistic::murmur_hash::{murmur_hash64a, DEFAULT_SEED};
+use crate::probabilistic::serialization::{DumpReader, DumpWriter};
+
+/// Scalable bloom filter
+/// see: https://redis.io/docs/data-types/probabilistic/bloom-filter/
+///
+/// A bloom filter is a bit array of m bits with k hash functions. Adding an item sets the k bits the item hashes to,
+/// checking an item tests whether all k bits are set. False positives are possible, false negatives are not.
+///
+/// A single filter only guarantees its error rate up to its capacity. When a layer is full, we add a new layer
+/// with `expansion` times the capacity and a tighter error rate, so the overall error rate stays below the requested one.
+/// see: https://gsd.di.uminho.pt/members/cbm/ps/dbloom.pdf
+
+pub const DEFAULT_ERROR_RATE: f64 = 0.01;
+pub const DEFAULT_CAPACITY: u64 = 100;
+pub const DEFAULT_EXPANSION: u64 = 2;
+
+// every new layer gets half the error rate of the previous one
+const ERROR_TIGHTENING_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+struct BloomLayer {
+    bits: Vec<u8>,
+    num_bits: u64,
+    num_hashes: u64,
+    capacity: u64,
+    error_rate: f64,
+    items: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilter {
+    layers: Vec<BloomLayer>,
+    expansion: u64,
+    non_scaling: bool,
+}
+
+impl BloomLayer {
+    fn new(capacity: u64, error_rate: f64) -> Self {
+        // optimal number of bits and hash functions for the given capacity and error rate
+        let bits_per_entry = -error_rate.ln() / std::f64::consts::LN_2.powi(2);
+        let num_bits = ((capacity as f64 * bits_per_entry).ceil() as u64).max(64);
+        let num_hashes = ((std::f64::consts::LN_2 * bits_per_entry).ceil() as u64).max(1);
+
+        BloomLayer {
+            bits: vec![0; num_bits.div_ceil(8) as usize],
+            num_bits,
+            num_hashes,
+            capacity,
+            error_rate,
+            items: 0,
+        }
+    }
+
+    /// Sets all bits of the item. Returns true if at least one bit was not set before
+    fn add(&mut self, hash: (u64, u64)) -> bool {
+        let mut changed = false;
+        for i in 0..self.num_hashes {
+            let bit = self.get_bit_index(hash, i);
+            let mask = 1 << (bit % 8);
+            let byte = &mut self.bits[(bit / 8) as usize];
+            if *byte & mask == 0 {
+                *byte |= mask;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.items += 1;
+        }
+        changed
+    }
+
+    fn contains(&self, hash: (u64, u64)) -> bool {
+        (0..self.num_hashes).all(|i| {
+            let bit = self.get_bit_index(hash, i);
+            self.bits[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+        })
+    }
+
+    // double hashing: the i-th hash function is h1 + i * h2
+    fn get_bit_index(&self, hash: (u64, u64), i: u64) -> u64 {
+        hash.0.wrapping_add(i.wrapping_mul(hash.1)) % self.num_bits
+    }
+}
+
+impl BloomFilter {
+    pub fn new(capacity: u64, error_rate: f64, expansion: u64, non_scaling: bool) -> Self {
+        BloomFilter {
+            layers: vec![BloomLayer::new(capacity, error_rate)],
+            expansion,
+            non_scaling,
+        }
+    }
+
+    /// Adds the item to the filter. Returns Ok(false) if the item (probably) already exists
+    pub fn add(&mut self, item: &str) -> Result<bool, String> {
+        let hash = get_hash(item);
+
+        if self.layers.iter().any(|layer| layer.contains(hash)) {
+            return Ok(false);
+        }
+
+        let last_layer = self.layers.last().expect("Bloom filter has no layers");
+        if last_layer.items >= last_layer.capacity {
+            if self.non_scaling {
+                return Err(String::from("ERR non scaling filter is full"));
+            }
+
+            let capacity = last_layer.capacity.saturating_mul(self.expansion);
+            let error_rate = last_layer.error_rate * ERROR_TIGHTENING_RATIO;
+            self.layers.push(BloomLayer::new(capacity, error_rate));
+        }
+
+        Ok(self.layers.last_mut().unwrap().add(hash))
+    }
+
+    pub fn contains(&self, item: &str) -> bool {
+        let hash = get_hash(item);
+        self.layers.iter().any(|layer| layer.contains(hash))
+    }
+
+    pub fn get_capacity(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.capacity).sum()
+    }
+
+    /// Number of bytes used by the filter
+    pub fn get_size(&self) -> u64 {
+        let layers_size: usize = self.layers.iter().map(|layer| layer.bits.len() + std::mem::size_of::<BloomLayer>()).sum();
+        (layers_size + std::mem::size_of::<BloomFilter>()) as u64
+    }
+
+    pub fn get_number_of_filters(&self) -> u64 {
+        self.layers.len() as u64
+    }
+
+    pub fn get_number_of_items(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.items).sum()
+    }
+
+    pub fn get_expansion(&self) -> Option<u64> {
+        if self.non_scaling {
+            return None;
+        }
+        Some(self.expansion)
+    }
+
+    pub fn dump(&self) -> String {
+        let mut writer = DumpWriter::new();
+        writer.write_u64(self.expansion);
+        writer.write_u64(self.non_scaling as u64);
+        writer.write_u64(self.layers.len() as u64);
+
+        for layer in self.layers.iter() {
+            writer.write_u64(layer.capacity);
+            writer.write_f64(layer.error_rate);
+            writer.write_u64(layer.num_hashes);
+            writer.write_u64(layer.num_bits);
+            writer.write_u64(layer.items);
+            writer.write_bytes(&layer.bits);
+        }
+
+        writer.to_hex()
+    }
+
+    pub fn restore(dump: &str) -> Result<BloomFilter, String> {
+        let mut reader = DumpReader::from_hex(dump)?;
+        let expansion = reader.read_u64()?;
+        let non_scaling = reader.read_u64()? != 0;
+        let num_layers = reader.read_u64()?;
+
+        let mut layers = Vec::new();
+        for _ in 0..num_layers {
+            let capacity = reader.read_u64()?;
+            let error_rate = reader.read_f64()?;
+            let num_hashes = reader.read_u64()?;
+            let num_bits = reader.read_u64()?;
+            let items = reader.read_u64()?;
+            let bits = reader.read_bytes()?;
+
+            if num_bits == 0 || bits.len() as u64 != num_bits.div_ceil(8) {
+                return Err(String::from("invalid bloom filter dump"));
+            }
+
+            layers.push(BloomLayer { bits, num_bits, num_hashes, capacity, error_rate, items });
+        }
+
+        if layers.is_empty() {
+            return Err(String::from("invalid bloom filter dump"));
+        }
+
+        Ok(BloomFilter { layers, expansion, non_scaling })
+    }
+}
+
+fn get_hash(item: &str) -> (u64, u64) {
+    let h1 = murmur_hash64a(item.as_bytes(), DEFAULT_SEED);
+    let h2 = murmur_hash64a(item.as_bytes(), h1);
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_contains() {
+        // given
+        let mut filter = BloomFilter::new(1000, 0.01, DEFAULT_EXPANSION, false);
+
+        // when
+        let added = filter.add("item").unwrap();
+        let added_again = filter.add("item").unwrap();
+
+        // then
+        assert!(added);
+        assert!(!added_again);
+        assert!(filter.contains("item"));
+        assert!(!filter.contains("other"));
+        assert_eq!(filter.get_number_of_items(), 1);
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        // given
+        let mut filter = BloomFilter::new(1000, 0.01, DEFAULT_EXPANSION, false);
+        for i in 0..1000 {
+            filter.add(&format!("item{}", i)).unwrap();
+        }
+
+        // when
+        let false_positives = (0..10000).filter(|i| filter.contains(&format!("other{}", i))).count();
+
+        // then
+        assert!((0..1000).all(|i| filter.contains(&format!("item{}", i))));
+        assert!(false_positives < 200, "too many false positives: {}", false_positives);
+    }
+
+    #[test]
+    fn test_scaling() {
+        // given
+        let mut scaling = BloomFilter::new(10, 0.01, 2, false);
+        let mut non_scaling = BloomFilter::new(10, 0.01, 2, true);
+
+        // when
+        for i in 0..100 {
+            scaling.add(&format!("item{}", i)).unwrap();
+        }
+        let non_scaling_results = (0..100).map(|i| non_scaling.add(&format!("item{}", i))).collect::<Vec<_>>();
+
+        // then
+        assert!(scaling.get_number_of_filters() > 1);
+        assert_eq!(scaling.get_capacity(), (0..scaling.get_number_of_filters()).map(|i| 10 * 2u64.pow(i as u32)).sum());
+        assert!((0..100).all(|i| scaling.contains(&format!("item{}", i))));
+        assert_eq!(non_scaling.get_number_of_filters(), 1);
+        assert!(non_scaling_results.iter().any(|result| result.is_err()));
+    }
+
+    #[test]
+    fn test_dump_and_restore() {
+        // given
+        let mut filter = BloomFilter::new(10, 0.01, 2, false);
+        for i in 0..50 {
+            filter.add(&format!("item{}", i)).unwrap();
+        }
+
+        // when
+        let restored = BloomFilter::restore(&filter.dump()).expect("Can not restore filter");
+
+        // then
+        assert_eq!(restored, filter);
+        assert!(BloomFilter::restore("abc").is_err());
+        assert!(BloomFilter::restore("").is_err());
+    }
+}
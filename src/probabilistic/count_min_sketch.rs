@@ -0,0 +1,238 @@
+use crate::probabilistic::murmur_hash::murmur_hash64a;
+use crate::probabilistic::serialization::{DumpReader, DumpWriter};
+
+/// Count-Min Sketch
+/// see: https://redis.io/docs/data-types/probabilistic/count-min-sketch/
+/// see: http://dimacs.rutgers.edu/~graham/pubs/papers/cm-full.pdf
+///
+/// A matrix of `depth` rows with `width` counters each. Every row uses its own hash function to map an item to one counter.
+/// Incrementing an item increments one counter per row, the estimated count is the minimum of these counters.
+/// Collisions can only increase counters, so the estimate is never lower than the real count.
+
+// counters are replied as RESP integers, which are signed
+const MAX_COUNT: u64 = i64::MAX as u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountMinSketch {
+    width: u64,
+    depth: u64,
+    // all rows stored in one array, row i starts at i * width
+    counters: Vec<u64>,
+    // sum of all increments
+    count: u64,
+}
+
+impl CountMinSketch {
+    pub fn new(width: u64, depth: u64) -> Self {
+        CountMinSketch {
+            width,
+            depth,
+            counters: vec![0; (width * depth) as usize],
+            count: 0,
+        }
+    }
+
+    /// Creates a sketch which overestimates the count by at most `error` * total count with the given probability of failure
+    /// (uses the same dimensions as redis)
+    pub fn new_by_probability(error: f64, probability: f64) -> Self {
+        let width = (2.0 / error).ceil() as u64;
+        let depth = (probability.ln() / 0.5f64.ln()).ceil() as u64;
+        CountMinSketch::new(width.max(1), depth.max(1))
+    }
+
+    /// Increments the item and returns its new estimated count
+    pub fn increment_by(&mut self, item: &str, increment: u64) -> Result<u64, String> {
+        let indexes = self.get_indexes(item);
+
+        if indexes.iter().any(|index| checked_add(self.counters[*index], increment).is_none()) || checked_add(self.count, increment).is_none() {
+            return Err(String::from("ERR CMS: INCRBY overflow"));
+        }
+
+        for index in indexes.iter() {
+            self.counters[*index] += increment;
+        }
+        self.count += increment;
+
+        Ok(indexes.iter().map(|index| self.counters[*index]).min().unwrap_or(0))
+    }
+
+    pub fn query(&self, item: &str) -> u64 {
+        self.get_indexes(item).iter().map(|index| self.counters[*index]).min().unwrap_or(0)
+    }
+
+    /// Replaces the counters with the weighted sum of the sources. All sketches need to have the same dimensions
+    pub fn merge(&mut self, sources: &[(&CountMinSketch, u64)]) -> Result<(), String> {
+        if sources.iter().any(|(source, _)| source.width != self.width || source.depth != self.depth) {
+            return Err(String::from("ERR CMS: width/depth is not equal"));
+        }
+
+        let mut counters = vec![0u64; self.counters.len()];
+        let mut count = 0u64;
+        for (source, weight) in sources.iter() {
+            for (counter, source_counter) in counters.iter_mut().zip(source.counters.iter()) {
+                *counter = source_counter.checked_mul(*weight).and_then(|value| checked_add(*counter, value)).ok_or_else(|| String::from("ERR CMS: MERGE overflow"))?;
+            }
+            count = source.count.checked_mul(*weight).and_then(|value| checked_add(count, value)).ok_or_else(|| String::from("ERR CMS: MERGE overflow"))?;
+        }
+
+        self.counters = counters;
+        self.count = count;
+        Ok(())
+    }
+
+    pub fn get_width(&self) -> u64 {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> u64 {
+        self.depth
+    }
+
+    pub fn get_count(&self) -> u64 {
+        self.count
+    }
+
+    /// Number of bytes used by the sketch
+    pub fn get_size(&self) -> u64 {
+        (self.counters.len() * std::mem::size_of::<u64>() + std::mem::size_of::<CountMinSketch>()) as u64
+    }
+
+    pub fn dump(&self) -> String {
+        let mut writer = DumpWriter::new();
+        writer.write_u64(self.width);
+        writer.write_u64(self.depth);
+        writer.write_u64(self.count);
+        for counter in self.counters.iter() {
+            writer.write_u64(*counter);
+        }
+        writer.to_hex()
+    }
+
+    pub fn restore(dump: &str) -> Result<CountMinSketch, String> {
+        let mut reader = DumpReader::from_hex(dump)?;
+        let width = reader.read_u64()?;
+        let depth = reader.read_u64()?;
+        let count = reader.read_u64()?;
+
+        let size = width.checked_mul(depth).filter(|size| *size > 0).ok_or_else(|| String::from("invalid count-min sketch dump"))?;
+        let mut counters = Vec::new();
+        for _ in 0..size {
+            counters.push(reader.read_u64()?);
+        }
+
+        if count > MAX_COUNT || counters.iter().any(|counter| *counter > MAX_COUNT) {
+            return Err(String::from("invalid count-min sketch dump"));
+        }
+        Ok(CountMinSketch { width, depth, counters, count })
+    }
+
+    // every row uses the row number as seed of the hash function
+    fn get_indexes(&self, item: &str) -> Vec<usize> {
+        (0..self.depth)
+            .map(|row| (row * self.width + murmur_hash64a(item.as_bytes(), row) % self.width) as usize)
+            .collect()
+    }
+}
+
+/// Adds the increment, None if the sum is larger than MAX_COUNT
+fn checked_add(value: u64, increment: u64) -> Option<u64> {
+    value.checked_add(increment).filter(|sum| *sum <= MAX_COUNT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increment_and_query() {
+        // given
+        let mut sketch = CountMinSketch::new(2000, 5);
+
+        // when
+        let count = sketch.increment_by("a", 3).unwrap();
+        sketch.increment_by("a", 2).unwrap();
+        sketch.increment_by("b", 1).unwrap();
+
+        // then
+        assert_eq!(count, 3);
+        assert_eq!(sketch.query("a"), 5);
+        assert_eq!(sketch.query("b"), 1);
+        assert_eq!(sketch.query("c"), 0);
+        assert_eq!(sketch.get_count(), 6);
+        assert!(sketch.increment_by("a", u64::MAX).is_err());
+        assert_eq!(sketch.query("a"), 5);
+    }
+
+    #[test]
+    fn test_counters_fit_into_integer_replies() {
+        // given
+        let mut sketch = CountMinSketch::new(10, 2);
+        sketch.increment_by("a", MAX_COUNT - 5).unwrap();
+
+        // when
+        let overflow = sketch.increment_by("a", 6);
+        // the doubled counters still fit into an u64
+        let merge_overflow = sketch.clone().merge(&[(&sketch, 2)]);
+
+        // then
+        assert_eq!(overflow, Err(String::from("ERR CMS: INCRBY overflow")));
+        assert!(merge_overflow.is_err());
+        assert_eq!(sketch.increment_by("a", 5), Ok(i64::MAX as u64));
+        assert_eq!(sketch.get_count(), MAX_COUNT);
+    }
+
+    #[test]
+    fn test_never_underestimates() {
+        // given
+        let mut sketch = CountMinSketch::new_by_probability(0.01, 0.01);
+
+        // when
+        for i in 0..1000u64 {
+            sketch.increment_by(&format!("item{}", i), i % 10 + 1).unwrap();
+        }
+
+        // then
+        assert_eq!(sketch.get_width(), 200);
+        assert_eq!(sketch.get_depth(), 7);
+        for i in 0..1000u64 {
+            let expected = i % 10 + 1;
+            let estimate = sketch.query(&format!("item{}", i));
+            assert!(estimate >= expected);
+            assert!(estimate <= expected + (0.01 * sketch.get_count() as f64) as u64 * 2);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        // given
+        let mut first = CountMinSketch::new(100, 4);
+        let mut second = CountMinSketch::new(100, 4);
+        let mut destination = CountMinSketch::new(100, 4);
+        first.increment_by("a", 2).unwrap();
+        second.increment_by("a", 3).unwrap();
+        second.increment_by("b", 1).unwrap();
+
+        // when
+        destination.merge(&[(&first, 1), (&second, 2)]).unwrap();
+
+        // then
+        assert_eq!(destination.query("a"), 8);
+        assert_eq!(destination.query("b"), 2);
+        assert_eq!(destination.get_count(), 10);
+        assert!(destination.merge(&[(&CountMinSketch::new(10, 4), 1)]).is_err());
+    }
+
+    #[test]
+    fn test_dump_and_restore() {
+        // given
+        let mut sketch = CountMinSketch::new(10, 3);
+        sketch.increment_by("a", 42).unwrap();
+
+        // when
+        let restored = CountMinSketch::restore(&sketch.dump()).expect("Can not restore sketch");
+
+        // then
+        assert_eq!(restored, sketch);
+        assert!(CountMinSketch::restore("").is_err());
+    }
+}
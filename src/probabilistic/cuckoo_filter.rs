@@ -0,0 +1,362 @@
+use rand::Rng;
+
+use crate::probabilistic::murmur_hash::{murmur_hash64a, DEFAULT_SEED};
+use crate::probabilistic::serialization::{DumpReader, DumpWriter};
+
+/// Cuckoo filter
+/// see: https://redis.io/docs/data-types/probabilistic/cuckoo-filter/
+/// see: https://www.cs.cmu.edu/~dga/papers/cuckoo-conext2014.pdf
+///
+/// Instead of bits, a cuckoo filter stores a small fingerprint of every item in one of two candidate buckets.
+/// The second bucket can be calculated from the first bucket and the fingerprint alone (partial-key cuckoo hashing),
+/// which allows to move fingerprints to their alternative bucket when both buckets are full.
+/// As the fingerprints are stored, items can be deleted again (in contrast to bloom filters).
+///
+/// When an item can not be inserted after `max_iterations` relocations, a new sub filter is added (if expansion is enabled).
+
+pub const DEFAULT_CAPACITY: u64 = 1024;
+pub const DEFAULT_BUCKET_SIZE: u64 = 2;
+pub const DEFAULT_MAX_ITERATIONS: u64 = 20;
+pub const DEFAULT_EXPANSION: u64 = 1;
+
+// an empty slot is represented by the fingerprint 0
+const EMPTY_SLOT: u8 = 0;
+
+#[derive(Debug, Clone, PartialEq)]
+struct CuckooSubFilter {
+    num_buckets: u64,
+    // all buckets stored in one array, bucket i starts at i * bucket_size
+    slots: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CuckooFilter {
+    filters: Vec<CuckooSubFilter>,
+    bucket_size: u64,
+    max_iterations: u64,
+    expansion: u64,
+    num_items: u64,
+    num_deletes: u64,
+}
+
+struct CuckooHash {
+    fingerprint: u8,
+    hash: u64,
+}
+
+impl CuckooSubFilter {
+    fn new(num_buckets: u64, bucket_size: u64) -> Self {
+        CuckooSubFilter {
+            num_buckets,
+            slots: vec![EMPTY_SLOT; (num_buckets * bucket_size) as usize],
+        }
+    }
+
+    // the number of buckets is a power of two, so the alternative bucket of the alternative bucket is the original bucket again
+    fn get_buckets(&self, hash: &CuckooHash) -> (u64, u64) {
+        let first = hash.hash & (self.num_buckets - 1);
+        (first, self.get_alternative_bucket(first, hash.fingerprint))
+    }
+
+    fn get_alternative_bucket(&self, bucket: u64, fingerprint: u8) -> u64 {
+        (bucket ^ (fingerprint as u64).wrapping_mul(0x5bd1e995)) & (self.num_buckets - 1)
+    }
+
+    fn bucket(&self, bucket: u64, bucket_size: u64) -> &[u8] {
+        let start = (bucket * bucket_size) as usize;
+        &self.slots[start..start + bucket_size as usize]
+    }
+
+    fn bucket_mut(&mut self, bucket: u64, bucket_size: u64) -> &mut [u8] {
+        let start = (bucket * bucket_size) as usize;
+        &mut self.slots[start..start + bucket_size as usize]
+    }
+
+    fn try_insert_into_bucket(&mut self, bucket: u64, bucket_size: u64, fingerprint: u8) -> bool {
+        match self.bucket_mut(bucket, bucket_size).iter_mut().find(|slot| **slot == EMPTY_SLOT) {
+            Some(slot) => {
+                *slot = fingerprint;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, hash: &CuckooHash, bucket_size: u64, max_iterations: u64) -> bool {
+        let (first, second) = self.get_buckets(hash);
+
+        if self.try_insert_into_bucket(first, bucket_size, hash.fingerprint) || self.try_insert_into_bucket(second, bucket_size, hash.fingerprint) {
+            return true;
+        }
+
+        // both buckets are full, so we kick out random fingerprints to their alternative bucket
+        // we remember all relocations, so we can undo them if we do not find a free slot
+        let mut rng = rand::thread_rng();
+        let mut relocations = Vec::new();
+        let mut fingerprint = hash.fingerprint;
+        let mut bucket = if rng.gen_bool(0.5) { first } else { second };
+
+        for _ in 0..max_iterations {
+            let slot = rng.gen_range(0..bucket_size) as usize;
+            let victim = std::mem::replace(&mut self.bucket_mut(bucket, bucket_size)[slot], fingerprint);
+            relocations.push((bucket, slot, victim));
+
+            fingerprint = victim;
+            bucket = self.get_alternative_bucket(bucket, fingerprint);
+
+            if self.try_insert_into_bucket(bucket, bucket_size, fingerprint) {
+                return true;
+            }
+        }
+
+        for (bucket, slot, victim) in relocations.into_iter().rev() {
+            self.bucket_mut(bucket, bucket_size)[slot] = victim;
+        }
+
+        false
+    }
+
+    fn count(&self, hash: &CuckooHash, bucket_size: u64) -> u64 {
+        let (first, second) = self.get_buckets(hash);
+        let mut count = self.bucket(first, bucket_size).iter().filter(|slot| **slot == hash.fingerprint).count();
+        if second != first {
+            count += self.bucket(second, bucket_size).iter().filter(|slot| **slot == hash.fingerprint).count();
+        }
+        count as u64
+    }
+
+    fn delete(&mut self, hash: &CuckooHash, bucket_size: u64) -> bool {
+        let (first, second) = self.get_buckets(hash);
+
+        for bucket in [first, second] {
+            if let Some(slot) = self.bucket_mut(bucket, bucket_size).iter_mut().find(|slot| **slot == hash.fingerprint) {
+                *slot = EMPTY_SLOT;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl CuckooFilter {
+    pub fn new(capacity: u64, bucket_size: u64, max_iterations: u64, expansion: u64) -> Self {
+        let num_buckets = capacity.div_ceil(bucket_size).max(1).next_power_of_two();
+
+        CuckooFilter {
+            filters: vec![CuckooSubFilter::new(num_buckets, bucket_size)],
+            bucket_size,
+            max_iterations,
+            expansion,
+            num_items: 0,
+            num_deletes: 0,
+        }
+    }
+
+    /// Adds the item, even if it already exists
+    pub fn add(&mut self, item: &str) -> Result<(), String> {
+        let hash = get_hash(item);
+
+        for filter in self.filters.iter_mut().rev() {
+            if filter.insert(&hash, self.bucket_size, self.max_iterations) {
+                self.num_items += 1;
+                return Ok(());
+            }
+        }
+
+        if self.expansion == 0 {
+            return Err(String::from("ERR Filter is full"));
+        }
+
+        let num_buckets = self.filters.last().unwrap().num_buckets.saturating_mul(self.expansion).next_power_of_two();
+        let mut filter = CuckooSubFilter::new(num_buckets, self.bucket_size);
+        if !filter.insert(&hash, self.bucket_size, self.max_iterations) {
+            return Err(String::from("ERR Could not insert item"));
+        }
+
+        self.filters.push(filter);
+        self.num_items += 1;
+        Ok(())
+    }
+
+    pub fn contains(&self, item: &str) -> bool {
+        let hash = get_hash(item);
+        self.filters.iter().any(|filter| filter.count(&hash, self.bucket_size) > 0)
+    }
+
+    /// Number of times the fingerprint of the item occurs in the filter. Can be higher than the real count for false positives
+    pub fn count(&self, item: &str) -> u64 {
+        let hash = get_hash(item);
+        self.filters.iter().map(|filter| filter.count(&hash, self.bucket_size)).sum()
+    }
+
+    /// Deletes one occurrence of the item, starting with the newest sub filter
+    pub fn delete(&mut self, item: &str) -> bool {
+        let hash = get_hash(item);
+
+        for filter in self.filters.iter_mut().rev() {
+            if filter.delete(&hash, self.bucket_size) {
+                self.num_items -= 1;
+                self.num_deletes += 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn get_number_of_filters(&self) -> u64 {
+        self.filters.len() as u64
+    }
+
+    pub fn get_number_of_items(&self) -> u64 {
+        self.num_items
+    }
+
+    pub fn get_number_of_deletes(&self) -> u64 {
+        self.num_deletes
+    }
+
+    pub fn get_number_of_buckets(&self) -> u64 {
+        self.filters.iter().map(|filter| filter.num_buckets).sum()
+    }
+
+    pub fn get_bucket_size(&self) -> u64 {
+        self.bucket_size
+    }
+
+    pub fn get_expansion(&self) -> u64 {
+        self.expansion
+    }
+
+    pub fn get_max_iterations(&self) -> u64 {
+        self.max_iterations
+    }
+
+    /// Number of bytes used by the filter
+    pub fn get_size(&self) -> u64 {
+        let filters_size: usize = self.filters.iter().map(|filter| filter.slots.len() + std::mem::size_of::<CuckooSubFilter>()).sum();
+        (filters_size + std::mem::size_of::<CuckooFilter>()) as u64
+    }
+
+    pub fn dump(&self) -> String {
+        let mut writer = DumpWriter::new();
+        writer.write_u64(self.bucket_size);
+        writer.write_u64(self.max_iterations);
+        writer.write_u64(self.expansion);
+        writer.write_u64(self.num_items);
+        writer.write_u64(self.num_deletes);
+        writer.write_u64(self.filters.len() as u64);
+
+        for filter in self.filters.iter() {
+            writer.write_u64(filter.num_buckets);
+            writer.write_bytes(&filter.slots);
+        }
+
+        writer.to_hex()
+    }
+
+    pub fn restore(dump: &str) -> Result<CuckooFilter, String> {
+        let mut reader = DumpReader::from_hex(dump)?;
+        let bucket_size = reader.read_u64()?;
+        let max_iterations = reader.read_u64()?;
+        let expansion = reader.read_u64()?;
+        let num_items = reader.read_u64()?;
+        let num_deletes = reader.read_u64()?;
+        let num_filters = reader.read_u64()?;
+
+        let mut filters = Vec::new();
+        for _ in 0..num_filters {
+            let num_buckets = reader.read_u64()?;
+            let slots = reader.read_bytes()?;
+
+            if !num_buckets.is_power_of_two() || slots.len() as u64 != num_buckets * bucket_size {
+                return Err(String::from("invalid cuckoo filter dump"));
+            }
+
+            filters.push(CuckooSubFilter { num_buckets, slots });
+        }
+
+        if filters.is_empty() || bucket_size == 0 {
+            return Err(String::from("invalid cuckoo filter dump"));
+        }
+
+        Ok(CuckooFilter { filters, bucket_size, max_iterations, expansion, num_items, num_deletes })
+    }
+}
+
+fn get_hash(item: &str) -> CuckooHash {
+    let hash = murmur_hash64a(item.as_bytes(), DEFAULT_SEED);
+
+    CuckooHash {
+        // fingerprints are in the range 1-255, as 0 marks an empty slot
+        fingerprint: (hash % 255 + 1) as u8,
+        hash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_count_delete() {
+        // given
+        let mut filter = CuckooFilter::new(1000, DEFAULT_BUCKET_SIZE, DEFAULT_MAX_ITERATIONS, DEFAULT_EXPANSION);
+
+        // when
+        filter.add("item").unwrap();
+        filter.add("item").unwrap();
+
+        // then
+        assert!(filter.contains("item"));
+        assert!(!filter.contains("other"));
+        assert_eq!(filter.count("item"), 2);
+        assert!(filter.delete("item"));
+        assert_eq!(filter.count("item"), 1);
+        assert!(filter.delete("item"));
+        assert!(!filter.delete("item"));
+        assert!(!filter.contains("item"));
+        assert_eq!(filter.get_number_of_items(), 0);
+    }
+
+    #[test]
+    fn test_expansion() {
+        // given
+        let mut filter = CuckooFilter::new(64, DEFAULT_BUCKET_SIZE, DEFAULT_MAX_ITERATIONS, DEFAULT_EXPANSION);
+        let mut non_expanding = CuckooFilter::new(64, DEFAULT_BUCKET_SIZE, DEFAULT_MAX_ITERATIONS, 0);
+
+        // when
+        for i in 0..500 {
+            filter.add(&format!("item{}", i)).unwrap();
+        }
+        let non_expanding_results = (0..500).map(|i| non_expanding.add(&format!("item{}", i))).collect::<Vec<_>>();
+
+        // then
+        assert!(filter.get_number_of_filters() > 1);
+        assert!((0..500).all(|i| filter.contains(&format!("item{}", i))));
+        assert!(non_expanding_results.iter().any(|result| result.is_err()));
+
+        // items that were added before the filter got full must still exist
+        let added = non_expanding_results.iter().enumerate().filter(|(_, result)| result.is_ok()).map(|(i, _)| i);
+        for i in added {
+            assert!(non_expanding.contains(&format!("item{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_dump_and_restore() {
+        // given
+        let mut filter = CuckooFilter::new(64, 4, DEFAULT_MAX_ITERATIONS, 2);
+        for i in 0..200 {
+            filter.add(&format!("item{}", i)).unwrap();
+        }
+
+        // when
+        let restored = CuckooFilter::restore(&filter.dump()).expect("Can not restore filter");
+
+        // then
+        assert_eq!(restored, filter);
+        assert!(CuckooFilter::restore("00").is_err());
+    }
+}
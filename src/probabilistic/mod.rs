@@ -0,0 +1,7 @@
+pub mod murmur_hash;
+pub mod serialization;
+pub mod bloom_filter;
+pub mod cuckoo_filter;
+pub mod probabilistic_helper;
+pub mod count_min_sketch;
+pub mod top_k;
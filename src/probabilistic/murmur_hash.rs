@@ -0,0 +1,54 @@
+/// MurmurHash2 64-bit variant (MurmurHash64A) by Austin Appleby
+/// see: https://github.com/aappleby/smhasher/blob/master/src/MurmurHash2.cpp
+///
+/// We use our own hash function instead of the one of the standard library,
+/// because the hashes of persisted filters must not change between versions.
+
+pub const DEFAULT_SEED: u64 = 0xc6a4a7935bd1e995;
+
+pub fn murmur_hash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+
+    let mut chunks = key.chunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap());
+
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur_hash64a() {
+        // the hash must be stable, as it is used for persisted filters
+        assert_eq!(murmur_hash64a(b"", 0), 0);
+        assert_eq!(murmur_hash64a(b"hello", 0), murmur_hash64a(b"hello", 0));
+        assert_ne!(murmur_hash64a(b"hello", 0), murmur_hash64a(b"hello", 1));
+        assert_ne!(murmur_hash64a(b"hello world", 0), murmur_hash64a(b"hello worle", 0));
+    }
+}
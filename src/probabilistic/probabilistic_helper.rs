@@ -0,0 +1,105 @@
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_BLOOM, OBJ_TYPE_CUCKOO};
+use crate::probabilistic::bloom_filter::BloomFilter;
+use crate::probabilistic::count_min_sketch::CountMinSketch;
+use crate::probabilistic::cuckoo_filter::CuckooFilter;
+use crate::probabilistic::top_k::TopK;
+use crate::probabilistic::{bloom_filter, cuckoo_filter};
+use crate::resp::DataType;
+use crate::resp::DataType::Error;
+use crate::store::{ObjectValue, Store};
+
+/// Shared lookup and argument parsing functions of the bloom filter (BF.*), cuckoo filter (CF.*),
+/// count-min sketch (CMS.*) and top-k (TOPK.*) commands
+
+pub const NOT_FOUND_ERROR: &str = "ERR not found";
+pub const ITEM_EXISTS_ERROR: &str = "ERR item exists";
+pub const CMS_KEY_NOT_FOUND_ERROR: &str = "ERR CMS: key does not exist";
+pub const CMS_KEY_EXISTS_ERROR: &str = "ERR CMS: key already exists";
+pub const TOPK_KEY_NOT_FOUND_ERROR: &str = "ERR TopK: key does not exist";
+pub const TOPK_KEY_EXISTS_ERROR: &str = "ERR TopK: key already exists";
+
+/// Looks up the bloom filter stored at key. Returns an error if the key holds a value of another type
+pub fn get_bloom_filter<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut BloomFilter>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::BloomFilter(_))) {
+                Some(ObjectValue::BloomFilter(filter)) => Ok(Some(filter)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Looks up the bloom filter stored at key and creates a filter with the default parameters if the key does not exist
+pub fn get_or_create_bloom_filter<'a>(store: &'a mut Store, key: &str) -> Result<&'a mut BloomFilter, DataType> {
+    if get_bloom_filter(store, key)?.is_none() {
+        let filter = BloomFilter::new(bloom_filter::DEFAULT_CAPACITY, bloom_filter::DEFAULT_ERROR_RATE, bloom_filter::DEFAULT_EXPANSION, false);
+        store.put(key, ObjectValue::BloomFilter(filter), -1, OBJ_TYPE_BLOOM | OBJ_ENCODING_RAW);
+    }
+
+    Ok(get_bloom_filter(store, key)?.expect("Bloom filter not found after creation"))
+}
+
+/// Looks up the cuckoo filter stored at key. Returns an error if the key holds a value of another type
+pub fn get_cuckoo_filter<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut CuckooFilter>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::CuckooFilter(_))) {
+                Some(ObjectValue::CuckooFilter(filter)) => Ok(Some(filter)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Looks up the cuckoo filter stored at key and creates a filter with the default parameters if the key does not exist
+pub fn get_or_create_cuckoo_filter<'a>(store: &'a mut Store, key: &str) -> Result<&'a mut CuckooFilter, DataType> {
+    if get_cuckoo_filter(store, key)?.is_none() {
+        let filter = CuckooFilter::new(cuckoo_filter::DEFAULT_CAPACITY, cuckoo_filter::DEFAULT_BUCKET_SIZE, cuckoo_filter::DEFAULT_MAX_ITERATIONS, cuckoo_filter::DEFAULT_EXPANSION);
+        store.put(key, ObjectValue::CuckooFilter(Box::new(filter)), -1, OBJ_TYPE_CUCKOO | OBJ_ENCODING_RAW);
+    }
+
+    Ok(get_cuckoo_filter(store, key)?.expect("Cuckoo filter not found after creation"))
+}
+
+/// Looks up the count-min sketch stored at key. Returns an error if the key holds a value of another type
+pub fn get_count_min_sketch<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut CountMinSketch>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::CountMinSketch(_))) {
+                Some(ObjectValue::CountMinSketch(sketch)) => Ok(Some(sketch)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Looks up the top-k stored at key. Returns an error if the key holds a value of another type
+pub fn get_top_k<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut TopK>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::TopK(_))) {
+                Some(ObjectValue::TopK(top_k)) => Ok(Some(top_k)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn parse_integer(value: &str, error: &str) -> Result<u64, DataType> {
+    value.parse::<u64>().map_err(|_| Error(String::from(error)))
+}
+
+/// Replies of the SCANDUMP commands: the whole filter is returned in the first chunk, the next call signals the end with iterator 0
+pub fn scan_dump_response(iterator: &str, dump: impl FnOnce() -> String) -> DataType {
+    match iterator.parse::<i64>() {
+        Ok(0) => DataType::Array(vec![DataType::Integer(1), DataType::BulkString(dump())]),
+        Ok(_) => DataType::Array(vec![DataType::Integer(0), DataType::BulkString(String::new())]),
+        Err(_) => Error(String::from("ERR invalid iterator")),
+    }
+}
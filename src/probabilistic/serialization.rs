@@ -0,0 +1,86 @@
+/// Serialization of probabilistic data structures to a hex encoded string
+///
+/// RESP bulk strings are stored as utf-8 strings, so we can not transmit the raw bytes of a filter.
+/// The hex encoding doubles the size, but keeps the dump readable for SCANDUMP and the AOF file.
+
+pub struct DumpWriter {
+    bytes: Vec<u8>,
+}
+
+impl DumpWriter {
+    pub fn new() -> Self {
+        DumpWriter { bytes: Vec::new() }
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn to_hex(&self) -> String {
+        let mut hex = String::with_capacity(self.bytes.len() * 2);
+        for byte in self.bytes.iter() {
+            hex.push_str(&format!("{:02x}", byte));
+        }
+        hex
+    }
+}
+
+pub struct DumpReader {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl DumpReader {
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        if hex.len() % 2 != 0 {
+            return Err(String::from("invalid dump length"));
+        }
+
+        let mut bytes = Vec::with_capacity(hex.len() / 2);
+        for i in (0..hex.len()).step_by(2) {
+            let byte = hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok());
+            match byte {
+                Some(byte) => bytes.push(byte),
+                None => return Err(String::from("invalid dump encoding")),
+            }
+        }
+
+        Ok(DumpReader { bytes, position: 0 })
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, String> {
+        let end = self.position + 8;
+        if end > self.bytes.len() {
+            return Err(String::from("unexpected end of dump"));
+        }
+
+        let value = u64::from_le_bytes(self.bytes[self.position..end].try_into().unwrap());
+        self.position = end;
+        Ok(value)
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+
+    pub fn read_bytes(&mut self) -> Result<Vec<u8>, String> {
+        let len = self.read_u64()? as usize;
+        let end = self.position.checked_add(len).ok_or_else(|| String::from("invalid dump length"))?;
+        if end > self.bytes.len() {
+            return Err(String::from("unexpected end of dump"));
+        }
+
+        let bytes = self.bytes[self.position..end].to_vec();
+        self.position = end;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,263 @@
+use rand::Rng;
+
+use crate::probabilistic::murmur_hash::{murmur_hash64a, DEFAULT_SEED};
+use crate::probabilistic::serialization::{DumpReader, DumpWriter};
+
+/// Top-K based on the HeavyKeeper algorithm
+/// see: https://redis.io/docs/data-types/probabilistic/top-k/
+/// see: https://www.usenix.org/system/files/conference/atc18/atc18-gong.pdf
+///
+/// Architecture
+/// A matrix of `depth` rows with `width` buckets each. Every bucket stores the fingerprint of an item and a counter.
+/// If an item maps to a bucket owned by another fingerprint, the counter of the bucket decays with probability decay^count,
+/// so small counters are taken over quickly, while the counters of heavy hitters are hard to decay.
+/// The k items with the highest counters are kept in a small list (redis uses a min-heap, as k is small a vector is sufficient).
+
+pub const DEFAULT_WIDTH: u64 = 8;
+pub const DEFAULT_DEPTH: u64 = 7;
+pub const DEFAULT_DECAY: f64 = 0.9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bucket {
+    fingerprint: u64,
+    count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopK {
+    k: u64,
+    width: u64,
+    depth: u64,
+    decay: f64,
+    // all rows stored in one array, row i starts at i * width
+    buckets: Vec<Bucket>,
+    // the current top k items with their counts, unordered
+    top_items: Vec<(String, u64)>,
+}
+
+impl TopK {
+    pub fn new(k: u64, width: u64, depth: u64, decay: f64) -> Self {
+        TopK {
+            k,
+            width,
+            depth,
+            decay,
+            buckets: vec![Bucket { fingerprint: 0, count: 0 }; (width * depth) as usize],
+            top_items: Vec::new(),
+        }
+    }
+
+    /// Increments the item and returns the item which got expelled from the top k list, if any
+    pub fn increment_by(&mut self, item: &str, increment: u64) -> Option<String> {
+        if increment == 0 {
+            return None;
+        }
+
+        let fingerprint = murmur_hash64a(item.as_bytes(), DEFAULT_SEED);
+        let mut rng = rand::thread_rng();
+        let mut max_count = 0;
+
+        for row in 0..self.depth {
+            let index = self.get_index(item, row);
+            let bucket = &mut self.buckets[index];
+
+            if bucket.count == 0 {
+                bucket.fingerprint = fingerprint;
+                bucket.count = increment;
+            } else if bucket.fingerprint == fingerprint {
+                bucket.count = bucket.count.saturating_add(increment);
+            } else {
+                // every increment tries to decay the counter of the other item, once it reaches 0 the bucket is taken over
+                for i in 0..increment {
+                    if rng.gen::<f64>() < self.decay.powf(bucket.count as f64) {
+                        bucket.count -= 1;
+                        if bucket.count == 0 {
+                            bucket.fingerprint = fingerprint;
+                            bucket.count = increment - i;
+                            break;
+                        }
+                    }
+                }
+            }
+
+            if bucket.fingerprint == fingerprint {
+                max_count = max_count.max(bucket.count);
+            }
+        }
+
+        self.update_top_items(item, max_count)
+    }
+
+    fn update_top_items(&mut self, item: &str, count: u64) -> Option<String> {
+        if let Some(entry) = self.top_items.iter_mut().find(|(top_item, _)| top_item == item) {
+            entry.1 = entry.1.max(count);
+            return None;
+        }
+
+        if (self.top_items.len() as u64) < self.k {
+            if count > 0 {
+                self.top_items.push((item.to_string(), count));
+            }
+            return None;
+        }
+
+        let (min_index, (_, min_count)) = self.top_items.iter().enumerate().min_by_key(|(_, (_, count))| *count)?;
+        if count <= *min_count {
+            return None;
+        }
+
+        let (expelled, _) = std::mem::replace(&mut self.top_items[min_index], (item.to_string(), count));
+        Some(expelled)
+    }
+
+    pub fn contains(&self, item: &str) -> bool {
+        self.top_items.iter().any(|(top_item, _)| top_item == item)
+    }
+
+    /// Estimated count of the item, which is the highest counter of all buckets owned by the item
+    pub fn count(&self, item: &str) -> u64 {
+        let fingerprint = murmur_hash64a(item.as_bytes(), DEFAULT_SEED);
+        (0..self.depth)
+            .map(|row| self.buckets[self.get_index(item, row)])
+            .filter(|bucket| bucket.fingerprint == fingerprint)
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the top k items ordered by count descending
+    pub fn list(&self) -> Vec<(String, u64)> {
+        let mut items = self.top_items.clone();
+        items.sort_by(|(item_a, count_a), (item_b, count_b)| count_b.cmp(count_a).then_with(|| item_a.cmp(item_b)));
+        items
+    }
+
+    pub fn get_k(&self) -> u64 {
+        self.k
+    }
+
+    pub fn get_width(&self) -> u64 {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> u64 {
+        self.depth
+    }
+
+    pub fn get_decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Number of bytes used by the top-k
+    pub fn get_size(&self) -> u64 {
+        let items_size: usize = self.top_items.iter().map(|(item, _)| item.capacity() + std::mem::size_of::<(String, u64)>()).sum();
+        (self.buckets.len() * std::mem::size_of::<Bucket>() + items_size + std::mem::size_of::<TopK>()) as u64
+    }
+
+    pub fn dump(&self) -> String {
+        let mut writer = DumpWriter::new();
+        writer.write_u64(self.k);
+        writer.write_u64(self.width);
+        writer.write_u64(self.depth);
+        writer.write_f64(self.decay);
+        for bucket in self.buckets.iter() {
+            writer.write_u64(bucket.fingerprint);
+            writer.write_u64(bucket.count);
+        }
+        writer.write_u64(self.top_items.len() as u64);
+        for (item, count) in self.top_items.iter() {
+            writer.write_bytes(item.as_bytes());
+            writer.write_u64(*count);
+        }
+        writer.to_hex()
+    }
+
+    pub fn restore(dump: &str) -> Result<TopK, String> {
+        let mut reader = DumpReader::from_hex(dump)?;
+        let k = reader.read_u64()?;
+        let width = reader.read_u64()?;
+        let depth = reader.read_u64()?;
+        let decay = reader.read_f64()?;
+
+        let size = width.checked_mul(depth).filter(|size| *size > 0).ok_or_else(|| String::from("invalid top-k dump"))?;
+        let mut buckets = Vec::new();
+        for _ in 0..size {
+            let fingerprint = reader.read_u64()?;
+            let count = reader.read_u64()?;
+            buckets.push(Bucket { fingerprint, count });
+        }
+
+        let num_items = reader.read_u64()?;
+        let mut top_items = Vec::new();
+        for _ in 0..num_items {
+            let item = String::from_utf8(reader.read_bytes()?).map_err(|_| String::from("invalid top-k dump"))?;
+            let count = reader.read_u64()?;
+            top_items.push((item, count));
+        }
+
+        Ok(TopK { k, width, depth, decay, buckets, top_items })
+    }
+
+    fn get_index(&self, item: &str, row: u64) -> usize {
+        (row * self.width + murmur_hash64a(item.as_bytes(), row) % self.width) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heavy_hitters() {
+        // given
+        let mut top_k = TopK::new(3, 50, 5, DEFAULT_DECAY);
+
+        // when
+        for i in 0..100 {
+            top_k.increment_by(&format!("noise{}", i), 1);
+        }
+        top_k.increment_by("a", 100);
+        top_k.increment_by("b", 80);
+        top_k.increment_by("c", 60);
+        for i in 100..200 {
+            top_k.increment_by(&format!("noise{}", i), 1);
+        }
+
+        // then
+        let items: Vec<String> = top_k.list().into_iter().map(|(item, _)| item).collect();
+        assert_eq!(items, vec!["a", "b", "c"]);
+        assert!(top_k.contains("a"));
+        assert!(!top_k.contains("noise1"));
+        assert!(top_k.count("a") >= 90);
+    }
+
+    #[test]
+    fn test_expelled_item() {
+        // given
+        let mut top_k = TopK::new(1, 10, 3, DEFAULT_DECAY);
+
+        // when
+        let first = top_k.increment_by("a", 1);
+        let second = top_k.increment_by("b", 5);
+
+        // then
+        assert_eq!(first, None);
+        assert_eq!(second, Some(String::from("a")));
+        assert_eq!(top_k.list(), vec![(String::from("b"), 5)]);
+    }
+
+    #[test]
+    fn test_dump_and_restore() {
+        // given
+        let mut top_k = TopK::new(2, 8, 3, 0.5);
+        top_k.increment_by("a", 3);
+        top_k.increment_by("b", 1);
+
+        // when
+        let restored = TopK::restore(&top_k.dump()).expect("Can not restore top-k");
+
+        // then
+        assert_eq!(restored, top_k);
+        assert!(TopK::restore("00").is_err());
+    }
+}
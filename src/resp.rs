@@ -1,5 +1,5 @@
 use std::i64;
-use std::io::{Read, Write};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::net::TcpStream;
 
 /// RESPParser is responsible for parsing Redis Serialization protocol (RESP2)
@@ -35,6 +35,26 @@ pub enum DataType {
     BulkString(String),
     Array(Vec<DataType>),
     Error(String),
+    // Null bulk string, used to represent a missing value (e.g. a member that does not exist)
+    Null,
+}
+
+/// Source of RESP encoded data, e.g. a client connection or the AOF file
+pub trait RESPStream: Read {
+    /// Returns whether there are bytes available to read without blocking
+    fn has_data(&mut self) -> bool;
+}
+
+impl RESPStream for TcpStream {
+    fn has_data(&mut self) -> bool {
+        self.peek(&mut [0; 1]).is_ok()
+    }
+}
+
+impl<R: Read> RESPStream for BufReader<R> {
+    fn has_data(&mut self) -> bool {
+        self.fill_buf().is_ok_and(|buffer| !buffer.is_empty())
+    }
 }
 
 impl DataType {
@@ -53,22 +73,15 @@ impl RESPParser {
         };
     }
 
-    fn read_line(&mut self, stream: &mut TcpStream) -> Option<&Vec<u8>> {
+    fn read_line<S: RESPStream>(&mut self, stream: &mut S) -> Option<&Vec<u8>> {
         if self.line_buffer.len() > 0 {
             // clear buffer if it is not empty
             self.line_buffer.clear();
         }
 
         // check whether there are bytes available to read
-        let mut buffer = [0; 1];
-        match stream.peek(&mut buffer) {
-            Ok(_) => {
-                // continue
-            }
-            Err(_) => {
-                // no bytes available to read
-                return None;
-            }
+        if !stream.has_data() {
+            return None;
         }
 
         // Parse sequence of bytes until next CRLF
@@ -114,12 +127,15 @@ impl RESPParser {
             DataType::Error(error) => {
                 format!("-{}\r\n", error)
             }
+            DataType::Null => {
+                String::from("$-1\r\n")
+            }
         };
     }
 
     /// Parse multiple commands from stream until no more bytes are available to read
     /// Method is used for pipelining
-    pub fn decode_next_bulk(&mut self, stream: &mut TcpStream) -> Result<Vec<DataType>, String> {
+    pub fn decode_next_bulk<S: RESPStream>(&mut self, stream: &mut S) -> Result<Vec<DataType>, String> {
         let mut bulk = Vec::new();
 
         loop {
@@ -139,7 +155,7 @@ impl RESPParser {
     }
 
     /// Parses next sequence of bytes from the stream and decodes it to a [`DataType`]
-    pub fn decode_next(&mut self, stream: &mut TcpStream) -> Result<DataType, String> {
+    pub fn decode_next<S: RESPStream>(&mut self, stream: &mut S) -> Result<DataType, String> {
         let line_option = self.read_line(stream);
         if line_option.is_none() {
             return Err(String::from("No more bytes to read"));
@@ -215,12 +231,14 @@ impl RESPParser {
         return Self::read_string(line.to_vec()).parse::<i64>().expect("Can not parse string to integer");
     }
 
-    pub fn write_to_stream(&mut self, stream: &mut TcpStream, data: Vec<DataType>) {
+    /// Writes the encoded data and returns the number of written bytes
+    pub fn write_to_stream(&mut self, stream: &mut TcpStream, data: Vec<DataType>) -> usize {
         let mut encoded_data = String::new();
         for data_type in data {
             encoded_data.push_str(&self.encode(data_type));
         }
         stream.write_all(encoded_data.as_bytes()).expect("Can not write to stream");
+        encoded_data.len()
     }
 
     pub fn flush_stream(&mut self, stream: &mut TcpStream) {
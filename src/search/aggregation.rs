@@ -0,0 +1,366 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Processing pipeline of FT.AGGREGATE
+/// see: https://redis.io/docs/interact/search-and-query/advanced-concepts/aggregations/
+///
+/// LOAD n @field...                                            loads fields of the matching documents into the rows
+/// GROUPBY n @field... REDUCE function nargs arg... [AS name]  groups the rows and reduces every group to one row
+/// SORTBY n @field [ASC|DESC]... [MAX num]                     sorts the rows, numbers are compared numerically
+/// LIMIT offset num                                            keeps num rows starting at offset
+///
+/// Supported reducers: COUNT, COUNT_DISTINCT, SUM, AVG, MIN and MAX.
+/// Steps are applied in the given order, fields referenced by a step have to be loaded or produced by a previous GROUPBY.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReduceFunction {
+    Count,
+    CountDistinct(String),
+    Sum(String),
+    Avg(String),
+    Min(String),
+    Max(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reducer {
+    pub function: ReduceFunction,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateStep {
+    GroupBy { fields: Vec<String>, reducers: Vec<Reducer> },
+    // fields with true for ascending order
+    SortBy { fields: Vec<(String, bool)>, max: Option<usize> },
+    Limit { offset: usize, count: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatePlan {
+    pub load: Vec<String>,
+    pub steps: Vec<AggregateStep>,
+}
+
+/// Values of a row and the fields which are returned, rows can contain loaded fields which are not returned
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub values: HashMap<String, String>,
+    pub fields: Vec<String>,
+}
+
+impl Row {
+    /// Returns the visible fields and their values, fields without a value are skipped
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        self.fields.iter()
+            .filter_map(|field| self.values.get(field).map(|value| (field.clone(), value.clone())))
+            .collect()
+    }
+}
+
+impl AggregatePlan {
+    pub fn parse(args: &[String]) -> Result<AggregatePlan, String> {
+        let mut plan = AggregatePlan { load: Vec::new(), steps: Vec::new() };
+
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "LOAD" => {
+                    let fields = read_counted_args(args, i, "LOAD")?;
+                    plan.load.extend(fields.iter().map(|field| to_field_name(field)));
+                    i += 2 + fields.len();
+                }
+                "GROUPBY" => {
+                    let fields: Vec<String> = read_counted_args(args, i, "GROUPBY")?.iter().map(|field| to_field_name(field)).collect();
+                    i += 2 + fields.len();
+
+                    let mut reducers = Vec::new();
+                    while i < args.len() && args[i].eq_ignore_ascii_case("REDUCE") {
+                        let (reducer, consumed) = parse_reducer(&args[i..])?;
+                        reducers.push(reducer);
+                        i += consumed;
+                    }
+                    plan.steps.push(AggregateStep::GroupBy { fields, reducers });
+                }
+                "SORTBY" => {
+                    let sort_args = read_counted_args(args, i, "SORTBY")?;
+                    i += 2 + sort_args.len();
+
+                    let mut fields: Vec<(String, bool)> = Vec::new();
+                    for arg in sort_args.iter() {
+                        match (arg.to_uppercase().as_str(), fields.last_mut()) {
+                            ("ASC", Some(field)) => field.1 = true,
+                            ("DESC", Some(field)) => field.1 = false,
+                            _ if arg.starts_with('@') => fields.push((to_field_name(arg), true)),
+                            _ => return Err(format!("Bad arguments for SORTBY: unexpected `{}`", arg)),
+                        }
+                    }
+
+                    let mut max = None;
+                    if i + 1 < args.len() && args[i].eq_ignore_ascii_case("MAX") {
+                        max = Some(parse_count(&args[i + 1], "MAX")?);
+                        i += 2;
+                    }
+                    plan.steps.push(AggregateStep::SortBy { fields, max });
+                }
+                "LIMIT" if i + 2 < args.len() => {
+                    let offset = parse_count(&args[i + 1], "LIMIT")?;
+                    let count = parse_count(&args[i + 2], "LIMIT")?;
+                    plan.steps.push(AggregateStep::Limit { offset, count });
+                    i += 3;
+                }
+                // only dialect 2 is implemented, other values are accepted
+                "DIALECT" if i + 1 < args.len() => {
+                    parse_count(&args[i + 1], "DIALECT")?;
+                    i += 2;
+                }
+                _ => return Err(format!("Unknown argument `{}`", args[i])),
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Returns all fields which have to be read from the documents
+    pub fn get_document_fields(&self) -> Vec<String> {
+        let mut fields = self.load.clone();
+        let mut produced: HashSet<&str> = HashSet::new();
+
+        for step in self.steps.iter() {
+            let referenced: Vec<&String> = match step {
+                AggregateStep::GroupBy { fields, reducers } => fields.iter().chain(reducers.iter().filter_map(|reducer| reducer.function.get_field())).collect(),
+                AggregateStep::SortBy { fields, .. } => fields.iter().map(|(field, _)| field).collect(),
+                AggregateStep::Limit { .. } => Vec::new(),
+            };
+            for field in referenced {
+                if !produced.contains(field.as_str()) && !fields.contains(field) {
+                    fields.push(field.clone());
+                }
+            }
+
+            if let AggregateStep::GroupBy { fields, reducers } = step {
+                produced.extend(fields.iter().map(|field| field.as_str()));
+                produced.extend(reducers.iter().map(|reducer| reducer.alias.as_str()));
+            }
+        }
+
+        fields
+    }
+
+    /// Runs the steps on the rows of the matching documents
+    pub fn execute(&self, mut rows: Vec<Row>) -> Vec<Row> {
+        for step in self.steps.iter() {
+            rows = match step {
+                AggregateStep::GroupBy { fields, reducers } => group_by(rows, fields, reducers),
+                AggregateStep::SortBy { fields, max } => {
+                    rows.sort_by(|a, b| compare_rows(a, b, fields));
+                    if let Some(max) = max {
+                        rows.truncate(*max);
+                    }
+                    rows
+                }
+                AggregateStep::Limit { offset, count } => rows.into_iter().skip(*offset).take(*count).collect(),
+            };
+        }
+        rows
+    }
+}
+
+impl ReduceFunction {
+    fn get_field(&self) -> Option<&String> {
+        match self {
+            ReduceFunction::Count => None,
+            ReduceFunction::CountDistinct(field) | ReduceFunction::Sum(field) | ReduceFunction::Avg(field)
+            | ReduceFunction::Min(field) | ReduceFunction::Max(field) => Some(field),
+        }
+    }
+
+    fn reduce(&self, rows: &[&Row]) -> String {
+        let values = || rows.iter().filter_map(|row| self.get_field().and_then(|field| row.values.get(field)));
+        let numbers = || values().filter_map(|value| value.parse::<f64>().ok());
+
+        match self {
+            ReduceFunction::Count => rows.len().to_string(),
+            ReduceFunction::CountDistinct(_) => values().collect::<HashSet<&String>>().len().to_string(),
+            ReduceFunction::Sum(_) => numbers().sum::<f64>().to_string(),
+            ReduceFunction::Avg(_) => {
+                let count = numbers().count();
+                if count == 0 {
+                    return 0.to_string();
+                }
+                (numbers().sum::<f64>() / count as f64).to_string()
+            }
+            ReduceFunction::Min(_) => numbers().fold(f64::INFINITY, f64::min).to_string(),
+            ReduceFunction::Max(_) => numbers().fold(f64::NEG_INFINITY, f64::max).to_string(),
+        }
+    }
+}
+
+/// Parses `REDUCE function nargs arg... [AS name]` and returns the reducer and the number of consumed arguments
+fn parse_reducer(args: &[String]) -> Result<(Reducer, usize), String> {
+    let name = args.get(1).ok_or_else(|| String::from("Bad arguments for REDUCE: missing function"))?.to_uppercase();
+    let reduce_args = read_counted_args(args, 1, "REDUCE")?;
+    let mut consumed = 3 + reduce_args.len();
+
+    let field = || match reduce_args {
+        [field] => Ok(to_field_name(field)),
+        _ => Err(format!("Bad arguments for {}: expected one field", name)),
+    };
+    let function = match name.as_str() {
+        "COUNT" if reduce_args.is_empty() => ReduceFunction::Count,
+        "COUNT" => return Err(String::from("Bad arguments for COUNT: expected no arguments")),
+        "COUNT_DISTINCT" => ReduceFunction::CountDistinct(field()?),
+        "SUM" => ReduceFunction::Sum(field()?),
+        "AVG" => ReduceFunction::Avg(field()?),
+        "MIN" => ReduceFunction::Min(field()?),
+        "MAX" => ReduceFunction::Max(field()?),
+        _ => return Err(format!("No such reducer `{}`", name)),
+    };
+
+    let alias = match (args.get(consumed), args.get(consumed + 1)) {
+        (Some(option), Some(alias)) if option.eq_ignore_ascii_case("AS") => {
+            consumed += 2;
+            alias.clone()
+        }
+        _ => format!("__generated_alias{}{}", name.to_lowercase(), function.get_field().cloned().unwrap_or_default()),
+    };
+
+    Ok((Reducer { function, alias }, consumed))
+}
+
+/// Returns the arguments following `args[i] nargs`
+fn read_counted_args<'a>(args: &'a [String], i: usize, option: &str) -> Result<&'a [String], String> {
+    let count = args.get(i + 1).and_then(|count| count.parse::<usize>().ok());
+    match count {
+        Some(count) if i + 2 + count <= args.len() => Ok(&args[i + 2..i + 2 + count]),
+        _ => Err(format!("Bad arguments for {}", option)),
+    }
+}
+
+fn parse_count(value: &str, option: &str) -> Result<usize, String> {
+    value.parse::<usize>().map_err(|_| format!("Bad arguments for {}", option))
+}
+
+fn to_field_name(field: &str) -> String {
+    field.strip_prefix('@').unwrap_or(field).to_string()
+}
+
+/// Groups rows with equal values of the fields, groups are returned in the order of their first row
+fn group_by(rows: Vec<Row>, fields: &[String], reducers: &[Reducer]) -> Vec<Row> {
+    let mut group_keys: Vec<Vec<Option<String>>> = Vec::new();
+    let mut groups: HashMap<Vec<Option<String>>, Vec<&Row>> = HashMap::new();
+
+    for row in rows.iter() {
+        let key: Vec<Option<String>> = fields.iter().map(|field| row.values.get(field).cloned()).collect();
+        if !groups.contains_key(&key) {
+            group_keys.push(key.clone());
+        }
+        groups.entry(key).or_default().push(row);
+    }
+
+    group_keys.into_iter().map(|key| {
+        let mut values: HashMap<String, String> = fields.iter().zip(key.iter())
+            .filter_map(|(field, value)| value.as_ref().map(|value| (field.clone(), value.clone())))
+            .collect();
+        for reducer in reducers.iter() {
+            values.insert(reducer.alias.clone(), reducer.function.reduce(&groups[&key]));
+        }
+
+        Row {
+            values,
+            fields: fields.iter().chain(reducers.iter().map(|reducer| &reducer.alias)).cloned().collect(),
+        }
+    }).collect()
+}
+
+/// Compares the values of the sort fields, numerically if both values are numbers. Rows without a value are sorted last
+fn compare_rows(a: &Row, b: &Row, fields: &[(String, bool)]) -> Ordering {
+    for (field, ascending) in fields.iter() {
+        let ordering = match (a.values.get(field), b.values.get(field)) {
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<f64>(), b.parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.total_cmp(&b),
+                    _ => a.cmp(b),
+                };
+                if *ascending { ordering } else { ordering.reverse() }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    fn row(values: &[(&str, &str)]) -> Row {
+        Row {
+            values: values.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect(),
+            fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_group_and_sort() {
+        // given
+        let plan = AggregatePlan::parse(&to_args("GROUPBY 1 @brand REDUCE COUNT 0 AS count REDUCE AVG 1 @price REDUCE MAX 1 @price SORTBY 2 @count DESC LIMIT 0 2")).unwrap();
+        let rows = vec![
+            row(&[("brand", "a"), ("price", "10")]),
+            row(&[("brand", "b"), ("price", "5")]),
+            row(&[("brand", "a"), ("price", "20")]),
+            row(&[("brand", "c"), ("price", "100")]),
+            row(&[("brand", "b"), ("price", "15")]),
+            row(&[("brand", "b"), ("price", "x")]),
+        ];
+
+        // when
+        let result: Vec<Vec<(String, String)>> = plan.execute(rows).iter().map(|row| row.to_pairs()).collect();
+
+        // then
+        let to_pairs = |pairs: &[(&str, &str)]| pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect::<Vec<_>>();
+        assert_eq!(result, vec![
+            to_pairs(&[("brand", "b"), ("count", "3"), ("__generated_aliasavgprice", "10"), ("__generated_aliasmaxprice", "15")]),
+            to_pairs(&[("brand", "a"), ("count", "2"), ("__generated_aliasavgprice", "15"), ("__generated_aliasmaxprice", "20")]),
+        ]);
+        assert_eq!(plan.get_document_fields(), vec![String::from("brand"), String::from("price")]);
+    }
+
+    #[test]
+    fn test_sort_loaded_fields() {
+        // given
+        let plan = AggregatePlan::parse(&to_args("LOAD 1 @name SORTBY 2 @price ASC MAX 3")).unwrap();
+        let rows = vec![
+            row(&[("name", "a"), ("price", "10")]),
+            row(&[("name", "b"), ("price", "9")]),
+            row(&[("name", "c")]),
+            row(&[("name", "d"), ("price", "100")]),
+        ];
+
+        // when
+        let result: Vec<String> = plan.execute(rows).iter().map(|row| row.values["name"].clone()).collect();
+
+        // then
+        assert_eq!(result, vec!["b", "a", "d"]);
+        assert_eq!(plan.get_document_fields(), vec![String::from("name"), String::from("price")]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(AggregatePlan::parse(&to_args("GROUPBY 2 @brand")).is_err());
+        assert!(AggregatePlan::parse(&to_args("GROUPBY 1 @brand REDUCE MEDIAN 1 @price")).is_err());
+        assert!(AggregatePlan::parse(&to_args("GROUPBY 1 @brand REDUCE SUM 0")).is_err());
+        assert!(AggregatePlan::parse(&to_args("SORTBY 1 ASC")).is_err());
+        assert!(AggregatePlan::parse(&to_args("LIMIT 0")).is_err());
+        assert!(AggregatePlan::parse(&to_args("APPLY x")).is_err());
+    }
+}
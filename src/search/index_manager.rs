@@ -0,0 +1,97 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::dict::Dict;
+use crate::search::schema::IndexDefinition;
+use crate::search::search_index::SearchIndex;
+use crate::store::{ObjectValue, StoreObject};
+
+/// Keeps all search indexes of the store up to date
+///
+/// The store notifies the manager whenever a key is put or removed, so new and deleted keys are (un)indexed immediately.
+/// Commands which modify values in place (e.g. HSET on an existing hash) only get a mutable reference to the value,
+/// so the key is marked as modified and indexed again before the next search.
+
+pub struct IndexManager {
+    indexes: HashMap<String, SearchIndex>,
+    modified_keys: HashSet<String>,
+}
+
+impl IndexManager {
+    pub fn new() -> Self {
+        IndexManager {
+            indexes: HashMap::new(),
+            modified_keys: HashSet::new(),
+        }
+    }
+
+    /// Creates the index and indexes all existing keys matching its prefixes
+    pub fn create_index(&mut self, definition: IndexDefinition, data: &Dict<StoreObject>) -> Result<(), String> {
+        if self.indexes.contains_key(&definition.name) {
+            return Err(String::from("Index already exists"));
+        }
+
+        let mut index = SearchIndex::new(definition)?;
+        for (key, value) in data.iter() {
+            if index.definition.matches_key(key) {
+                index.index_document(key, value.value.as_ref());
+            }
+        }
+
+        self.indexes.insert(index.definition.name.clone(), index);
+        Ok(())
+    }
+
+    pub fn drop_index(&mut self, name: &str) -> Option<SearchIndex> {
+        self.indexes.remove(name)
+    }
+
+    pub fn get_index(&self, name: &str) -> Option<&SearchIndex> {
+        self.indexes.get(name)
+    }
+
+    pub fn get_definitions(&self) -> Vec<&IndexDefinition> {
+        self.indexes.values().map(|index| &index.definition).collect()
+    }
+
+    /// Returns whether any index covers the key
+    pub fn is_indexed(&self, key: &str) -> bool {
+        self.indexes.values().any(|index| index.definition.matches_key(key))
+    }
+
+    pub fn on_put(&mut self, key: &str, value: &ObjectValue) {
+        self.modified_keys.remove(key);
+        for index in self.indexes.values_mut() {
+            if index.definition.matches_key(key) {
+                index.index_document(key, value);
+            }
+        }
+    }
+
+    pub fn on_remove(&mut self, key: &str) {
+        self.modified_keys.remove(key);
+        for index in self.indexes.values_mut() {
+            index.remove_document(key);
+        }
+    }
+
+    pub fn on_modify(&mut self, key: &str) {
+        if self.is_indexed(key) {
+            self.modified_keys.insert(key.to_string());
+        }
+    }
+
+    /// Indexes the keys which were modified in place since the last refresh
+    pub fn refresh(&mut self, data: &Dict<StoreObject>) {
+        for key in self.modified_keys.drain() {
+            for index in self.indexes.values_mut() {
+                if !index.definition.matches_key(&key) {
+                    continue;
+                }
+                match data.get(&key) {
+                    Some(value) => index.index_document(&key, value.value.as_ref()),
+                    None => index.remove_document(&key),
+                }
+            }
+        }
+    }
+}
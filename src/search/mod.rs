@@ -0,0 +1,9 @@
+pub mod schema;
+pub mod query;
+pub mod tokenizer;
+pub mod stemmer;
+pub mod text_index;
+pub mod aggregation;
+pub mod search_index;
+pub mod index_manager;
+pub mod vector;
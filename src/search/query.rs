@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+/// Query syntax of FT.SEARCH and FT.AGGREGATE
+/// see: https://redis.io/docs/interact/search-and-query/query/
+///
+/// *                                       all documents
+/// word                                    documents containing the (stemmed) word in any TEXT field
+/// prefix*                                 documents containing a word starting with the prefix
+/// @field:word, @field:(word1 word2)       words within one TEXT field
+/// @field:[min max]                        numeric range, `(` excludes a bound, `-inf` and `+inf` are unbounded
+/// @field:{tag1 | tag2}                    documents with one of the tags
+/// -expression                             negation
+/// expression1 expression2                 intersection, all expressions have to match
+/// expression1 | expression2               union, one of the expressions has to match
+/// (expression)                            grouping
+/// expression=>[KNN k @field $param [AS name]] the k documents with the nearest vectors which match the expression
+///
+/// `$name` references a parameter passed with the PARAMS option, it can be used for the vector and k.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryNode {
+    All,
+    // field is None for terms which are searched in all TEXT fields
+    Term { field: Option<String>, term: String, is_prefix: bool },
+    Numeric { field: String, min: f64, min_exclusive: bool, max: f64, max_exclusive: bool },
+    Tag { field: String, values: Vec<String> },
+    Not(Box<QueryNode>),
+    Intersect(Vec<QueryNode>),
+    Union(Vec<QueryNode>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnnClause {
+    pub k: usize,
+    pub field: String,
+    pub vector: String,
+    // name of the returned distance field, defaults to __<field>_score
+    pub score_field: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub root: QueryNode,
+    pub knn: Option<KnnClause>,
+}
+
+impl Query {
+    pub fn parse(query: &str, params: &HashMap<String, String>) -> Result<Query, String> {
+        let (expression, knn) = match query.find("=>") {
+            Some(position) => (&query[..position], Some(parse_knn(&query[position + 2..], params)?)),
+            None => (query, None),
+        };
+
+        let mut parser = QueryParser { input: expression.chars().collect(), position: 0 };
+        let root = parser.parse_union(None)?;
+        parser.skip_whitespace();
+        if parser.position < parser.input.len() {
+            return Err(format!("Syntax error at offset {} near `{}`", parser.position, parser.input[parser.position]));
+        }
+
+        Ok(Query { root, knn })
+    }
+}
+
+fn parse_knn(clause: &str, params: &HashMap<String, String>) -> Result<KnnClause, String> {
+    let clause = clause.trim();
+    let clause = clause.strip_prefix('[').and_then(|clause| clause.strip_suffix(']'))
+        .ok_or_else(|| String::from("Syntax error: expected [KNN k @field $vector]"))?;
+
+    let tokens: Vec<&str> = clause.split_whitespace().collect();
+    if tokens.len() < 4 || !tokens[0].eq_ignore_ascii_case("KNN") {
+        return Err(String::from("Syntax error: expected [KNN k @field $vector]"));
+    }
+
+    let k = resolve_param(tokens[1], params)?;
+    let k = k.parse::<usize>().map_err(|_| format!("Invalid value for k: {}", k))?;
+    let field = tokens[2].strip_prefix('@').ok_or_else(|| format!("Syntax error: expected a vector field, got {}", tokens[2]))?.to_string();
+    let vector = resolve_param(tokens[3], params)?;
+
+    let score_field = match tokens.get(4) {
+        Some(token) if token.eq_ignore_ascii_case("AS") && tokens.len() == 6 => tokens[5].to_string(),
+        None => format!("__{}_score", field),
+        Some(_) => return Err(String::from("Syntax error: unexpected arguments in KNN clause")),
+    };
+
+    Ok(KnnClause { k, field, vector, score_field })
+}
+
+fn resolve_param(token: &str, params: &HashMap<String, String>) -> Result<String, String> {
+    match token.strip_prefix('$') {
+        Some(name) => params.get(name).cloned().ok_or_else(|| format!("No such parameter `{}`", name)),
+        None => Ok(token.to_string()),
+    }
+}
+
+struct QueryParser {
+    input: Vec<char>,
+    position: usize,
+}
+
+impl QueryParser {
+    /// Parses expressions separated by `|`. The field scopes the terms of `@field:(...)` expressions
+    fn parse_union(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        let mut nodes = vec![self.parse_intersect(field)?];
+        loop {
+            self.skip_whitespace();
+            if !self.consume('|') {
+                break;
+            }
+            nodes.push(self.parse_intersect(field)?);
+        }
+
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { QueryNode::Union(nodes) })
+    }
+
+    fn parse_intersect(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some(')') | Some('|') => break,
+                _ => nodes.push(self.parse_node(field)?),
+            }
+        }
+
+        match nodes.len() {
+            0 => Err(format!("Syntax error at offset {}: expected an expression", self.position)),
+            1 => Ok(nodes.remove(0)),
+            _ => Ok(QueryNode::Intersect(nodes)),
+        }
+    }
+
+    fn parse_node(&mut self, field: Option<&str>) -> Result<QueryNode, String> {
+        if self.consume('-') {
+            return Ok(QueryNode::Not(Box::new(self.parse_node(field)?)));
+        }
+        if self.consume('(') {
+            let node = self.parse_union(field)?;
+            self.skip_whitespace();
+            if !self.consume(')') {
+                return Err(String::from("Syntax error: missing `)`"));
+            }
+            return Ok(node);
+        }
+        if self.consume('*') {
+            return Ok(QueryNode::All);
+        }
+        if self.consume('@') {
+            return self.parse_field_expression();
+        }
+
+        let term = self.read_term();
+        if term.is_empty() {
+            return Err(format!("Syntax error at offset {}: unexpected `{}`", self.position, self.input[self.position]));
+        }
+        let is_prefix = self.consume('*');
+        Ok(QueryNode::Term { field: field.map(|field| field.to_string()), term: term.to_lowercase(), is_prefix })
+    }
+
+    fn parse_field_expression(&mut self) -> Result<QueryNode, String> {
+        let field = self.read_while(|char| char.is_alphanumeric() || char == '_' || char == '.');
+        if field.is_empty() || !self.consume(':') {
+            return Err(format!("Syntax error at offset {}: expected @field:", self.position));
+        }
+        self.skip_whitespace();
+
+        if self.consume('[') {
+            let range = self.read_until(']')?;
+            let bounds: Vec<&str> = range.split_whitespace().collect();
+            if bounds.len() != 2 {
+                return Err(format!("Syntax error: invalid numeric range [{}]", range));
+            }
+            let (min, min_exclusive) = parse_bound(bounds[0])?;
+            let (max, max_exclusive) = parse_bound(bounds[1])?;
+            return Ok(QueryNode::Numeric { field, min, min_exclusive, max, max_exclusive });
+        }
+
+        if self.consume('{') {
+            let tags = self.read_until('}')?;
+            let values: Vec<String> = tags.split('|').map(|tag| tag.trim().replace('\\', "")).filter(|tag| !tag.is_empty()).collect();
+            if values.is_empty() {
+                return Err(String::from("Syntax error: empty tag filter"));
+            }
+            return Ok(QueryNode::Tag { field, values });
+        }
+
+        self.parse_node(Some(&field))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).cloned()
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.read_while(|char| char.is_whitespace());
+    }
+
+    fn read_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> String {
+        let start = self.position;
+        while self.position < self.input.len() && predicate(self.input[self.position]) {
+            self.position += 1;
+        }
+        self.input[start..self.position].iter().collect()
+    }
+
+    /// Reads a word, escaped characters (e.g. `\-`) are part of the word
+    fn read_term(&mut self) -> String {
+        let mut term = String::new();
+        while let Some(char) = self.peek() {
+            if char == '\\' && self.position + 1 < self.input.len() {
+                term.push(self.input[self.position + 1]);
+                self.position += 2;
+            } else if char.is_alphanumeric() || char == '_' {
+                term.push(char);
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        term
+    }
+
+    /// Reads until the closing character (escaped characters are skipped) and consumes it
+    fn read_until(&mut self, end: char) -> Result<String, String> {
+        let start = self.position;
+        while self.position < self.input.len() && self.input[self.position] != end {
+            if self.input[self.position] == '\\' {
+                self.position += 1;
+            }
+            self.position += 1;
+        }
+
+        if self.position >= self.input.len() {
+            return Err(format!("Syntax error: missing `{}`", end));
+        }
+        let value = self.input[start..self.position].iter().collect();
+        self.position += 1;
+        Ok(value)
+    }
+}
+
+fn parse_bound(bound: &str) -> Result<(f64, bool), String> {
+    let (bound, exclusive) = match bound.strip_prefix('(') {
+        Some(bound) => (bound, true),
+        None => (bound, false),
+    };
+
+    let value = match bound.to_lowercase().as_str() {
+        "-inf" => f64::NEG_INFINITY,
+        "inf" | "+inf" => f64::INFINITY,
+        _ => bound.parse::<f64>().map_err(|_| format!("Syntax error: invalid numeric value {}", bound))?,
+    };
+    Ok((value, exclusive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(field: Option<&str>, term: &str) -> QueryNode {
+        QueryNode::Term { field: field.map(|field| field.to_string()), term: term.to_string(), is_prefix: false }
+    }
+
+    #[test]
+    fn test_parse_knn_query() {
+        // given
+        let params = HashMap::from([(String::from("BLOB"), String::from("1,2")), (String::from("K"), String::from("3"))]);
+
+        // when
+        let query = Query::parse("(@genre:{action | sci\\-fi} -@price:[(10 +inf])=>[KNN $K @vec $BLOB AS distance]", &params).unwrap();
+
+        // then
+        assert_eq!(query.root, QueryNode::Intersect(vec![
+            QueryNode::Tag { field: String::from("genre"), values: vec![String::from("action"), String::from("sci-fi")] },
+            QueryNode::Not(Box::new(QueryNode::Numeric { field: String::from("price"), min: 10.0, min_exclusive: true, max: f64::INFINITY, max_exclusive: false })),
+        ]));
+        assert_eq!(query.knn, Some(KnnClause { k: 3, field: String::from("vec"), vector: String::from("1,2"), score_field: String::from("distance") }));
+    }
+
+    #[test]
+    fn test_parse_text_query() {
+        // when
+        let query = Query::parse("Hello wor* | @title:(foo -bar) @body:baz", &HashMap::new()).unwrap();
+
+        // then
+        assert_eq!(query.root, QueryNode::Union(vec![
+            QueryNode::Intersect(vec![term(None, "hello"), QueryNode::Term { field: None, term: String::from("wor"), is_prefix: true }]),
+            QueryNode::Intersect(vec![
+                QueryNode::Intersect(vec![term(Some("title"), "foo"), QueryNode::Not(Box::new(term(Some("title"), "bar")))]),
+                term(Some("body"), "baz"),
+            ]),
+        ]));
+        assert_eq!(query.knn, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let params = HashMap::new();
+        assert_eq!(Query::parse("*", &params).unwrap().root, QueryNode::All);
+        assert_eq!(Query::parse("*=>[KNN 2 @vec 1,2]", &params).unwrap().knn.unwrap().score_field, "__vec_score");
+        assert!(Query::parse("*=>[KNN 2 @vec $BLOB]", &params).is_err());
+        assert!(Query::parse("@price:[1 2", &params).is_err());
+        assert!(Query::parse("(hello", &params).is_err());
+        assert!(Query::parse("hello)", &params).is_err());
+        assert!(Query::parse("", &params).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::search::vector::distance::DistanceMetric;
+
+/// Index definition of FT.CREATE
+/// see: https://redis.io/commands/ft.create/
+///
+/// FT.CREATE index [ON HASH | JSON] [PREFIX count prefix [prefix ...]] SCHEMA field [AS alias] type [options] [field ...]
+///
+/// Supported field types:
+/// TEXT [WEIGHT weight] [NOSTEM]
+/// NUMERIC
+/// TAG [SEPARATOR separator] [CASESENSITIVE]
+/// VECTOR FLAT | HNSW count TYPE FLOAT32 DIM dim DISTANCE_METRIC L2 | IP | COSINE [M m] [EF_CONSTRUCTION ef] [EF_RUNTIME ef]
+///
+/// SORTABLE is accepted for all field types, every field can be used by SORTBY.
+/// Fields of hash indexes are hash fields, fields of JSON indexes are JSONPath expressions (e.g. `$.embedding AS embedding`).
+/// Vectors are stored as text, either comma separated (`0.1,0.2,0.3`) or as JSON array (`[0.1,0.2,0.3]`).
+
+pub const DEFAULT_HNSW_M: usize = 16;
+pub const DEFAULT_HNSW_EF_CONSTRUCTION: usize = 200;
+pub const DEFAULT_HNSW_EF_RUNTIME: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexTarget {
+    Hash,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorAlgorithm {
+    Flat,
+    Hnsw { m: usize, ef_construction: usize, ef_runtime: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VectorFieldOptions {
+    pub algorithm: VectorAlgorithm,
+    pub dim: usize,
+    pub metric: DistanceMetric,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Text { weight: f64, no_stem: bool },
+    Numeric,
+    Tag { separator: char, case_sensitive: bool },
+    Vector(VectorFieldOptions),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaField {
+    // hash field or JSONPath the value is read from
+    pub path: String,
+    // name of the field within queries
+    pub name: String,
+    pub field_type: FieldType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexDefinition {
+    pub name: String,
+    pub target: IndexTarget,
+    pub prefixes: Vec<String>,
+    pub fields: Vec<SchemaField>,
+    // arguments of FT.CREATE, used to recreate the index when rewriting the AOF file
+    pub arguments: Vec<String>,
+}
+
+impl IndexDefinition {
+    /// Parses the arguments of FT.CREATE (starting with the index name)
+    pub fn parse(args: &[String]) -> Result<IndexDefinition, String> {
+        let name = args.first().ok_or_else(|| String::from("ERR wrong number of arguments for 'ft.create' command"))?;
+        let mut target = IndexTarget::Hash;
+        let mut prefixes = Vec::new();
+
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].to_uppercase().as_str() {
+                "ON" if i + 1 < args.len() => {
+                    target = match args[i + 1].to_uppercase().as_str() {
+                        "HASH" => IndexTarget::Hash,
+                        "JSON" => IndexTarget::Json,
+                        _ => return Err(format!("ERR Unknown index type '{}'", args[i + 1])),
+                    };
+                    i += 2;
+                }
+                "PREFIX" if i + 1 < args.len() => {
+                    let count = args[i + 1].parse::<usize>().map_err(|_| String::from("ERR Bad arguments for PREFIX: expected a number"))?;
+                    if i + 2 + count > args.len() {
+                        return Err(String::from("ERR Bad arguments for PREFIX: not enough arguments"));
+                    }
+                    prefixes.extend(args[i + 2..i + 2 + count].iter().cloned());
+                    i += 2 + count;
+                }
+                "SCHEMA" => break,
+                _ => return Err(format!("ERR Unknown argument `{}`", args[i])),
+            }
+        }
+
+        if i >= args.len() {
+            return Err(String::from("ERR No schema found"));
+        }
+
+        let fields = parse_schema_fields(&args[i + 1..])?;
+        if fields.is_empty() {
+            return Err(String::from("ERR Fields arguments are missing"));
+        }
+
+        Ok(IndexDefinition {
+            name: name.clone(),
+            target,
+            prefixes,
+            fields,
+            arguments: args.to_vec(),
+        })
+    }
+
+    /// An index without prefixes covers all keys
+    pub fn matches_key(&self, key: &str) -> bool {
+        self.prefixes.is_empty() || self.prefixes.iter().any(|prefix| key.starts_with(prefix.as_str()))
+    }
+
+    pub fn get_field(&self, name: &str) -> Option<&SchemaField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+fn parse_schema_fields(args: &[String]) -> Result<Vec<SchemaField>, String> {
+    let mut fields: Vec<SchemaField> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let path = args[i].clone();
+        let mut name = path.clone();
+        i += 1;
+
+        if i + 1 < args.len() && args[i].eq_ignore_ascii_case("AS") {
+            name = args[i + 1].clone();
+            i += 2;
+        }
+
+        let field_type = match args.get(i).map(|field_type| field_type.to_uppercase()) {
+            Some(field_type) => field_type,
+            None => return Err(format!("ERR Field `{}` does not have a type", name)),
+        };
+        i += 1;
+
+        let field_type = match field_type.as_str() {
+            "TEXT" => {
+                let mut weight = 1.0;
+                let mut no_stem = false;
+                loop {
+                    match args.get(i).map(|option| option.to_uppercase()) {
+                        Some(option) if option == "WEIGHT" && i + 1 < args.len() => {
+                            weight = match args[i + 1].parse::<f64>() {
+                                Ok(weight) if weight.is_finite() && weight >= 0.0 => weight,
+                                _ => return Err(String::from("ERR Could not parse field weight")),
+                            };
+                            i += 2;
+                        }
+                        Some(option) if option == "NOSTEM" => {
+                            no_stem = true;
+                            i += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                FieldType::Text { weight, no_stem }
+            }
+            "NUMERIC" => FieldType::Numeric,
+            "TAG" => {
+                let mut separator = ',';
+                let mut case_sensitive = false;
+                loop {
+                    match args.get(i).map(|option| option.to_uppercase()) {
+                        Some(option) if option == "SEPARATOR" && i + 1 < args.len() => {
+                            let mut chars = args[i + 1].chars();
+                            separator = match (chars.next(), chars.next()) {
+                                (Some(separator), None) => separator,
+                                _ => return Err(String::from("ERR Tag separator must be a single character")),
+                            };
+                            i += 2;
+                        }
+                        Some(option) if option == "CASESENSITIVE" => {
+                            case_sensitive = true;
+                            i += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                FieldType::Tag { separator, case_sensitive }
+            }
+            "VECTOR" => {
+                let (options, consumed) = parse_vector_options(&args[i..])?;
+                i += consumed;
+                FieldType::Vector(options)
+            }
+            _ => return Err(format!("ERR Invalid field type for field `{}`", name)),
+        };
+
+        // all fields are sortable, so the option has no effect
+        if args.get(i).is_some_and(|option| option.eq_ignore_ascii_case("SORTABLE")) {
+            i += 1;
+        }
+
+        if fields.iter().any(|field| field.name == name) {
+            return Err(format!("ERR Duplicate field in schema - {}", name));
+        }
+        fields.push(SchemaField { path, name, field_type });
+    }
+
+    Ok(fields)
+}
+
+/// Parses `FLAT | HNSW count attribute value ...` and returns the options and the number of consumed arguments
+fn parse_vector_options(args: &[String]) -> Result<(VectorFieldOptions, usize), String> {
+    let algorithm = args.first().map(|algorithm| algorithm.to_uppercase()).unwrap_or_default();
+    let count = match args.get(1).map(|count| count.parse::<usize>()) {
+        Some(Ok(count)) if count.is_multiple_of(2) && 2 + count <= args.len() => count,
+        _ => return Err(String::from("ERR Bad arguments for vector similarity number of parameters")),
+    };
+
+    let mut dim = None;
+    let mut metric = None;
+    let mut m = DEFAULT_HNSW_M;
+    let mut ef_construction = DEFAULT_HNSW_EF_CONSTRUCTION;
+    let mut ef_runtime = DEFAULT_HNSW_EF_RUNTIME;
+
+    for pair in args[2..2 + count].chunks(2) {
+        let value = &pair[1];
+        let parse_number = |name: &str| match value.parse::<usize>() {
+            Ok(number) if number > 0 => Ok(number),
+            _ => Err(format!("ERR Bad arguments for vector similarity {}", name)),
+        };
+
+        match pair[0].to_uppercase().as_str() {
+            "TYPE" => {
+                if !value.eq_ignore_ascii_case("FLOAT32") {
+                    return Err(format!("ERR Bad arguments for vector similarity TYPE: unsupported type {}", value));
+                }
+            }
+            "DIM" => dim = Some(parse_number("DIM")?),
+            "DISTANCE_METRIC" => {
+                metric = Some(DistanceMetric::parse(value).ok_or_else(|| format!("ERR Bad arguments for vector similarity DISTANCE_METRIC: unknown metric {}", value))?);
+            }
+            "INITIAL_CAP" | "BLOCK_SIZE" => {
+                parse_number(&pair[0].to_uppercase())?;
+            }
+            "M" if algorithm == "HNSW" => m = parse_number("M")?,
+            "EF_CONSTRUCTION" if algorithm == "HNSW" => ef_construction = parse_number("EF_CONSTRUCTION")?,
+            "EF_RUNTIME" if algorithm == "HNSW" => ef_runtime = parse_number("EF_RUNTIME")?,
+            _ => return Err(format!("ERR Bad arguments for vector similarity {} index: unknown argument {}", algorithm, pair[0])),
+        }
+    }
+
+    let algorithm = match algorithm.as_str() {
+        "FLAT" => VectorAlgorithm::Flat,
+        "HNSW" => VectorAlgorithm::Hnsw { m, ef_construction, ef_runtime },
+        _ => return Err(format!("ERR Bad arguments for vector similarity algorithm: unknown algorithm {}", algorithm)),
+    };
+
+    let dim = dim.ok_or_else(|| String::from("ERR Missing mandatory parameter: cannot create vector index without specifying DIM"))?;
+    let metric = metric.ok_or_else(|| String::from("ERR Missing mandatory parameter: cannot create vector index without specifying DISTANCE_METRIC"))?;
+
+    Ok((VectorFieldOptions { algorithm, dim, metric }, 2 + count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(command: &str) -> Vec<String> {
+        command.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        // given
+        let args = to_args("idx ON JSON PREFIX 2 doc: item: SCHEMA $.embedding AS vec VECTOR HNSW 8 TYPE FLOAT32 DIM 3 DISTANCE_METRIC COSINE M 8 $.price AS price NUMERIC $.tags AS tags TAG SEPARATOR |");
+
+        // when
+        let definition = IndexDefinition::parse(&args).unwrap();
+        let text_definition = IndexDefinition::parse(&to_args("idx SCHEMA title TEXT WEIGHT 5.0 SORTABLE body TEXT NOSTEM")).unwrap();
+
+        // then
+        assert_eq!(definition.target, IndexTarget::Json);
+        assert_eq!(definition.prefixes, vec![String::from("doc:"), String::from("item:")]);
+        assert!(definition.matches_key("item:1"));
+        assert!(!definition.matches_key("user:1"));
+        assert_eq!(definition.fields.len(), 3);
+        assert_eq!(definition.get_field("vec").unwrap().path, "$.embedding");
+        assert_eq!(definition.get_field("vec").unwrap().field_type, FieldType::Vector(VectorFieldOptions {
+            algorithm: VectorAlgorithm::Hnsw { m: 8, ef_construction: DEFAULT_HNSW_EF_CONSTRUCTION, ef_runtime: DEFAULT_HNSW_EF_RUNTIME },
+            dim: 3,
+            metric: DistanceMetric::Cosine,
+        }));
+        assert_eq!(definition.get_field("tags").unwrap().field_type, FieldType::Tag { separator: '|', case_sensitive: false });
+        assert_eq!(text_definition.target, IndexTarget::Hash);
+        assert_eq!(text_definition.get_field("title").unwrap().field_type, FieldType::Text { weight: 5.0, no_stem: false });
+        assert_eq!(text_definition.get_field("body").unwrap().field_type, FieldType::Text { weight: 1.0, no_stem: true });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(IndexDefinition::parse(&to_args("idx ON HASH")).is_err());
+        assert!(IndexDefinition::parse(&to_args("idx SCHEMA vec VECTOR FLAT 2 TYPE FLOAT32")).is_err());
+        assert!(IndexDefinition::parse(&to_args("idx SCHEMA vec VECTOR FLAT 4 TYPE FLOAT32 DIM 2")).is_err());
+        assert!(IndexDefinition::parse(&to_args("idx SCHEMA vec VECTOR FLAT 6 TYPE FLOAT32 DIM 2 DISTANCE_METRIC L2")).is_ok());
+        assert!(IndexDefinition::parse(&to_args("idx SCHEMA price NUMERIC price TAG")).is_err());
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use crate::json::json_path::{get_at, JsonPath};
+use crate::json::json_value::JsonValue;
+use crate::search::query::{Query, QueryNode};
+use crate::search::schema::{FieldType, IndexDefinition, IndexTarget, SchemaField};
+use crate::search::text_index::TextIndex;
+use crate::search::tokenizer::{is_stop_word, to_term, tokenize};
+use crate::search::vector::distance::parse_vector;
+use crate::search::vector::vector_index::VectorIndex;
+use crate::store::ObjectValue;
+
+/// Secondary index over the hash or JSON keys matching the prefixes of the index definition
+///
+/// Every indexed key gets an internal document id. TEXT fields are tokenized into an inverted index per field,
+/// numeric and tag values are kept per document and checked when filtering, vectors are added to the vector index of their field.
+/// Values which can not be indexed (e.g. a vector with the wrong dimension) are skipped, the key is still indexed.
+///
+/// Queries are evaluated to the matching document ids and their BM25 score (0 for documents matched by non-text expressions).
+
+#[derive(Debug, Clone)]
+struct IndexedDocument {
+    key: String,
+    numbers: HashMap<String, f64>,
+    tags: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub key: String,
+    // BM25 score of the text terms of the query
+    pub score: f64,
+    // distance to the query vector of KNN queries
+    pub distance: Option<f32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    pub definition: IndexDefinition,
+    // parsed JSONPath of every schema field (only for JSON indexes)
+    json_paths: Vec<Option<JsonPath>>,
+    doc_ids: HashMap<String, u64>,
+    documents: HashMap<u64, IndexedDocument>,
+    text_indexes: HashMap<String, TextIndex>,
+    vector_indexes: HashMap<String, VectorIndex>,
+    next_doc_id: u64,
+}
+
+impl SearchIndex {
+    pub fn new(definition: IndexDefinition) -> Result<Self, String> {
+        let mut json_paths = Vec::with_capacity(definition.fields.len());
+        let mut text_indexes = HashMap::new();
+        let mut vector_indexes = HashMap::new();
+
+        for field in definition.fields.iter() {
+            json_paths.push(match definition.target {
+                IndexTarget::Json => Some(JsonPath::parse(&field.path).map_err(|err| format!("Invalid JSONPath `{}`: {}", field.path, err))?),
+                IndexTarget::Hash => None,
+            });
+            match &field.field_type {
+                FieldType::Text { .. } => {
+                    text_indexes.insert(field.name.clone(), TextIndex::new());
+                }
+                FieldType::Vector(options) => {
+                    vector_indexes.insert(field.name.clone(), VectorIndex::new(options));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(SearchIndex {
+            definition,
+            json_paths,
+            doc_ids: HashMap::new(),
+            documents: HashMap::new(),
+            text_indexes,
+            vector_indexes,
+            next_doc_id: 1,
+        })
+    }
+
+    /// Adds the key to the index or updates the indexed values of the key
+    pub fn index_document(&mut self, key: &str, value: &ObjectValue) {
+        self.remove_document(key);
+
+        let is_indexed_type = matches!((self.definition.target, value), (IndexTarget::Hash, ObjectValue::Hash(_)) | (IndexTarget::Json, ObjectValue::Json(_)));
+        if !is_indexed_type {
+            return;
+        }
+
+        let id = self.next_doc_id;
+        self.next_doc_id += 1;
+
+        let mut document = IndexedDocument { key: key.to_string(), numbers: HashMap::new(), tags: HashMap::new() };
+        for (i, field) in self.definition.fields.iter().enumerate() {
+            let field_value = match self.extract_field(i, value) {
+                Some(field_value) => field_value,
+                None => continue,
+            };
+
+            match &field.field_type {
+                FieldType::Text { no_stem, .. } => {
+                    let terms: Vec<String> = to_texts(&field_value).iter()
+                        .flat_map(|text| tokenize(text))
+                        .map(|token| to_term(&token, *no_stem))
+                        .collect();
+                    if let Some(index) = self.text_indexes.get_mut(&field.name) {
+                        index.add(id, &terms);
+                    }
+                }
+                FieldType::Numeric => {
+                    if let Some(number) = to_number(&field_value) {
+                        document.numbers.insert(field.name.clone(), number);
+                    }
+                }
+                FieldType::Tag { .. } => {
+                    document.tags.insert(field.name.clone(), to_tags(&field_value, field));
+                }
+                FieldType::Vector(options) => {
+                    if let (Some(vector), Some(index)) = (to_vector(&field_value, options.dim), self.vector_indexes.get_mut(&field.name)) {
+                        index.insert(id, vector);
+                    }
+                }
+            }
+        }
+
+        self.doc_ids.insert(key.to_string(), id);
+        self.documents.insert(id, document);
+    }
+
+    pub fn remove_document(&mut self, key: &str) {
+        if let Some(id) = self.doc_ids.remove(key) {
+            self.documents.remove(&id);
+            for index in self.text_indexes.values_mut() {
+                index.remove(id);
+            }
+            for index in self.vector_indexes.values_mut() {
+                index.remove(id);
+            }
+        }
+    }
+
+    pub fn num_docs(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn get_keys(&self) -> Vec<String> {
+        self.doc_ids.keys().cloned().collect()
+    }
+
+    /// Returns the value of a field as string. Schema fields are looked up by name, other fields by hash field or JSONPath
+    pub fn get_field_value(&self, value: &ObjectValue, name: &str) -> Option<String> {
+        let field_value = match self.definition.fields.iter().position(|field| field.name == name) {
+            Some(i) => self.extract_field(i, value)?,
+            None => match value {
+                ObjectValue::Hash(hash) => JsonValue::String(hash.get(name)?.clone()),
+                ObjectValue::Json(document) => {
+                    let path = JsonPath::parse(name).ok()?;
+                    let location = path.find(document).into_iter().next()?;
+                    get_at(document, &location)?.clone()
+                }
+                _ => return None,
+            },
+        };
+
+        match field_value {
+            JsonValue::String(text) => Some(text),
+            field_value => Some(field_value.to_json_string()),
+        }
+    }
+
+    /// Returns the matching keys, ordered by distance for KNN queries and by score (and key for equal scores) otherwise
+    pub fn search(&self, query: &Query) -> Result<Vec<SearchResult>, String> {
+        let scores = self.evaluate(&query.root)?;
+
+        let knn = match &query.knn {
+            Some(knn) => knn,
+            None => {
+                let mut results: Vec<SearchResult> = scores.iter()
+                    .filter_map(|(id, score)| self.documents.get(id).map(|document| SearchResult { key: document.key.clone(), score: *score, distance: None }))
+                    .collect();
+                results.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.key.cmp(&b.key)));
+                return Ok(results);
+            }
+        };
+
+        let (index, dim) = match (self.vector_indexes.get(&knn.field), self.definition.get_field(&knn.field).map(|field| &field.field_type)) {
+            (Some(index), Some(FieldType::Vector(options))) => (index, options.dim),
+            _ => return Err(format!("Expected a vector field, got `{}`", knn.field)),
+        };
+        let vector = parse_vector(&knn.vector, dim)
+            .ok_or_else(|| format!("Error parsing vector similarity query: query vector does not match index's expected dimension {}", dim))?;
+
+        let passes = |id: u64| scores.contains_key(&id);
+        let filter: Option<&dyn Fn(u64) -> bool> = if query.root == QueryNode::All { None } else { Some(&passes) };
+        Ok(index.knn(&vector, knn.k, filter).into_iter()
+            .filter_map(|(id, distance)| self.documents.get(&id).map(|document| SearchResult {
+                key: document.key.clone(),
+                score: scores.get(&id).cloned().unwrap_or(0.0),
+                distance: Some(distance),
+            }))
+            .collect())
+    }
+
+    /// Returns the ids of all documents matching the expression and their score
+    fn evaluate(&self, node: &QueryNode) -> Result<HashMap<u64, f64>, String> {
+        match node {
+            QueryNode::All => Ok(self.documents.keys().map(|id| (*id, 0.0)).collect()),
+            QueryNode::Term { field, term, is_prefix } => {
+                // stop words are not indexed, so they do not restrict the result
+                if is_stop_word(term) && !is_prefix {
+                    return self.evaluate(&QueryNode::All);
+                }
+
+                let fields: Vec<&SchemaField> = match field {
+                    Some(name) => vec![self.get_field(name, "TEXT", |field_type| matches!(field_type, FieldType::Text { .. }))?],
+                    None => self.definition.fields.iter().filter(|field| matches!(field.field_type, FieldType::Text { .. })).collect(),
+                };
+
+                let mut scores = HashMap::new();
+                for field in fields {
+                    let (weight, no_stem) = match field.field_type {
+                        FieldType::Text { weight, no_stem } => (weight, no_stem),
+                        _ => continue,
+                    };
+                    // prefixes are matched against the stems, stemming the prefix itself could cut it further
+                    let term = if *is_prefix { term.clone() } else { to_term(term, no_stem) };
+                    if let Some(index) = self.text_indexes.get(&field.name) {
+                        for (id, score) in index.score(&term, *is_prefix) {
+                            *scores.entry(id).or_insert(0.0) += score * weight;
+                        }
+                    }
+                }
+                Ok(scores)
+            }
+            QueryNode::Numeric { field, min, min_exclusive, max, max_exclusive } => {
+                self.get_field(field, "NUMERIC", |field_type| matches!(field_type, FieldType::Numeric))?;
+                Ok(self.filter_documents(|document| document.numbers.get(field).is_some_and(|value| {
+                    let above_min = if *min_exclusive { value > min } else { value >= min };
+                    let below_max = if *max_exclusive { value < max } else { value <= max };
+                    above_min && below_max
+                })))
+            }
+            QueryNode::Tag { field, values } => {
+                let case_sensitive = match self.get_field(field, "TAG", |field_type| matches!(field_type, FieldType::Tag { .. }))?.field_type {
+                    FieldType::Tag { case_sensitive, .. } => case_sensitive,
+                    _ => false,
+                };
+                // tags of case insensitive fields are indexed in lowercase
+                let values: Vec<String> = values.iter().map(|value| if case_sensitive { value.clone() } else { value.to_lowercase() }).collect();
+                Ok(self.filter_documents(|document| document.tags.get(field).is_some_and(|tags| values.iter().any(|value| tags.contains(value)))))
+            }
+            QueryNode::Not(node) => {
+                let excluded = self.evaluate(node)?;
+                Ok(self.documents.keys().filter(|id| !excluded.contains_key(id)).map(|id| (*id, 0.0)).collect())
+            }
+            QueryNode::Intersect(nodes) => {
+                let mut scores: Option<HashMap<u64, f64>> = None;
+                for node in nodes.iter() {
+                    let node_scores = self.evaluate(node)?;
+                    scores = Some(match scores {
+                        None => node_scores,
+                        Some(scores) => scores.into_iter()
+                            .filter_map(|(id, score)| node_scores.get(&id).map(|node_score| (id, score + node_score)))
+                            .collect(),
+                    });
+                }
+                Ok(scores.unwrap_or_default())
+            }
+            QueryNode::Union(nodes) => {
+                let mut scores = HashMap::new();
+                for node in nodes.iter() {
+                    for (id, score) in self.evaluate(node)? {
+                        *scores.entry(id).or_insert(0.0) += score;
+                    }
+                }
+                Ok(scores)
+            }
+        }
+    }
+
+    fn get_field<F: Fn(&FieldType) -> bool>(&self, name: &str, expected_type: &str, is_expected_type: F) -> Result<&SchemaField, String> {
+        match self.definition.get_field(name) {
+            Some(field) if is_expected_type(&field.field_type) => Ok(field),
+            Some(_) => Err(format!("Field `{}` is not a {} field", name, expected_type)),
+            None => Err(format!("Unknown field `{}`", name)),
+        }
+    }
+
+    fn filter_documents<F: Fn(&IndexedDocument) -> bool>(&self, predicate: F) -> HashMap<u64, f64> {
+        self.documents.iter().filter(|(_, document)| predicate(document)).map(|(id, _)| (*id, 0.0)).collect()
+    }
+
+    /// Reads the value of the i-th schema field, hash values are returned as JSON strings
+    fn extract_field(&self, i: usize, value: &ObjectValue) -> Option<JsonValue> {
+        match value {
+            ObjectValue::Hash(hash) => hash.get(&self.definition.fields[i].path).map(|value| JsonValue::String(value.clone())),
+            ObjectValue::Json(document) => self.json_paths[i].as_ref()
+                .and_then(|path| path.find(document).first().and_then(|location| get_at(document, location)).cloned()),
+            _ => None,
+        }
+    }
+}
+
+fn to_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Integer(number) => Some(*number as f64),
+        JsonValue::Float(number) => Some(*number),
+        JsonValue::String(text) => text.trim().parse::<f64>().ok().filter(|number| !number.is_nan()),
+        _ => None,
+    }
+}
+
+/// Text of a TEXT field, JSON arrays of strings are indexed as multiple texts
+fn to_texts(value: &JsonValue) -> Vec<String> {
+    match value {
+        JsonValue::String(text) => vec![text.clone()],
+        JsonValue::Array(values) => values.iter().filter_map(|value| match value {
+            JsonValue::String(text) => Some(text.clone()),
+            _ => None,
+        }).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Splits the value by the separator of the field. Tags of case insensitive fields are stored in lowercase
+fn to_tags(value: &JsonValue, field: &SchemaField) -> Vec<String> {
+    let (separator, case_sensitive) = match field.field_type {
+        FieldType::Tag { separator, case_sensitive } => (separator, case_sensitive),
+        _ => return Vec::new(),
+    };
+
+    let values: Vec<String> = match value {
+        JsonValue::String(text) => text.split(separator).map(|tag| tag.to_string()).collect(),
+        JsonValue::Array(_) => to_texts(value),
+        JsonValue::Bool(value) => vec![value.to_string()],
+        _ => Vec::new(),
+    };
+
+    values.into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| if case_sensitive { tag } else { tag.to_lowercase() })
+        .collect()
+}
+
+fn to_vector(value: &JsonValue, dim: usize) -> Option<Vec<f32>> {
+    match value {
+        JsonValue::String(text) => parse_vector(text, dim),
+        JsonValue::Array(values) => {
+            let vector = values.iter().map(|value| to_number(value).map(|number| number as f32)).collect::<Option<Vec<f32>>>()?;
+            if vector.len() != dim {
+                return None;
+            }
+            Some(vector)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::json_parser::parse_json;
+
+    fn create_index(command: &str) -> SearchIndex {
+        let args: Vec<String> = command.split_whitespace().map(|arg| arg.to_string()).collect();
+        SearchIndex::new(IndexDefinition::parse(&args).unwrap()).unwrap()
+    }
+
+    fn hash(fields: &[(&str, &str)]) -> ObjectValue {
+        ObjectValue::Hash(fields.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect())
+    }
+
+    fn search(index: &SearchIndex, query: &str) -> Vec<String> {
+        index.search(&Query::parse(query, &HashMap::new()).unwrap()).unwrap().into_iter().map(|result| result.key).collect()
+    }
+
+    #[test]
+    fn test_knn_with_filters() {
+        // given
+        let mut index = create_index("idx ON HASH PREFIX 1 doc: SCHEMA vec VECTOR HNSW 6 TYPE FLOAT32 DIM 2 DISTANCE_METRIC L2 price NUMERIC color TAG");
+        index.index_document("doc:1", &hash(&[("vec", "0,0"), ("price", "10"), ("color", "Red")]));
+        index.index_document("doc:2", &hash(&[("vec", "1,1"), ("price", "20"), ("color", "blue")]));
+        index.index_document("doc:3", &hash(&[("vec", "2,2"), ("price", "30"), ("color", "red,blue")]));
+        index.index_document("doc:4", &hash(&[("vec", "3,3"), ("price", "40"), ("color", "green")]));
+        let params = HashMap::new();
+
+        // when
+        let nearest = index.search(&Query::parse("*=>[KNN 2 @vec [1.1,1.1]]", &params).unwrap()).unwrap();
+
+        // then
+        assert_eq!(nearest.iter().map(|result| result.key.as_str()).collect::<Vec<_>>(), vec!["doc:2", "doc:3"]);
+        assert!((nearest[0].distance.unwrap() - 0.02).abs() < 0.0001);
+        assert_eq!(search(&index, "(@color:{red} @price:[(10 +inf])=>[KNN 2 @vec [0,0]]"), vec!["doc:3"]);
+        assert_eq!(search(&index, "-@color:{red}"), vec!["doc:2", "doc:4"]);
+        assert_eq!(search(&index, "@color:{green} | @price:[-inf 10]"), vec!["doc:1", "doc:4"]);
+        assert!(index.search(&Query::parse("@size:[1 2]", &params).unwrap()).is_err());
+        assert!(index.search(&Query::parse("@color:[1 2]", &params).unwrap()).is_err());
+        assert!(index.search(&Query::parse("*=>[KNN 2 @vec [1,2,3]]", &params).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_full_text_search() {
+        // given
+        let mut index = create_index("idx ON HASH PREFIX 1 doc: SCHEMA title TEXT WEIGHT 2 body TEXT code TEXT NOSTEM");
+        index.index_document("doc:1", &hash(&[("title", "Running shoes"), ("body", "Shoes for the road"), ("code", "runs")]));
+        index.index_document("doc:2", &hash(&[("title", "Road bikes"), ("body", "A bike runs faster than running shoes")]));
+        index.index_document("doc:3", &hash(&[("title", "Hiking boots"), ("body", "Boots for the mountains")]));
+
+        // then
+        // the title has a higher weight than the body
+        assert_eq!(search(&index, "run"), vec!["doc:1", "doc:2"]);
+        assert_eq!(search(&index, "road shoes"), vec!["doc:1", "doc:2"]);
+        assert_eq!(search(&index, "@title:(road) | boots"), vec!["doc:3", "doc:2"]);
+        assert_eq!(search(&index, "shoes -@title:running"), vec!["doc:2"]);
+        assert_eq!(search(&index, "mount*"), vec!["doc:3"]);
+        assert_eq!(search(&index, "@code:runs"), vec!["doc:1"]);
+        assert_eq!(search(&index, "@code:run"), Vec::<String>::new());
+        assert_eq!(search(&index, "the boots"), vec!["doc:3"]);
+    }
+
+    #[test]
+    fn test_update_and_remove() {
+        // given
+        let mut index = create_index("idx ON JSON SCHEMA $.embedding AS vec VECTOR FLAT 6 TYPE FLOAT32 DIM 2 DISTANCE_METRIC COSINE $.name AS name TEXT");
+        index.index_document("a", &ObjectValue::Json(parse_json(r#"{"embedding":[1,0],"name":"first"}"#).unwrap()));
+        index.index_document("b", &ObjectValue::Json(parse_json(r#"{"embedding":[0,1],"name":"second"}"#).unwrap()));
+        index.index_document("c", &hash(&[("embedding", "1,0")]));
+
+        // when
+        index.index_document("a", &ObjectValue::Json(parse_json(r#"{"embedding":[-1,0],"name":"updated"}"#).unwrap()));
+        index.remove_document("b");
+
+        // then
+        assert_eq!(search(&index, "*=>[KNN 5 @vec [1,0]]"), vec!["a"]);
+        assert_eq!(search(&index, "first | second"), Vec::<String>::new());
+        assert_eq!(search(&index, "updated"), vec!["a"]);
+        assert_eq!(index.num_docs(), 1);
+        assert_eq!(index.get_field_value(&ObjectValue::Json(parse_json(r#"{"name":"x","n":[1]}"#).unwrap()), "$.n"), Some(String::from("[1]")));
+    }
+}
@@ -0,0 +1,222 @@
+/// Porter stemming algorithm for english words
+/// see: https://tartarus.org/martin/PorterStemmer/def.txt
+///
+/// Reduces inflected words to a common stem (e.g. "connected", "connecting" and "connection" to "connect"),
+/// so that a query for one form of a word also matches the other forms.
+/// Words are expected in lowercase, words with less than 3 characters or non-ascii characters are not changed.
+
+pub fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|byte| byte.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut stemmer = Stemmer { word: word.as_bytes().to_vec() };
+    stemmer.step_1a();
+    stemmer.step_1b();
+    stemmer.step_1c();
+    stemmer.step_2();
+    stemmer.step_3();
+    stemmer.step_4();
+    stemmer.step_5();
+
+    String::from_utf8(stemmer.word).expect("Stem is not valid utf-8")
+}
+
+struct Stemmer {
+    word: Vec<u8>,
+}
+
+impl Stemmer {
+    fn is_consonant(&self, i: usize) -> bool {
+        match self.word[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    /// Number of vowel-consonant sequences within the first `len` characters ([C](VC)^m[V])
+    fn measure(&self, len: usize) -> usize {
+        let mut count = 0;
+        let mut i = 0;
+
+        while i < len && self.is_consonant(i) {
+            i += 1;
+        }
+        while i < len {
+            while i < len && !self.is_consonant(i) {
+                i += 1;
+            }
+            if i >= len {
+                break;
+            }
+            while i < len && self.is_consonant(i) {
+                i += 1;
+            }
+            count += 1;
+        }
+
+        count
+    }
+
+    fn has_vowel(&self, len: usize) -> bool {
+        (0..len).any(|i| !self.is_consonant(i))
+    }
+
+    fn ends_with_double_consonant(&self, len: usize) -> bool {
+        len >= 2 && self.word[len - 1] == self.word[len - 2] && self.is_consonant(len - 1)
+    }
+
+    /// consonant-vowel-consonant, where the last consonant is not w, x or y (e.g. hop, but not hoy)
+    fn ends_with_cvc(&self, len: usize) -> bool {
+        len >= 3
+            && self.is_consonant(len - 3)
+            && !self.is_consonant(len - 2)
+            && self.is_consonant(len - 1)
+            && !matches!(self.word[len - 1], b'w' | b'x' | b'y')
+    }
+
+    fn ends_with(&self, suffix: &str) -> bool {
+        self.word.ends_with(suffix.as_bytes())
+    }
+
+    fn stem_len(&self, suffix: &str) -> usize {
+        self.word.len() - suffix.len()
+    }
+
+    fn replace_suffix(&mut self, suffix: &str, replacement: &str) {
+        let len = self.stem_len(suffix);
+        self.word.truncate(len);
+        self.word.extend_from_slice(replacement.as_bytes());
+    }
+
+    /// Replaces the first matching suffix if the measure of the remaining stem is greater than `min_measure`
+    fn replace_first(&mut self, rules: &[(&str, &str)], min_measure: usize) {
+        for (suffix, replacement) in rules.iter() {
+            if self.ends_with(suffix) {
+                if self.measure(self.stem_len(suffix)) > min_measure {
+                    self.replace_suffix(suffix, replacement);
+                }
+                return;
+            }
+        }
+    }
+
+    fn step_1a(&mut self) {
+        if self.ends_with("sses") {
+            self.replace_suffix("sses", "ss");
+        } else if self.ends_with("ies") {
+            self.replace_suffix("ies", "i");
+        } else if !self.ends_with("ss") && self.ends_with("s") {
+            self.replace_suffix("s", "");
+        }
+    }
+
+    fn step_1b(&mut self) {
+        if self.ends_with("eed") {
+            if self.measure(self.stem_len("eed")) > 0 {
+                self.replace_suffix("eed", "ee");
+            }
+            return;
+        }
+
+        let suffix = if self.ends_with("ed") {
+            "ed"
+        } else if self.ends_with("ing") {
+            "ing"
+        } else {
+            return;
+        };
+
+        if !self.has_vowel(self.stem_len(suffix)) {
+            return;
+        }
+        self.replace_suffix(suffix, "");
+
+        if self.ends_with("at") || self.ends_with("bl") || self.ends_with("iz") {
+            self.word.push(b'e');
+        } else if self.ends_with_double_consonant(self.word.len()) && !matches!(self.word[self.word.len() - 1], b'l' | b's' | b'z') {
+            self.word.pop();
+        } else if self.measure(self.word.len()) == 1 && self.ends_with_cvc(self.word.len()) {
+            self.word.push(b'e');
+        }
+    }
+
+    fn step_1c(&mut self) {
+        if self.ends_with("y") && self.has_vowel(self.word.len() - 1) {
+            self.replace_suffix("y", "i");
+        }
+    }
+
+    fn step_2(&mut self) {
+        self.replace_first(&[
+            ("ational", "ate"), ("tional", "tion"), ("enci", "ence"), ("anci", "ance"), ("izer", "ize"),
+            ("abli", "able"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous"),
+            ("ization", "ize"), ("ation", "ate"), ("ator", "ate"), ("alism", "al"), ("iveness", "ive"),
+            ("fulness", "ful"), ("ousness", "ous"), ("aliti", "al"), ("iviti", "ive"), ("biliti", "ble"),
+        ], 0);
+    }
+
+    fn step_3(&mut self) {
+        self.replace_first(&[
+            ("icate", "ic"), ("ative", ""), ("alize", "al"), ("iciti", "ic"), ("ical", "ic"), ("ful", ""), ("ness", ""),
+        ], 0);
+    }
+
+    fn step_4(&mut self) {
+        let suffixes = [
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent",
+            "ion", "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+
+        // the longest matching suffix wins (e.g. "ement" instead of "ent")
+        let suffix = match suffixes.iter().filter(|suffix| self.ends_with(suffix)).max_by_key(|suffix| suffix.len()) {
+            Some(suffix) => *suffix,
+            None => return,
+        };
+
+        let len = self.stem_len(suffix);
+        if self.measure(len) <= 1 {
+            return;
+        }
+        if suffix == "ion" && !(len > 0 && matches!(self.word[len - 1], b's' | b't')) {
+            return;
+        }
+        self.word.truncate(len);
+    }
+
+    fn step_5(&mut self) {
+        if self.ends_with("e") {
+            let len = self.word.len() - 1;
+            let measure = self.measure(len);
+            if measure > 1 || (measure == 1 && !self.ends_with_cvc(len)) {
+                self.word.pop();
+            }
+        }
+
+        let len = self.word.len();
+        if self.measure(len) > 1 && self.ends_with_double_consonant(len) && self.word[len - 1] == b'l' {
+            self.word.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        let expected = [
+            ("caresses", "caress"), ("ponies", "poni"), ("cats", "cat"), ("agreed", "agre"), ("plastered", "plaster"),
+            ("motoring", "motor"), ("hopping", "hop"), ("filing", "file"), ("happy", "happi"), ("relational", "relat"),
+            ("conditional", "condit"), ("generalization", "gener"), ("hopefulness", "hope"), ("electrical", "electr"),
+            ("adjustment", "adjust"), ("controlling", "control"), ("connected", "connect"), ("connection", "connect"),
+            ("running", "run"), ("is", "is"), ("über", "über"),
+        ];
+
+        for (word, stem_word) in expected {
+            assert_eq!(stem(word), stem_word, "stem of {}", word);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+/// Inverted index of a TEXT field with BM25 scoring
+/// see: https://en.wikipedia.org/wiki/Okapi_BM25
+///
+/// Maps every term to the documents containing it and the number of occurrences (term frequency).
+/// The length of every document is kept to normalize the score by the average document length.
+///
+/// score(term, doc) = idf(term) * tf * (k1 + 1) / (tf + k1 * (1 - b + b * doc_length / avg_doc_length))
+/// idf(term) = ln(1 + (num_docs - doc_freq + 0.5) / (doc_freq + 0.5))
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+#[derive(Debug, Clone)]
+pub struct TextIndex {
+    postings: HashMap<String, HashMap<u64, u32>>,
+    doc_lengths: HashMap<u64, u32>,
+    // distinct terms of every document, used to remove its postings
+    doc_terms: HashMap<u64, Vec<String>>,
+    total_length: u64,
+}
+
+impl TextIndex {
+    pub fn new() -> Self {
+        TextIndex {
+            postings: HashMap::new(),
+            doc_lengths: HashMap::new(),
+            doc_terms: HashMap::new(),
+            total_length: 0,
+        }
+    }
+
+    pub fn add(&mut self, id: u64, terms: &[String]) {
+        self.remove(id);
+
+        for term in terms.iter() {
+            *self.postings.entry(term.clone()).or_default().entry(id).or_insert(0) += 1;
+        }
+        let mut distinct_terms = terms.to_vec();
+        distinct_terms.sort();
+        distinct_terms.dedup();
+
+        self.doc_lengths.insert(id, terms.len() as u32);
+        self.doc_terms.insert(id, distinct_terms);
+        self.total_length += terms.len() as u64;
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        let length = match self.doc_lengths.remove(&id) {
+            Some(length) => length,
+            None => return,
+        };
+        self.total_length -= length as u64;
+
+        for term in self.doc_terms.remove(&id).unwrap_or_default() {
+            if let Some(documents) = self.postings.get_mut(&term) {
+                documents.remove(&id);
+                if documents.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// Returns the BM25 score of all documents containing the term (or a term starting with it for prefix queries)
+    pub fn score(&self, term: &str, is_prefix: bool) -> HashMap<u64, f64> {
+        let mut scores = HashMap::new();
+        if self.doc_lengths.is_empty() {
+            return scores;
+        }
+
+        let num_docs = self.doc_lengths.len() as f64;
+        let avg_length = (self.total_length as f64 / num_docs).max(1.0);
+
+        let postings: Vec<&HashMap<u64, u32>> = if is_prefix {
+            self.postings.iter().filter(|(indexed_term, _)| indexed_term.starts_with(term)).map(|(_, documents)| documents).collect()
+        } else {
+            self.postings.get(term).into_iter().collect()
+        };
+
+        for documents in postings {
+            let doc_freq = documents.len() as f64;
+            let idf = (1.0 + (num_docs - doc_freq + 0.5) / (doc_freq + 0.5)).ln();
+
+            for (id, frequency) in documents.iter() {
+                let frequency = *frequency as f64;
+                let length = *self.doc_lengths.get(id).unwrap_or(&0) as f64;
+                let score = idf * frequency * (K1 + 1.0) / (frequency + K1 * (1.0 - B + B * length / avg_length));
+                *scores.entry(*id).or_insert(0.0) += score;
+            }
+        }
+
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|term| term.to_string()).collect()
+    }
+
+    #[test]
+    fn test_score() {
+        // given
+        let mut index = TextIndex::new();
+        index.add(1, &terms("red apple"));
+        index.add(2, &terms("red red red car"));
+        index.add(3, &terms("green apple tree with a long description"));
+
+        // when
+        let red = index.score("red", false);
+        let apple = index.score("apple", false);
+        let prefix = index.score("app", true);
+
+        // then
+        assert_eq!(red.len(), 2);
+        assert!(red[&2] > red[&1]);
+        // shorter documents score higher for the same term frequency
+        assert!(apple[&1] > apple[&3]);
+        assert_eq!(prefix, apple);
+        assert!(index.score("app", false).is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        // given
+        let mut index = TextIndex::new();
+        index.add(1, &terms("red apple"));
+        index.add(2, &terms("green apple"));
+
+        // when
+        index.remove(1);
+        index.add(2, &terms("yellow banana"));
+
+        // then
+        assert!(index.score("red", false).is_empty());
+        assert!(index.score("apple", false).is_empty());
+        assert_eq!(index.score("banana", false).len(), 1);
+        assert_eq!(index.total_length, 2);
+    }
+}
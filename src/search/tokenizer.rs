@@ -0,0 +1,51 @@
+use crate::search::stemmer::stem;
+
+/// Tokenization of TEXT fields and query terms
+/// see: https://redis.io/docs/interact/search-and-query/advanced-concepts/escaping/
+///
+/// Text is split at whitespace and punctuation and converted to lowercase. Stop words are dropped,
+/// the remaining tokens are stemmed unless the field was created with NOSTEM.
+
+/// Default stop words of redis
+pub const STOP_WORDS: [&str; 33] = [
+    "a", "is", "the", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into",
+    "it", "no", "not", "of", "on", "or", "such", "that", "their", "then", "there", "these",
+    "they", "this", "to", "was", "will", "with",
+];
+
+pub fn is_stop_word(token: &str) -> bool {
+    STOP_WORDS.contains(&token)
+}
+
+/// Splits the text into lowercase tokens, stop words are removed
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|char: char| !(char.is_alphanumeric() || char == '_'))
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .filter(|token| !is_stop_word(token))
+        .collect()
+}
+
+/// Converts a token to the term stored in the inverted index
+pub fn to_term(token: &str, no_stem: bool) -> String {
+    if no_stem {
+        return token.to_string();
+    }
+    stem(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        // when
+        let tokens = tokenize("The quick-brown fox, jumps over THE lazy_dog!");
+
+        // then
+        assert_eq!(tokens, vec!["quick", "brown", "fox", "jumps", "over", "lazy_dog"]);
+        assert_eq!(to_term("jumps", false), "jump");
+        assert_eq!(to_term("jumps", true), "jumps");
+    }
+}
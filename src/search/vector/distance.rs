@@ -0,0 +1,81 @@
+/// Distance metrics of vector fields
+/// see: https://redis.io/docs/interact/search-and-query/advanced-concepts/vectors/#distance-metrics
+///
+/// All metrics return smaller values for more similar vectors (like redis does):
+/// L2      squared euclidean distance
+/// IP      1 - inner product
+/// COSINE  1 - cosine similarity
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceMetric {
+    L2,
+    InnerProduct,
+    Cosine,
+}
+
+impl DistanceMetric {
+    pub fn parse(name: &str) -> Option<DistanceMetric> {
+        match name.to_uppercase().as_str() {
+            "L2" => Some(DistanceMetric::L2),
+            "IP" => Some(DistanceMetric::InnerProduct),
+            "COSINE" => Some(DistanceMetric::Cosine),
+            _ => None,
+        }
+    }
+
+    pub fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            DistanceMetric::L2 => a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum(),
+            DistanceMetric::InnerProduct => 1.0 - dot_product(a, b),
+            DistanceMetric::Cosine => {
+                let norm = (dot_product(a, a) * dot_product(b, b)).sqrt();
+                if norm == 0.0 {
+                    return 1.0;
+                }
+                1.0 - dot_product(a, b) / norm
+            }
+        }
+    }
+}
+
+fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Parses a vector stored as comma separated list (`0.1,0.2`) or JSON array (`[0.1, 0.2]`)
+pub fn parse_vector(text: &str, dim: usize) -> Option<Vec<f32>> {
+    let text = text.trim();
+    let text = text.strip_prefix('[').and_then(|text| text.strip_suffix(']')).unwrap_or(text);
+
+    let vector = text.split(',').map(|value| value.trim().parse::<f32>().ok().filter(|value| value.is_finite())).collect::<Option<Vec<f32>>>()?;
+    if vector.len() != dim {
+        return None;
+    }
+    Some(vector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        // given
+        let a = [1.0, 0.0];
+        let b = [0.0, 2.0];
+
+        // then
+        assert_eq!(DistanceMetric::L2.distance(&a, &b), 5.0);
+        assert_eq!(DistanceMetric::InnerProduct.distance(&a, &b), 1.0);
+        assert_eq!(DistanceMetric::Cosine.distance(&a, &b), 1.0);
+        assert_eq!(DistanceMetric::Cosine.distance(&a, &[3.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_parse_vector() {
+        assert_eq!(parse_vector("1,2.5,-3", 3), Some(vec![1.0, 2.5, -3.0]));
+        assert_eq!(parse_vector("[1, 2.5, -3]", 3), Some(vec![1.0, 2.5, -3.0]));
+        assert_eq!(parse_vector("1,2", 3), None);
+        assert_eq!(parse_vector("1,a,3", 3), None);
+    }
+}
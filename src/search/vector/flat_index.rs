@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use crate::search::vector::distance::DistanceMetric;
+
+/// Brute force vector index (FLAT algorithm)
+///
+/// Compares the query with every stored vector, so results are exact but queries are O(n).
+
+#[derive(Debug, Clone)]
+pub struct FlatIndex {
+    metric: DistanceMetric,
+    vectors: HashMap<u64, Vec<f32>>,
+}
+
+impl FlatIndex {
+    pub fn new(metric: DistanceMetric) -> Self {
+        FlatIndex {
+            metric,
+            vectors: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, id: u64, vector: Vec<f32>) {
+        self.vectors.insert(id, vector);
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.vectors.remove(&id);
+    }
+
+    /// Returns the ids and distances of the k nearest vectors which pass the filter, ordered by distance
+    pub fn knn(&self, query: &[f32], k: usize, filter: &dyn Fn(u64) -> bool) -> Vec<(u64, f32)> {
+        let candidates = self.vectors.iter()
+            .filter(|(id, _)| filter(**id))
+            .map(|(id, vector)| (*id, self.metric.distance(query, vector)));
+        nearest(candidates, k)
+    }
+}
+
+/// Selects the k nearest candidates, ties are ordered by id to get stable results
+pub fn nearest<I: Iterator<Item=(u64, f32)>>(candidates: I, k: usize) -> Vec<(u64, f32)> {
+    let mut candidates: Vec<(u64, f32)> = candidates.collect();
+    candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    candidates.truncate(k);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knn() {
+        // given
+        let mut index = FlatIndex::new(DistanceMetric::L2);
+        index.insert(1, vec![0.0, 0.0]);
+        index.insert(2, vec![1.0, 1.0]);
+        index.insert(3, vec![5.0, 5.0]);
+        index.insert(4, vec![0.5, 0.0]);
+        index.remove(4);
+
+        // when
+        let all = index.knn(&[0.9, 0.9], 2, &|_| true);
+        let filtered = index.knn(&[0.9, 0.9], 2, &|id| id != 2);
+        let remaining = index.knn(&[0.9, 0.9], 10, &|_| true);
+
+        // then
+        assert_eq!(all.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(filtered.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(remaining.len(), 3);
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use rand::Rng;
+
+use crate::search::vector::distance::DistanceMetric;
+use crate::search::vector::flat_index::nearest;
+
+/// Hierarchical Navigable Small World graph (HNSW algorithm)
+/// see: https://arxiv.org/abs/1603.09320
+///
+/// Every vector is a node of a multi-layer graph. Higher layers contain exponentially fewer nodes and act as
+/// express lanes: a search starts at the entry point on the top layer, greedily moves to the closest node and
+/// descends until it reaches layer 0, where the `ef` closest nodes are collected by a best-first search.
+///
+/// M               max number of neighbors per node and layer (2 * M on layer 0)
+/// EF_CONSTRUCTION size of the candidate list when inserting nodes (higher = better graph, slower inserts)
+/// EF_RUNTIME      size of the candidate list when searching (higher = better recall, slower queries)
+///
+/// Removed nodes are unlinked from their neighbors and the neighbors are reconnected with each other, so no tombstones are kept.
+/// Edges of other nodes pointing to a removed node are skipped when searching and dropped first when pruning neighbors.
+/// Filtered queries traverse the whole graph but only collect matching nodes. If the filter is too selective
+/// to find k results this way, the matching nodes are compared with the query directly.
+
+#[derive(Debug, Clone)]
+struct HnswNode {
+    vector: Vec<f32>,
+    // neighbors per layer, index 0 is the bottom layer
+    neighbors: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    metric: DistanceMetric,
+    m: usize,
+    ef_construction: usize,
+    ef_runtime: usize,
+    level_multiplier: f64,
+    nodes: HashMap<u64, HnswNode>,
+    entry_point: Option<u64>,
+}
+
+/// Node id and distance to the query, ordered by distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: u64,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.id.cmp(&other.id))
+    }
+}
+
+impl HnswIndex {
+    pub fn new(metric: DistanceMetric, m: usize, ef_construction: usize, ef_runtime: usize) -> Self {
+        let m = m.max(2);
+        HnswIndex {
+            metric,
+            m,
+            ef_construction,
+            ef_runtime,
+            level_multiplier: 1.0 / (m as f64).ln(),
+            nodes: HashMap::new(),
+            entry_point: None,
+        }
+    }
+
+    pub fn insert(&mut self, id: u64, vector: Vec<f32>) {
+        if self.nodes.contains_key(&id) {
+            self.remove(id);
+        }
+
+        let level = self.random_level();
+        self.nodes.insert(id, HnswNode { vector: vector.clone(), neighbors: vec![Vec::new(); level + 1] });
+
+        let entry_point = match self.entry_point {
+            Some(entry_point) => entry_point,
+            None => {
+                self.entry_point = Some(id);
+                return;
+            }
+        };
+        let top_level = self.get_level(entry_point);
+
+        // greedy search on the layers above the level of the new node
+        let mut entry_points = vec![entry_point];
+        for layer in (level + 1..=top_level).rev() {
+            entry_points = self.search_layer(&vector, &entry_points, 1, layer, None).iter().map(|candidate| candidate.id).collect();
+        }
+
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(&vector, &entry_points, self.ef_construction, layer, None);
+            let neighbors: Vec<u64> = candidates.iter().take(self.m).map(|candidate| candidate.id).collect();
+
+            for neighbor in neighbors.iter() {
+                self.connect(*neighbor, id, layer);
+            }
+            self.nodes.get_mut(&id).expect("Node not found").neighbors[layer] = neighbors;
+            entry_points = candidates.iter().map(|candidate| candidate.id).collect();
+        }
+
+        if level > top_level {
+            self.entry_point = Some(id);
+        }
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        let node = match self.nodes.remove(&id) {
+            Some(node) => node,
+            None => return,
+        };
+
+        for (layer, neighbors) in node.neighbors.iter().enumerate() {
+            for neighbor in neighbors.iter() {
+                if let Some(neighbor_node) = self.nodes.get_mut(neighbor) {
+                    if let Some(neighbor_layer) = neighbor_node.neighbors.get_mut(layer) {
+                        neighbor_layer.retain(|other| *other != id);
+                    }
+                }
+            }
+
+            // reconnect the former neighbors, so that the graph stays navigable
+            for neighbor in neighbors.iter() {
+                for other in neighbors.iter() {
+                    if neighbor != other && self.nodes.contains_key(neighbor) && self.nodes.contains_key(other) {
+                        self.connect(*neighbor, *other, layer);
+                    }
+                }
+            }
+        }
+
+        if self.entry_point == Some(id) {
+            self.entry_point = self.nodes.iter().max_by_key(|(id, node)| (node.neighbors.len(), Reverse(**id))).map(|(id, _)| *id);
+        }
+    }
+
+    /// Returns the ids and distances of the (approximately) k nearest vectors which pass the filter, ordered by distance
+    pub fn knn(&self, query: &[f32], k: usize, filter: Option<&dyn Fn(u64) -> bool>) -> Vec<(u64, f32)> {
+        let entry_point = match self.entry_point {
+            Some(entry_point) if k > 0 => entry_point,
+            _ => return Vec::new(),
+        };
+
+        let mut entry_points = vec![entry_point];
+        for layer in (1..=self.get_level(entry_point)).rev() {
+            entry_points = self.search_layer(query, &entry_points, 1, layer, None).iter().map(|candidate| candidate.id).collect();
+        }
+
+        let results = self.search_layer(query, &entry_points, self.ef_runtime.max(k), 0, filter);
+        if results.len() < k {
+            if let Some(filter) = filter {
+                let candidates = self.nodes.iter()
+                    .filter(|(id, _)| filter(**id))
+                    .map(|(id, node)| (*id, self.metric.distance(query, &node.vector)));
+                return nearest(candidates, k);
+            }
+        }
+
+        results.into_iter().take(k).map(|candidate| (candidate.id, candidate.distance)).collect()
+    }
+
+    /// Best-first search on one layer, returns up to ef nodes passing the filter ordered by distance
+    fn search_layer(&self, query: &[f32], entry_points: &[u64], ef: usize, layer: usize, filter: Option<&dyn Fn(u64) -> bool>) -> Vec<Candidate> {
+        let passes = |id: u64| filter.is_none_or(|filter| filter(id));
+
+        let mut visited: HashSet<u64> = HashSet::new();
+        // min-heap of nodes to visit and max-heap of the best results so far
+        let mut candidates: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for entry_point in entry_points.iter() {
+            if !visited.insert(*entry_point) {
+                continue;
+            }
+            let candidate = Candidate { distance: self.get_distance(query, *entry_point), id: *entry_point };
+            candidates.push(Reverse(candidate));
+            if passes(*entry_point) {
+                results.push(candidate);
+            }
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|furthest| current.distance > furthest.distance) {
+                break;
+            }
+
+            let neighbors = match self.nodes.get(&current.id).and_then(|node| node.neighbors.get(layer)) {
+                Some(neighbors) => neighbors,
+                None => continue,
+            };
+
+            for neighbor in neighbors.iter() {
+                // edges are directed, so other nodes can still point to removed nodes
+                if !visited.insert(*neighbor) || !self.nodes.contains_key(neighbor) {
+                    continue;
+                }
+
+                let candidate = Candidate { distance: self.get_distance(query, *neighbor), id: *neighbor };
+                if results.len() < ef || results.peek().is_some_and(|furthest| candidate.distance < furthest.distance) {
+                    candidates.push(Reverse(candidate));
+                    if passes(*neighbor) {
+                        results.push(candidate);
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Adds an edge from node to neighbor and keeps only the closest neighbors if the node has too many
+    fn connect(&mut self, node_id: u64, neighbor: u64, layer: usize) {
+        let max_neighbors = if layer == 0 { self.m * 2 } else { self.m };
+
+        let node = match self.nodes.get(&node_id) {
+            Some(node) if layer < node.neighbors.len() && !node.neighbors[layer].contains(&neighbor) => node,
+            _ => return,
+        };
+
+        let mut neighbors = node.neighbors[layer].clone();
+        neighbors.push(neighbor);
+        if neighbors.len() > max_neighbors {
+            let vector = &node.vector;
+            let mut ranked: Vec<Candidate> = neighbors.iter().map(|id| Candidate { distance: self.get_distance(vector, *id), id: *id }).collect();
+            ranked.sort();
+            neighbors = ranked.into_iter().take(max_neighbors).map(|candidate| candidate.id).collect();
+        }
+
+        self.nodes.get_mut(&node_id).expect("Node not found").neighbors[layer] = neighbors;
+    }
+
+    fn get_distance(&self, query: &[f32], id: u64) -> f32 {
+        match self.nodes.get(&id) {
+            Some(node) => self.metric.distance(query, &node.vector),
+            None => f32::INFINITY,
+        }
+    }
+
+    fn get_level(&self, id: u64) -> usize {
+        self.nodes.get(&id).map_or(0, |node| node.neighbors.len() - 1)
+    }
+
+    fn random_level(&self) -> usize {
+        let random: f64 = rand::thread_rng().gen_range(f64::EPSILON..1.0);
+        (-random.ln() * self.level_multiplier).floor() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::vector::flat_index::FlatIndex;
+
+    fn random_vectors(count: u64, dim: usize) -> Vec<(u64, Vec<f32>)> {
+        let mut rng = rand::thread_rng();
+        (0..count).map(|id| (id, (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())).collect()
+    }
+
+    #[test]
+    fn test_knn_recall() {
+        // given
+        let mut hnsw = HnswIndex::new(DistanceMetric::L2, 16, 200, 50);
+        let mut flat = FlatIndex::new(DistanceMetric::L2);
+        for (id, vector) in random_vectors(500, 8) {
+            hnsw.insert(id, vector.clone());
+            flat.insert(id, vector);
+        }
+
+        // when
+        let mut found = 0;
+        for (_, query) in random_vectors(20, 8) {
+            let expected: HashSet<u64> = flat.knn(&query, 10, &|_| true).iter().map(|(id, _)| *id).collect();
+            found += hnsw.knn(&query, 10, None).iter().filter(|(id, _)| expected.contains(id)).count();
+        }
+
+        // then
+        assert!(found >= 180, "recall too low: {}/200", found);
+    }
+
+    #[test]
+    fn test_remove_and_filter() {
+        // given
+        let mut hnsw = HnswIndex::new(DistanceMetric::L2, 4, 20, 10);
+        for (id, vector) in random_vectors(100, 4) {
+            hnsw.insert(id, vector);
+        }
+
+        // when
+        for id in 0..50 {
+            hnsw.remove(id);
+        }
+        let all = hnsw.knn(&[0.0, 0.0, 0.0, 0.0], 100, None);
+        let filtered = hnsw.knn(&[0.0, 0.0, 0.0, 0.0], 5, Some(&|id| id.is_multiple_of(10)));
+
+        // then
+        assert_eq!(hnsw.nodes.len(), 50);
+        assert!(all.iter().all(|(id, _)| *id >= 50));
+        assert!(all.len() >= 45);
+        assert_eq!(filtered.iter().map(|(id, _)| *id).collect::<HashSet<_>>(), HashSet::from([50, 60, 70, 80, 90]));
+    }
+}
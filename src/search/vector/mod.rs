@@ -0,0 +1,4 @@
+pub mod distance;
+pub mod flat_index;
+pub mod hnsw_index;
+pub mod vector_index;
@@ -0,0 +1,42 @@
+use crate::search::schema::{VectorAlgorithm, VectorFieldOptions};
+use crate::search::vector::flat_index::FlatIndex;
+use crate::search::vector::hnsw_index::HnswIndex;
+
+/// Vector index of a VECTOR field, uses the algorithm configured in the schema
+
+#[derive(Debug, Clone)]
+pub enum VectorIndex {
+    Flat(FlatIndex),
+    Hnsw(HnswIndex),
+}
+
+impl VectorIndex {
+    pub fn new(options: &VectorFieldOptions) -> Self {
+        match options.algorithm {
+            VectorAlgorithm::Flat => VectorIndex::Flat(FlatIndex::new(options.metric)),
+            VectorAlgorithm::Hnsw { m, ef_construction, ef_runtime } => VectorIndex::Hnsw(HnswIndex::new(options.metric, m, ef_construction, ef_runtime)),
+        }
+    }
+
+    pub fn insert(&mut self, id: u64, vector: Vec<f32>) {
+        match self {
+            VectorIndex::Flat(index) => index.insert(id, vector),
+            VectorIndex::Hnsw(index) => index.insert(id, vector),
+        }
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        match self {
+            VectorIndex::Flat(index) => index.remove(id),
+            VectorIndex::Hnsw(index) => index.remove(id),
+        }
+    }
+
+    /// Returns the ids and distances of the k nearest vectors, optionally only of ids which pass the filter
+    pub fn knn(&self, query: &[f32], k: usize, filter: Option<&dyn Fn(u64) -> bool>) -> Vec<(u64, f32)> {
+        match self {
+            VectorIndex::Flat(index) => index.knn(query, k, filter.unwrap_or(&|_| true)),
+            VectorIndex::Hnsw(index) => index.knn(query, k, filter),
+        }
+    }
+}
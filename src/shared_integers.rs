@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+
+use crate::store::ObjectValue;
+use crate::string_value::StringValue;
+
+/// Shared objects for the integers 0-9999, see OBJ_SHARED_INTEGERS in Redis
+///
+/// Keys holding a small integer (e.g. flags and counters) reference the same immutable object instead of allocating
+/// their own. Modifying a shared value copies it first (copy-on-write), see `StoreObject::get_value_mut`.
+/// The access time of a key is stored next to the value reference, so sharing works with every eviction policy.
+
+pub const SHARED_INTEGERS: i64 = 10000;
+
+lazy_static! {
+    static ref SHARED_INTEGER_OBJECTS: Vec<Arc<ObjectValue>> = (0..SHARED_INTEGERS)
+        .map(|int| Arc::new(ObjectValue::String(StringValue::Int(int))))
+        .collect();
+}
+
+/// Allocates the shared objects on startup, so the first write of a small integer does not pay for it
+pub fn create_shared_integers() {
+    lazy_static::initialize(&SHARED_INTEGER_OBJECTS);
+}
+
+/// Returns the shared object for integers in range, otherwise allocates a new object
+pub fn create_object_value(value: ObjectValue) -> Arc<ObjectValue> {
+    if let ObjectValue::String(StringValue::Int(int)) = value {
+        if (0..SHARED_INTEGERS).contains(&int) {
+            return SHARED_INTEGER_OBJECTS[int as usize].clone();
+        }
+    }
+
+    Arc::new(value)
+}
+
+pub fn is_shared(value: &Arc<ObjectValue>) -> bool {
+    match value.as_ref() {
+        ObjectValue::String(StringValue::Int(int)) if (0..SHARED_INTEGERS).contains(int) => {
+            Arc::ptr_eq(value, &SHARED_INTEGER_OBJECTS[*int as usize])
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_object_value() {
+        // given
+        let first = create_object_value(ObjectValue::String(StringValue::Int(42)));
+        let second = create_object_value(ObjectValue::String(StringValue::Int(42)));
+        let out_of_range = create_object_value(ObjectValue::String(StringValue::Int(SHARED_INTEGERS)));
+
+        // then
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(is_shared(&first));
+        assert!(!is_shared(&out_of_range));
+    }
+
+    #[test]
+    fn test_copy_on_write() {
+        // given
+        let mut value = create_object_value(ObjectValue::String(StringValue::Int(7)));
+
+        // when
+        if let ObjectValue::String(string) = Arc::make_mut(&mut value) {
+            string.append("1");
+        }
+
+        // then
+        assert!(!is_shared(&value));
+        assert_eq!(create_object_value(ObjectValue::String(StringValue::Int(7))).as_ref(), &ObjectValue::String(StringValue::Int(7)));
+    }
+}
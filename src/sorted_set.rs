@@ -0,0 +1,162 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
+
+use crate::store::estimate_from_samples;
+
+/// Sorted set implementation
+/// see: https://redis.io/docs/data-types/sorted-sets/
+///
+/// Every member is unique and associated with a floating point score.
+/// Members are ordered by score and members with the same score are ordered lexicographically.
+///
+/// Architecture
+/// Redis uses a hash table (member -> score) together with a skip list (ordered by score) to have O(1) score lookups
+/// and O(log n) range queries. We use the same idea, but replace the skip list with a BTreeSet.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortedSet {
+    scores: HashMap<String, f64>,
+    ordered: BTreeSet<SortedSetEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SortedSetEntry {
+    score: f64,
+    member: String,
+}
+
+impl Eq for SortedSetEntry {}
+
+impl PartialOrd for SortedSetEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortedSetEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.total_cmp(&other.score).then_with(|| self.member.cmp(&other.member))
+    }
+}
+
+impl SortedSet {
+    pub fn new() -> Self {
+        SortedSet {
+            scores: HashMap::new(),
+            ordered: BTreeSet::new(),
+        }
+    }
+
+    /// Inserts the member or updates its score. Returns true if the member was newly added
+    pub fn insert(&mut self, member: &str, score: f64) -> bool {
+        match self.scores.insert(member.to_string(), score) {
+            Some(old_score) => {
+                self.ordered.remove(&SortedSetEntry { score: old_score, member: member.to_string() });
+                self.ordered.insert(SortedSetEntry { score, member: member.to_string() });
+                false
+            }
+            None => {
+                self.ordered.insert(SortedSetEntry { score, member: member.to_string() });
+                true
+            }
+        }
+    }
+
+    pub fn remove(&mut self, member: &str) -> Option<f64> {
+        let score = self.scores.remove(member)?;
+        self.ordered.remove(&SortedSetEntry { score, member: member.to_string() });
+        Some(score)
+    }
+
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Estimated number of bytes used by the sorted set, every member is stored twice (hash table and tree).
+    /// The size of the members is extrapolated from samples members, 0 adds up all members
+    pub fn get_memory_usage(&self, samples: usize) -> u64 {
+        let entry_overhead = std::mem::size_of::<(String, f64)>() + std::mem::size_of::<SortedSetEntry>();
+        let member_sizes = self.scores.keys().map(|member| (member.capacity() * 2 + entry_overhead) as u64);
+        estimate_from_samples(member_sizes, self.len(), samples) + std::mem::size_of::<SortedSet>() as u64
+    }
+
+    /// Iterates over all (member, score) pairs in ascending score order
+    pub fn iter(&self) -> impl Iterator<Item=(&str, f64)> {
+        self.ordered.iter().map(|entry| (entry.member.as_str(), entry.score))
+    }
+
+    /// Iterates over all (member, score) pairs with min <= score < max in ascending score order
+    pub fn range_by_score(&self, min: f64, max: f64) -> impl Iterator<Item=(&str, f64)> {
+        let lower = SortedSetEntry { score: min, member: String::new() };
+        self.ordered
+            .range((Bound::Included(lower), Bound::Unbounded))
+            .take_while(move |entry| entry.score < max)
+            .map(|entry| (entry.member.as_str(), entry.score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_update_score() {
+        // given
+        let mut sorted_set = SortedSet::new();
+
+        // when
+        let added_a = sorted_set.insert("a", 3.0);
+        let added_b = sorted_set.insert("b", 1.0);
+        let added_a_again = sorted_set.insert("a", 0.5);
+
+        // then
+        assert!(added_a);
+        assert!(added_b);
+        assert!(!added_a_again);
+        assert_eq!(sorted_set.len(), 2);
+        assert_eq!(sorted_set.score("a"), Some(0.5));
+        assert_eq!(sorted_set.iter().collect::<Vec<_>>(), vec![("a", 0.5), ("b", 1.0)]);
+    }
+
+    #[test]
+    fn test_remove() {
+        // given
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 1.0);
+        sorted_set.insert("b", 2.0);
+
+        // when
+        let removed = sorted_set.remove("a");
+        let not_existing = sorted_set.remove("c");
+
+        // then
+        assert_eq!(removed, Some(1.0));
+        assert_eq!(not_existing, None);
+        assert_eq!(sorted_set.iter().collect::<Vec<_>>(), vec![("b", 2.0)]);
+    }
+
+    #[test]
+    fn test_range_by_score() {
+        // given
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 1.0);
+        sorted_set.insert("b", 2.0);
+        sorted_set.insert("c", 2.0);
+        sorted_set.insert("d", 3.0);
+
+        // when
+        let range = sorted_set.range_by_score(2.0, 3.0).collect::<Vec<_>>();
+
+        // then
+        assert_eq!(range, vec![("b", 2.0), ("c", 2.0)]);
+    }
+}
@@ -5,7 +5,19 @@ pub struct KeyspaceStatistics {
     pub number_of_keys: u64,
 }
 
+/// Statistics of the key expiration, see the Stats section of INFO
+pub struct ExpirationStatistics {
+    // keys removed because they expired, by the active expiration cycle or when they were accessed
+    pub expired_keys: u64,
+    // moving average of the share of expired keys among the keys sampled by the active expiration cycle
+    pub expired_stale_perc: f64,
+}
+
 lazy_static! {
+    pub static ref EXPIRATION_STATISTICS: Arc<Mutex<ExpirationStatistics>> = {
+        Arc::new(Mutex::new(ExpirationStatistics { expired_keys: 0, expired_stale_perc: 0.0 }))
+    };
+
     pub static ref KEYSPACE_STATISTICS: Arc<Mutex<Vec<KeyspaceStatistics>>> = {
         let mut stats = Vec::new();
         stats.push(KeyspaceStatistics { number_of_keys: 0 });
@@ -13,10 +25,29 @@ lazy_static! {
     };
 }
 
+/// Updates the number of keys of the database, statistics of databases which were not used so far are added
 pub fn update_keyspace_statistics(keyspace_id: usize, number_of_keys: u64) {
     if let Ok(mut stats) = KEYSPACE_STATISTICS.lock() {
-        if let Some(keyspace_stats) = stats.get_mut(keyspace_id) {
-            keyspace_stats.number_of_keys = number_of_keys;
+        while stats.len() <= keyspace_id {
+            stats.push(KeyspaceStatistics { number_of_keys: 0 });
+        }
+        stats[keyspace_id].number_of_keys = number_of_keys;
+    }
+}
+
+pub fn increment_expired_keys(count: u64) {
+    if let Ok(mut stats) = EXPIRATION_STATISTICS.lock() {
+        stats.expired_keys += count;
+    }
+}
+
+/// Adds the stale share of the last active expiration cycle to the moving average, which is returned
+pub fn update_expired_stale_perc(stale_perc: f64) -> f64 {
+    match EXPIRATION_STATISTICS.lock() {
+        Ok(mut stats) => {
+            stats.expired_stale_perc = stale_perc * 0.05 + stats.expired_stale_perc * 0.95;
+            stats.expired_stale_perc
         }
+        Err(_) => stale_perc,
     }
 }
@@ -1,17 +1,55 @@
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-use crate::eviction::all_keys_lru_eviction_strategy::AllKeysLRUEvictionStrategy;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::eviction::eviction::{EvictionManager, EvictionManagerConfiguration};
-use crate::object_type_encoding::{get_type, OBJ_ENCODING_EMBSTR, OBJ_ENCODING_INT, OBJ_ENCODING_RAW, OBJ_TYPE_STRING};
+use crate::active_expiration::DEFAULT_ACTIVE_EXPIRE_EFFORT;
+use crate::allocator::{get_allocated_bytes, get_startup_allocated_bytes};
+use crate::client::ClientRegistry;
+use crate::dict::Dict;
+use crate::expiry_index::ExpiryIndex;
+use crate::eviction::eviction::{DEFAULT_MAXMEMORY_SAMPLES, EvictionManager, EvictionManagerConfiguration, EvictionPolicy};
+use crate::eviction::lfu_eviction_strategy;
+use crate::eviction::lfu_eviction_strategy::LFUConfiguration;
+use crate::eviction::lru_eviction_strategy;
+use crate::eviction::lru_eviction_strategy::get_lru_clock;
+use crate::glob::glob_match;
+use crate::object_type_encoding::{get_type, OBJ_TYPE_STRING};
+#[cfg(test)]
+use crate::object_type_encoding::{OBJ_ENCODING_EMBSTR, OBJ_ENCODING_HT, OBJ_ENCODING_INT, OBJ_ENCODING_RAW, OBJ_TYPE_HASH, OBJ_TYPE_TIMESERIES};
+#[cfg(test)]
+use crate::timeseries::time_series::DuplicatePolicy;
+use crate::hash::hash_helper::get_hash_memory_usage;
+use crate::json::json_value::JsonValue;
+use crate::probabilistic::bloom_filter::BloomFilter;
+use crate::probabilistic::count_min_sketch::CountMinSketch;
+use crate::probabilistic::cuckoo_filter::CuckooFilter;
+use crate::probabilistic::top_k::TopK;
 use crate::resp::DataType;
-use crate::stats::update_keyspace_statistics;
+use crate::search::index_manager::IndexManager;
+use crate::search::query::Query;
+use crate::search::schema::IndexDefinition;
+use crate::search::search_index::{SearchIndex, SearchResult};
+use crate::shared_integers::{create_object_value, is_shared};
+use crate::sorted_set::SortedSet;
+use crate::stats::{increment_expired_keys, update_keyspace_statistics};
+use crate::string_value::StringValue;
+use crate::timeseries::time_series::TimeSeries;
 
+/// Every value is allocated with the size of the largest variant, so large structures are boxed to keep
+/// strings, which are the most common values, small
 #[derive(Debug)]
 #[derive(PartialEq)]
 #[derive(Clone)]
 pub enum ObjectValue {
-    String(String),
+    String(StringValue),
+    SortedSet(Box<SortedSet>),
+    Json(JsonValue),
+    BloomFilter(BloomFilter),
+    CuckooFilter(Box<CuckooFilter>),
+    CountMinSketch(CountMinSketch),
+    TopK(Box<TopK>),
+    TimeSeries(Box<TimeSeries>),
+    Hash(HashMap<String, String>),
 }
 
 #[derive(Debug, Clone)]
@@ -20,97 +58,278 @@ pub struct StoreObject {
     // last 4 bits = encoding of object
     // number-range = 0-15
     pub type_encoding: u8,
-    // stores the actual value of the object, e.g. a pointer to a string
-    pub value: Box<ObjectValue>,
-    // as we have no native support for bitfields in Rust, we store the last accessed timestamp as 32bit instead of 24bit
+    // stores the actual value of the object, small integers reference a shared object, see shared_integers
+    pub value: Arc<ObjectValue>,
+    // 24 bit LRU clock of the last access, see lru_eviction_strategy. As we have no native support for bitfields in Rust,
+    // the upper 8 bits of the u32 are unused. With an LFU policy it stores the access frequency instead, see lfu_eviction_strategy
     pub last_accessed_at: u32,
 }
 
 impl StoreObject {
     pub fn new(value: ObjectValue, type_encoding: u8) -> Self {
         StoreObject {
-            value: Box::new(value),
+            value: create_object_value(value),
             type_encoding,
-            last_accessed_at: get_current_clock()
+            last_accessed_at: get_lru_clock()
         }
     }
 
+    /// Milliseconds since the object was accessed the last time, see OBJECT IDLETIME
+    pub fn get_idle_time_ms(&self) -> u64 {
+        lru_eviction_strategy::get_idle_time_ms(self.last_accessed_at)
+    }
+
+    /// Updates the access time, or the access frequency if LFU is used
+    fn record_access(&mut self, lfu: Option<&LFUConfiguration>) {
+        self.last_accessed_at = match lfu {
+            Some(lfu) => lfu_eviction_strategy::update_access(self.last_accessed_at, lfu),
+            None => get_lru_clock(),
+        };
+    }
+
     pub fn get_value_clone(&self) -> ObjectValue {
         return self.value.as_ref().clone();
     }
+
+    /// Mutable access to the value, a value shared with other keys is copied first
+    pub fn get_value_mut(&mut self) -> &mut ObjectValue {
+        Arc::make_mut(&mut self.value)
+    }
+
+    /// Mutable access to the value if it has the expected type. The type is checked on the shared value,
+    /// so looking up a key of another type does not copy it
+    pub fn get_value_mut_if(&mut self, has_type: fn(&ObjectValue) -> bool) -> Option<&mut ObjectValue> {
+        if !has_type(self.value.as_ref()) {
+            return None;
+        }
+        Some(self.get_value_mut())
+    }
+
+    /// Replaces the value, small integers reference a shared object again
+    pub fn set_value(&mut self, value: ObjectValue) {
+        self.value = create_object_value(value);
+    }
+
+    /// Number of keys referencing the value plus the reference of the shared integer pool, see OBJECT REFCOUNT
+    pub fn get_ref_count(&self) -> usize {
+        Arc::strong_count(&self.value)
+    }
+
+    /// Estimated number of bytes used by the object
+    pub fn get_memory_usage(&self) -> u64 {
+        self.get_sampled_memory_usage(0)
+    }
+
+    /// Estimated number of bytes used by the object, see MEMORY USAGE.
+    /// Like Redis, the size of hashes, sorted sets and JSON arrays and objects is extrapolated from the first
+    /// samples elements, 0 computes the size of all elements
+    pub fn get_sampled_memory_usage(&self, samples: usize) -> u64 {
+        // shared objects are allocated once for all keys
+        if is_shared(&self.value) {
+            return std::mem::size_of::<StoreObject>() as u64;
+        }
+
+        let value_size = match self.value.as_ref() {
+            ObjectValue::String(string) => string.get_memory_usage(),
+            ObjectValue::SortedSet(sorted_set) => sorted_set.get_memory_usage(samples),
+            ObjectValue::Json(document) => document.get_sampled_memory_usage(samples),
+            ObjectValue::BloomFilter(filter) => filter.get_size(),
+            ObjectValue::CuckooFilter(filter) => filter.get_size(),
+            ObjectValue::CountMinSketch(sketch) => sketch.get_size(),
+            ObjectValue::TopK(top_k) => top_k.get_size(),
+            ObjectValue::TimeSeries(series) => series.get_memory_usage(),
+            ObjectValue::Hash(hash) => get_hash_memory_usage(hash, samples),
+        };
+        (std::mem::size_of::<StoreObject>() + std::mem::size_of::<ObjectValue>()) as u64 + value_size
+    }
 }
 
-pub struct Store {
-    data: HashMap<String, StoreObject>,
+/// Estimated memory used by the hash tables of one database, see MEMORY STATS
+pub struct DatabaseOverhead {
+    pub db: usize,
+    pub keys: usize,
+    pub main_bytes: u64,
+    pub expires_bytes: u64,
+}
+
+/// Keys, expirations and search indexes of one logical database
+struct Database {
+    data: Dict<StoreObject>,
     // stores the expiration of keys in unix epoch milliseconds
-    expiration_data: HashMap<String, i64>,
+    expiration_data: Dict<i64>,
+    // keys ordered by their expiration, only maintained if active-expire-index is enabled
+    expiry_index: Option<ExpiryIndex>,
+    search_indexes: IndexManager,
+    // keys holding a time series, so the retention job does not visit all keys
+    time_series_keys: HashSet<String>,
+}
+
+impl Database {
+    fn new(with_expiry_index: bool) -> Self {
+        Database {
+            data: Dict::new(),
+            expiration_data: Dict::new(),
+            expiry_index: with_expiry_index.then(ExpiryIndex::new),
+            search_indexes: IndexManager::new(),
+            time_series_keys: HashSet::new(),
+        }
+    }
+
+    /// Sets or removes the expiration of the key and keeps the expiry index in sync
+    fn set_expiration(&mut self, key: &str, expires_at: Option<i64>) {
+        let previous = match expires_at {
+            Some(expires_at) => self.expiration_data.insert(String::from(key), expires_at),
+            None => self.expiration_data.remove(key),
+        };
+
+        if let Some(expiry_index) = self.expiry_index.as_mut() {
+            if let Some(previous) = previous {
+                expiry_index.remove(key, previous);
+            }
+            if let Some(expires_at) = expires_at {
+                expiry_index.insert(key, expires_at);
+            }
+        }
+    }
+}
+
+/// The store holds a fixed number of logical databases. All key operations work on the selected database,
+/// the command handler selects the database of the client connection before executing its commands
+pub struct Store {
+    databases: Vec<Database>,
+    selected_db: usize,
     eviction_manager: Option<EvictionManager>,
+    // effort of the active expiration cycle from 1 to 10, see active_expiration
+    active_expire_effort: u32,
+    // state of the connected clients, see client
+    clients: ClientRegistry,
 }
 
 impl Store {
-    pub fn new() -> Self {
+    pub fn new(databases: usize) -> Self {
         Store {
-            data: HashMap::new(),
-            expiration_data: HashMap::new(),
-            eviction_manager: Some(EvictionManager::new(EvictionManagerConfiguration { keys_limit: 5, eviction_ratio: 0.4 }, Box::new(AllKeysLRUEvictionStrategy::new()))),
+            databases: (0..databases.max(1)).map(|_| Database::new(false)).collect(),
+            selected_db: 0,
+            eviction_manager: Some(EvictionManager::new(EvictionManagerConfiguration { maxmemory: 0, policy: EvictionPolicy::NoEviction, lfu: LFUConfiguration::new(), samples: DEFAULT_MAXMEMORY_SAMPLES })),
+            active_expire_effort: DEFAULT_ACTIVE_EXPIRE_EFFORT,
+            clients: ClientRegistry::new(),
         }
     }
 
+    /// Adds or overwrites the key, an expiration duration of 0 or less stores the key without expiration.
+    /// The expiration of an overwritten key is removed
     pub fn put(&mut self, key: &str, value: ObjectValue, expiration_duration_ms: i64, type_encoding: u8) {
-        // check for eviction
+        self.perform_evictions();
+
+        let store_object = self.create_store_object(value, type_encoding);
+        let expires_at = if expiration_duration_ms > 0 {
+            let now = chrono::Utc::now();
+            let duration = chrono::Duration::milliseconds(expiration_duration_ms);
+            Some((now + duration).timestamp_millis())
+        } else {
+            None
+        };
+
+        self.insert(key, store_object, expires_at);
+    }
+
+    /// Adds or overwrites the key with an expiration in unix epoch milliseconds, see SET EXAT or KEEPTTL
+    pub fn put_with_expiry(&mut self, key: &str, value: ObjectValue, expires_at: Option<i64>, type_encoding: u8) {
+        self.perform_evictions();
+
+        let store_object = self.create_store_object(value, type_encoding);
+        self.insert(key, store_object, expires_at);
+    }
+
+    fn create_store_object(&self, value: ObjectValue, type_encoding: u8) -> StoreObject {
+        let mut store_object = StoreObject::new(value, type_encoding);
+        if self.get_lfu_tracking().is_some() {
+            store_object.last_accessed_at = lfu_eviction_strategy::get_initial_access();
+        }
+        store_object
+    }
+
+    /// Evicts keys if the used memory exceeds maxmemory.
+    /// Returns false if the memory is still above the limit, e.g. with the noeviction policy
+    pub fn perform_evictions(&mut self) -> bool {
         let mut eviction_manager = self.eviction_manager.take().expect("EvictionManager is None");
         if eviction_manager.ready_for_evict(self) {
             eviction_manager.evict(self);
         }
+        let below_limit = !eviction_manager.ready_for_evict(self);
         self.eviction_manager = Some(eviction_manager);
-        //
-
-        let store_object = StoreObject::new(value, type_encoding);
-        self.data.insert(String::from(key), store_object);
+        below_limit
+    }
 
-        if expiration_duration_ms > 0 {
-            let now = chrono::Utc::now();
-            let duration = chrono::Duration::milliseconds(expiration_duration_ms);
-            let expires_at = now + duration;
-            self.expiration_data.insert(String::from(key), expires_at.timestamp_millis());
-        }
+    /// Adds the object to the selected database without checking for eviction, replacing the expiration of an existing key
+    fn insert(&mut self, key: &str, store_object: StoreObject, expires_at: Option<i64>) {
+        let db = &mut self.databases[self.selected_db];
+        db.search_indexes.on_put(key, store_object.value.as_ref());
+        match store_object.value.as_ref() {
+            ObjectValue::TimeSeries(_) => db.time_series_keys.insert(String::from(key)),
+            _ => db.time_series_keys.remove(key),
+        };
+        db.data.insert(String::from(key), store_object);
+        db.set_expiration(key, expires_at);
 
-        update_keyspace_statistics(0, self.data.len() as u64);
+        update_keyspace_statistics(self.selected_db, db.data.len() as u64);
     }
 
     pub fn remove(&mut self, key: &str) -> Option<StoreObject> {
-        let removed_key = self.data.remove(key);
-        self.expiration_data.remove(key);
+        let db = &mut self.databases[self.selected_db];
+        let removed_key = db.data.remove(key);
+        db.set_expiration(key, None);
+        db.search_indexes.on_remove(key);
+        db.time_series_keys.remove(key);
 
-        update_keyspace_statistics(0, self.data.len() as u64);
+        update_keyspace_statistics(self.selected_db, db.data.len() as u64);
 
         removed_key
     }
 
     pub fn get(&mut self, key: &str) -> Option<StoreObject> {
-        let has_expired = &self.has_expired(key);
+        self.get_ref(key).cloned()
+    }
 
-        match self.data.entry(key.to_string()) {
-            Entry::Occupied(mut entry) => {
-                if *has_expired {
-                    entry.remove();
-                    return None;
-                }
+    /// Like `get`, but returns a reference instead of a copy of the object, used by commands which only read the value
+    pub fn get_ref(&mut self, key: &str) -> Option<&StoreObject> {
+        if self.expire_if_needed(key) {
+            return None;
+        }
+
+        let lfu = self.get_lfu_tracking();
+        let store_object = self.databases[self.selected_db].data.get_mut(key)?;
+        store_object.record_access(lfu.as_ref());
+        Some(store_object)
+    }
 
-                entry.get_mut().last_accessed_at = get_current_clock();
+    /// Returns a mutable reference to the object, used by commands that modify values in place (e.g. adding a member to a sorted set)
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut StoreObject> {
+        if self.expire_if_needed(key) {
+            return None;
+        }
 
-                let store_object = entry.get();
-                Some(store_object.clone())
-            }
-            Entry::Vacant(_) => {
-                return None;
-            }
+        let lfu = self.get_lfu_tracking();
+        let db = &mut self.databases[self.selected_db];
+        let store_object = db.data.get_mut(key)?;
+        store_object.record_access(lfu.as_ref());
+        db.search_indexes.on_modify(key);
+        Some(store_object)
+    }
+
+    /// Removes the key if it has expired and returns whether it was removed, see expired_keys in INFO
+    pub fn expire_if_needed(&mut self, key: &str) -> bool {
+        if !self.has_expired(key) {
+            return false;
         }
+        self.remove(key);
+        increment_expired_keys(1);
+        true
     }
 
     fn has_expired(&self, key: &str) -> bool {
         let now = chrono::Utc::now().timestamp_millis();
-        let expires_at = self.expiration_data.get(key);
+        let expires_at = self.databases[self.selected_db].expiration_data.get(key);
 
         if expires_at.is_none() {
             return false;
@@ -119,19 +338,471 @@ impl Store {
        *expires_at.unwrap() <= now
     }
 
-    pub fn get_data(&self) -> &HashMap<String, StoreObject> {
-        &self.data
+    pub fn get_data(&self) -> &Dict<StoreObject> {
+        &self.databases[self.selected_db].data
+    }
+
+    /// Mutable access to all values, used by background jobs which modify values in place (e.g. time series retention)
+    pub fn get_data_mut(&mut self) -> &mut Dict<StoreObject> {
+        &mut self.databases[self.selected_db].data
+    }
+
+    /// Keys of the selected database which hold a time series
+    pub fn get_time_series_keys(&self) -> &HashSet<String> {
+        &self.databases[self.selected_db].time_series_keys
+    }
+
+    pub fn get_expiration_data(&self) -> &Dict<i64> {
+        &self.databases[self.selected_db].expiration_data
+    }
+
+    /// Bytes used by all keys and values of all databases. Like Redis, it is derived from the allocated bytes minus the
+    /// memory of the server at startup and the overhead of the hash tables, so no key needs to be visited
+    pub fn get_dataset_memory(&self) -> u64 {
+        let overhead_bytes: u64 = self.get_database_overheads().iter().map(|overhead| overhead.main_bytes + overhead.expires_bytes).sum();
+        get_allocated_bytes().saturating_sub(get_startup_allocated_bytes() + overhead_bytes)
+    }
+
+    /// Estimated number of bytes used by all keys and values of all databases, visits every key
+    pub fn estimate_dataset_memory(&self) -> u64 {
+        self.databases.iter()
+            .flat_map(|db| db.data.iter())
+            .map(|(key, value)| key.capacity() as u64 + value.get_memory_usage())
+            .sum()
+    }
+
+    /// Estimated overhead of the hash tables of all databases which contain keys
+    pub fn get_database_overheads(&self) -> Vec<DatabaseOverhead> {
+        self.databases.iter().enumerate()
+            .filter(|(_, db)| !db.data.is_empty())
+            .map(|(index, db)| {
+                DatabaseOverhead {
+                    db: index,
+                    keys: db.data.len(),
+                    main_bytes: db.data.get_memory_usage(),
+                    expires_bytes: db.expiration_data.get_memory_usage() + db.expiration_data.get_keys_memory_usage()
+                        + db.expiry_index.as_ref().map_or(0, |expiry_index| expiry_index.get_memory_usage()),
+                }
+            })
+            .collect()
+    }
+
+    /// Moves buckets of the hash tables which are being resized until the budget is used, called by the event loop while idle.
+    /// Returns whether a hash table is still being rehashed
+    pub fn rehash_databases(&mut self, budget: Duration) -> bool {
+        let start = Instant::now();
+        for db in self.databases.iter_mut() {
+            if db.data.is_rehashing() {
+                db.data.rehash_for(budget.saturating_sub(start.elapsed()));
+            }
+            if db.expiration_data.is_rehashing() {
+                db.expiration_data.rehash_for(budget.saturating_sub(start.elapsed()));
+            }
+        }
+        self.get_rehashing_dict_count() > 0
     }
 
-    pub fn get_expiration_data(&self) -> &HashMap<String, i64> {
-        &self.expiration_data
+    /// Number of hash tables of all databases which are being rehashed
+    pub fn get_rehashing_dict_count(&self) -> usize {
+        self.databases.iter()
+            .map(|db| db.data.is_rehashing() as usize + db.expiration_data.is_rehashing() as usize)
+            .sum()
+    }
+
+    /// Bytes of the old hash tables which are freed once the rehashing is complete
+    pub fn get_rehashing_memory(&self) -> u64 {
+        self.databases.iter()
+            .map(|db| db.data.get_rehashing_memory_usage() + db.expiration_data.get_rehashing_memory_usage())
+            .sum()
+    }
+
+    /// Returns database, key and memory usage of the largest keys of all databases, ordered by size
+    pub fn get_biggest_keys(&self, count: usize) -> Vec<(usize, String, u64)> {
+        let mut keys: Vec<(usize, String, u64)> = self.databases.iter().enumerate()
+            .flat_map(|(index, db)| db.data.iter().map(move |(key, value)| (index, key, key.capacity() as u64 + value.get_memory_usage())))
+            .map(|(index, key, size)| (index, key.clone(), size))
+            .collect();
+        keys.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+        keys.truncate(count);
+        keys
     }
 
     pub fn get_expiry(&self, key: &str) -> Option<i64> {
-        self.expiration_data.get(key).cloned()
+        self.databases[self.selected_db].expiration_data.get(key).cloned()
+    }
+
+    /// Sets the expiration of an existing key in unix epoch milliseconds, returns false if the key does not exist
+    pub fn set_expiry(&mut self, key: &str, expires_at: i64) -> bool {
+        if !self.contains_key(key) {
+            return false;
+        }
+        self.databases[self.selected_db].set_expiration(key, Some(expires_at));
+        true
+    }
+
+    /// Removes the expiration of the key, returns false if the key does not exist or has no expiration
+    pub fn persist(&mut self, key: &str) -> bool {
+        if !self.contains_key(key) || self.get_expiry(key).is_none() {
+            return false;
+        }
+        self.databases[self.selected_db].set_expiration(key, None);
+        true
+    }
+
+    /// Whether the databases keep their keys ordered by expiration, see expiry_index
+    pub fn has_expiry_index(&self) -> bool {
+        self.databases[0].expiry_index.is_some()
+    }
+
+    /// Enables or disables the expiry index of all databases, enabling it indexes all keys with an expiration
+    pub fn set_expiry_index(&mut self, enabled: bool) {
+        for db in self.databases.iter_mut() {
+            db.expiry_index = enabled.then(|| {
+                let mut expiry_index = ExpiryIndex::new();
+                for (key, expires_at) in db.expiration_data.iter() {
+                    expiry_index.insert(key, *expires_at);
+                }
+                expiry_index
+            });
+        }
+    }
+
+    /// Returns up to `count` keys of the selected database which are expired, ordered by expiration.
+    /// Returns nothing if the expiry index is disabled
+    pub fn get_expired_keys(&self, count: usize) -> Vec<String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.databases[self.selected_db].expiry_index.as_ref()
+            .map_or(Vec::new(), |expiry_index| expiry_index.get_expired_keys(now, count))
+    }
+
+    /// Returns whether the key exists without updating its access time, expired keys are removed
+    pub fn contains_key(&mut self, key: &str) -> bool {
+        if self.expire_if_needed(key) {
+            return false;
+        }
+        self.get_data().contains_key(key)
+    }
+
+    /// Returns the object without updating its access time, used by introspection commands (e.g. OBJECT)
+    pub fn get_without_touch(&mut self, key: &str) -> Option<&StoreObject> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        self.get_data().get(key)
+    }
+
+    /// Updates the access time of the key, returns false if the key does not exist
+    pub fn touch(&mut self, key: &str) -> bool {
+        if !self.contains_key(key) {
+            return false;
+        }
+        let lfu = self.get_lfu_tracking();
+        match self.databases[self.selected_db].data.get_mut(key) {
+            Some(store_object) => {
+                store_object.record_access(lfu.as_ref());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renames the key and keeps its expiration. An existing new key is overwritten if `replace` is set,
+    /// otherwise false is returned and nothing is changed
+    pub fn rename(&mut self, key: &str, new_key: &str, replace: bool) -> Result<bool, String> {
+        if !self.contains_key(key) {
+            return Err(String::from("no such key"));
+        }
+        if key == new_key {
+            return Ok(replace);
+        }
+        if !replace && self.contains_key(new_key) {
+            return Ok(false);
+        }
+
+        let expires_at = self.get_expiry(key);
+        let store_object = self.remove(key).expect("Key not found");
+        self.remove(new_key);
+        self.insert(new_key, store_object, expires_at);
+        Ok(true)
+    }
+
+    /// Copies the value and the expiration of the key to the new key in the given database.
+    /// Returns false if the key does not exist or the new key exists and `replace` is not set
+    pub fn copy(&mut self, key: &str, new_key: &str, db: usize, replace: bool) -> Result<bool, String> {
+        if db >= self.databases.len() {
+            return Err(String::from("DB index is out of range"));
+        }
+        if db == self.selected_db && key == new_key {
+            return Err(String::from("source and destination objects are the same"));
+        }
+
+        let store_object = match self.get(key) {
+            Some(store_object) => store_object,
+            None => return Ok(false),
+        };
+        let expires_at = self.get_expiry(key);
+
+        let source_db = self.selected_db;
+        self.selected_db = db;
+        let copied = replace || !self.contains_key(new_key);
+        if copied {
+            self.remove(new_key);
+            self.insert(new_key, self.create_store_object(store_object.get_value_clone(), store_object.type_encoding), expires_at);
+        }
+        self.selected_db = source_db;
+        Ok(copied)
+    }
+
+    /// Returns up to `count` random keys for eviction, with `volatile` only keys with an expiration
+    pub fn sample_keys(&self, count: usize, volatile: bool) -> Vec<String> {
+        let db = &self.databases[self.selected_db];
+        match volatile {
+            true => db.expiration_data.sample(count).into_iter().map(|(key, _)| key.clone()).collect(),
+            false => db.data.sample(count).into_iter().map(|(key, _)| key.clone()).collect(),
+        }
+    }
+
+    /// Whether a key taken from the eviction pool can still be evicted, it may have been removed or persisted in the meantime
+    pub fn is_evictable(&self, key: &str, volatile: bool) -> bool {
+        let db = &self.databases[self.selected_db];
+        match volatile {
+            true => db.expiration_data.contains_key(key),
+            false => db.data.contains_key(key),
+        }
+    }
+
+    /// Returns a random key, expired keys which are picked are removed and another key is picked
+    pub fn random_key(&mut self) -> Option<String> {
+        loop {
+            let (key, _) = self.databases[self.selected_db].data.random_entry()?;
+            let key = key.clone();
+
+            if !self.expire_if_needed(&key) {
+                return Some(key);
+            }
+        }
+    }
+
+    /// Returns the keys matching the glob-style pattern, expired keys are skipped
+    pub fn get_keys(&self, pattern: &str) -> Vec<String> {
+        self.get_data().keys()
+            .filter(|key| glob_match(pattern, key) && !self.has_expired(key))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the keys of the buckets starting at the cursor and the cursor of the next call (0 when the iteration is complete).
+    ///
+    /// Buckets are visited until at least `count` keys are found or 10 * `count` buckets are visited, see Dict::scan.
+    /// Every key which exists during the whole iteration is returned, keys of one bucket are always returned together.
+    /// Expired keys are removed instead of being returned
+    pub fn scan(&mut self, cursor: u64, count: usize) -> (u64, Vec<String>) {
+        let mut keys: Vec<String> = Vec::new();
+        let mut next_cursor = cursor;
+
+        let data = &self.databases[self.selected_db].data;
+        for _ in 0..count.max(1) * 10 {
+            next_cursor = data.scan(next_cursor, |key, _| keys.push(key.clone()));
+            if next_cursor == 0 || keys.len() >= count.max(1) {
+                break;
+            }
+        }
+
+        keys.retain(|key| !self.expire_if_needed(key));
+
+        (next_cursor, keys)
+    }
+
+    /// Memory limit in bytes which triggers eviction, 0 means no limit
+    pub fn get_maxmemory(&self) -> u64 {
+        self.eviction_manager.as_ref().map_or(0, |eviction_manager| eviction_manager.config.maxmemory)
+    }
+
+    pub fn set_maxmemory(&mut self, maxmemory: u64) {
+        if let Some(eviction_manager) = self.eviction_manager.as_mut() {
+            eviction_manager.config.maxmemory = maxmemory;
+        }
+    }
+
+    pub fn get_eviction_policy(&self) -> EvictionPolicy {
+        self.eviction_manager.as_ref().map_or(EvictionPolicy::NoEviction, |eviction_manager| eviction_manager.config.policy)
+    }
+
+    pub fn set_eviction_policy(&mut self, policy: EvictionPolicy) {
+        if let Some(eviction_manager) = self.eviction_manager.as_mut() {
+            eviction_manager.set_policy(policy);
+        }
+    }
+
+    /// Number of keys sampled per eviction, see maxmemory-samples
+    pub fn get_maxmemory_samples(&self) -> usize {
+        self.eviction_manager.as_ref().map_or(DEFAULT_MAXMEMORY_SAMPLES, |eviction_manager| eviction_manager.config.samples)
+    }
+
+    pub fn set_maxmemory_samples(&mut self, samples: usize) {
+        if let Some(eviction_manager) = self.eviction_manager.as_mut() {
+            eviction_manager.config.samples = samples;
+        }
+    }
+
+    /// Tunables of the access frequency counters, see lfu-log-factor and lfu-decay-time
+    pub fn get_lfu_configuration(&self) -> LFUConfiguration {
+        self.eviction_manager.as_ref().map_or(LFUConfiguration::new(), |eviction_manager| eviction_manager.config.lfu)
+    }
+
+    pub fn set_lfu_configuration(&mut self, lfu: LFUConfiguration) {
+        if let Some(eviction_manager) = self.eviction_manager.as_mut() {
+            eviction_manager.config.lfu = lfu;
+        }
+    }
+
+    /// Configuration of the access frequency counters if an LFU policy is used, otherwise access times are tracked
+    fn get_lfu_tracking(&self) -> Option<LFUConfiguration> {
+        self.get_eviction_policy().is_lfu().then(|| self.get_lfu_configuration())
+    }
+
+    pub fn get_active_expire_effort(&self) -> u32 {
+        self.active_expire_effort
+    }
+
+    pub fn set_active_expire_effort(&mut self, effort: u32) {
+        self.active_expire_effort = effort;
+    }
+
+    pub fn get_clients(&self) -> &ClientRegistry {
+        &self.clients
+    }
+
+    pub fn get_clients_mut(&mut self) -> &mut ClientRegistry {
+        &mut self.clients
+    }
+
+    pub fn get_db_count(&self) -> usize {
+        self.databases.len()
+    }
+
+    pub fn get_selected_db(&self) -> usize {
+        self.selected_db
+    }
+
+    pub fn select_db(&mut self, db: usize) -> Result<(), String> {
+        if db >= self.databases.len() {
+            return Err(String::from("DB index is out of range"));
+        }
+        self.selected_db = db;
+        Ok(())
+    }
+
+    /// Moves the key with its expiration from the selected database to the other database.
+    /// Returns false if the key does not exist or the other database already contains the key
+    pub fn move_key(&mut self, key: &str, db: usize) -> Result<bool, String> {
+        if db >= self.databases.len() {
+            return Err(String::from("DB index is out of range"));
+        }
+        if db == self.selected_db {
+            return Err(String::from("source and destination objects are the same"));
+        }
+
+        let source_db = self.selected_db;
+        self.selected_db = db;
+        let exists_in_destination = self.get(key).is_some();
+        self.selected_db = source_db;
+        if exists_in_destination || self.get(key).is_none() {
+            return Ok(false);
+        }
+
+        let expires_at = self.get_expiry(key);
+        let store_object = self.remove(key).expect("Key not found");
+        self.selected_db = db;
+        self.insert(key, store_object, expires_at);
+        self.selected_db = source_db;
+        Ok(true)
+    }
+
+    /// Swaps the contents of two databases, clients which selected one of them see the data of the other one
+    pub fn swap_dbs(&mut self, first: usize, second: usize) -> Result<(), String> {
+        if first >= self.databases.len() || second >= self.databases.len() {
+            return Err(String::from("DB index is out of range"));
+        }
+
+        self.databases.swap(first, second);
+        update_keyspace_statistics(first, self.databases[first].data.len() as u64);
+        update_keyspace_statistics(second, self.databases[second].data.len() as u64);
+        Ok(())
+    }
+
+    /// Removes all keys and search indexes of the selected database
+    pub fn flush_db(&mut self) {
+        let with_expiry_index = self.has_expiry_index();
+        self.databases[self.selected_db] = Database::new(with_expiry_index);
+        update_keyspace_statistics(self.selected_db, 0);
+    }
+
+    pub fn create_search_index(&mut self, definition: IndexDefinition) -> Result<(), String> {
+        let db = &mut self.databases[self.selected_db];
+        db.search_indexes.create_index(definition, &db.data)
+    }
+
+    /// Drops the index, with `delete_documents` all keys indexed by it are removed as well
+    pub fn drop_search_index(&mut self, index_name: &str, delete_documents: bool) -> Result<(), String> {
+        let index = self.databases[self.selected_db].search_indexes.drop_index(index_name).ok_or_else(|| String::from("Unknown Index name"))?;
+        if delete_documents {
+            for key in index.get_keys() {
+                self.remove(&key);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_search_index_definitions(&self) -> Vec<&IndexDefinition> {
+        self.databases[self.selected_db].search_indexes.get_definitions()
+    }
+
+    /// Runs the query on the index. Keys modified in place are indexed again and expired keys of the result are removed,
+    /// so that the result only contains live keys with their current values
+    pub fn search(&mut self, index_name: &str, query: &Query) -> Result<Vec<SearchResult>, String> {
+        loop {
+            let mut results = self.get_search_index(index_name)?.search(query)?;
+            let expired_keys: HashSet<String> = results.iter()
+                .filter(|result| self.expire_if_needed(&result.key))
+                .map(|result| result.key.clone())
+                .collect();
+            if expired_keys.is_empty() {
+                return Ok(results);
+            }
+
+            // expired keys take the place of live neighbours in the k nearest results, so the query runs again
+            // on the index without them. Every run removes at least one key
+            if query.knn.is_none() {
+                results.retain(|result| !expired_keys.contains(&result.key));
+                return Ok(results);
+            }
+        }
+    }
+
+    /// Returns the index after indexing the keys which were modified in place
+    pub fn get_search_index(&mut self, index_name: &str) -> Result<&SearchIndex, String> {
+        let db = &mut self.databases[self.selected_db];
+        db.search_indexes.refresh(&db.data);
+        db.search_indexes.get_index(index_name).ok_or_else(|| format!("{}: no such index", index_name))
+    }
+
+    /// Returns the index without indexing modified keys, used to read the values of search results next to `get_data`
+    pub fn find_search_index(&self, index_name: &str) -> Option<&SearchIndex> {
+        self.databases[self.selected_db].search_indexes.get_index(index_name)
     }
 }
 
+/// Sum of the sizes of len elements, extrapolated from the first samples sizes if there are more elements.
+/// With 0 samples the sizes of all elements are added up
+pub fn estimate_from_samples(sizes: impl Iterator<Item=u64>, len: usize, samples: usize) -> u64 {
+    if samples == 0 || samples >= len {
+        return sizes.sum();
+    }
+    let sampled_size: u64 = sizes.take(samples).sum();
+    sampled_size * len as u64 / samples as u64
+}
+
 pub fn store_object_to_datatype(value: &StoreObject) -> DataType {
     let obj_type = get_type(value.type_encoding);
 
@@ -139,7 +810,10 @@ pub fn store_object_to_datatype(value: &StoreObject) -> DataType {
         OBJ_TYPE_STRING => {
             return match value.value.as_ref() {
                 ObjectValue::String(string) => {
-                    DataType::BulkString(string.clone())
+                    DataType::BulkString(string.as_str().into_owned())
+                }
+                _ => {
+                    panic!("Unknown value for string type");
                 }
             };
         }
@@ -149,46 +823,40 @@ pub fn store_object_to_datatype(value: &StoreObject) -> DataType {
     }
 }
 
-fn get_current_clock() -> u32 {
-    let now = chrono::Utc::now();
-    let now = now.timestamp_millis();
-    (now & 0xFFFFFFFF) as u32
-}
-
 #[test]
 fn test_store_put_get() {
     // given
-    let mut store = Store::new();
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
 
     // when
-    store.put("key", ObjectValue::String("value".to_string()), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
-    store.put("key2", ObjectValue::String("123".to_string()), 1000, OBJ_TYPE_STRING | OBJ_ENCODING_INT);
-    store.put("key4", ObjectValue::String(String::from("12345678901234567890123456789012345678901234567890test12345")), 2000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.put("key2", ObjectValue::String(StringValue::new("123".to_string())), 1000, OBJ_TYPE_STRING | OBJ_ENCODING_INT);
+    store.put("key4", ObjectValue::String(StringValue::new(String::from("12345678901234567890123456789012345678901234567890test12345"))), 2000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
 
     // then
     let key = store.get("key").expect("Key not found");
     assert_eq!(key.type_encoding, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
-    assert_eq!(key.get_value_clone(), ObjectValue::String("value".to_string()));
+    assert_eq!(key.get_value_clone(), ObjectValue::String(StringValue::new("value".to_string())));
     assert_eq!(store.get_expiry("key"), None);
 
     let key2 = store.get("key2").expect("Key not found");
     assert_eq!(key2.type_encoding, OBJ_TYPE_STRING | OBJ_ENCODING_INT);
-    assert_eq!(key2.get_value_clone(), ObjectValue::String("123".to_string()));
+    assert_eq!(key2.get_value_clone(), ObjectValue::String(StringValue::new("123".to_string())));
     assert_eq!(store.get_expiry("key2").unwrap(), chrono::Utc::now().timestamp_millis() + 1000);
 
     let key4 = store.get("key4").expect("Key not found");
     assert_eq!(key4.type_encoding, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
-    assert_eq!(key4.get_value_clone(), ObjectValue::String(String::from("12345678901234567890123456789012345678901234567890test12345")));
+    assert_eq!(key4.get_value_clone(), ObjectValue::String(StringValue::new(String::from("12345678901234567890123456789012345678901234567890test12345"))));
     assert_eq!(store.get_expiry("key4").unwrap(), chrono::Utc::now().timestamp_millis() + 2000);
 }
 
 #[test]
 fn test_store_remove() {
     // given
-    let mut store = Store::new();
-    store.put("key", ObjectValue::String("value".to_string()), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
-    store.put("key2", ObjectValue::String("123".to_string()), 1000, OBJ_TYPE_STRING | OBJ_ENCODING_INT);
-    store.put("key4", ObjectValue::String(String::from("12345678901234567890123456789012345678901234567890test12345")), 2000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.put("key2", ObjectValue::String(StringValue::new("123".to_string())), 1000, OBJ_TYPE_STRING | OBJ_ENCODING_INT);
+    store.put("key4", ObjectValue::String(StringValue::new(String::from("12345678901234567890123456789012345678901234567890test12345"))), 2000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
 
     // then
     let removed_key = store.remove("key");
@@ -197,8 +865,259 @@ fn test_store_remove() {
     let not_existing_key = store.remove("notExistingKey");
 
     // when
-    assert_eq!(removed_key.expect("Key not found").value, Box::new(ObjectValue::String("value".to_string())));
-    assert_eq!(removed_key_2.expect("Key not found").value, Box::new(ObjectValue::String("123".to_string())));
-    assert_eq!(removed_key_4.expect("Key not found").value, Box::new(ObjectValue::String(String::from("12345678901234567890123456789012345678901234567890test12345"))));
+    assert_eq!(removed_key.expect("Key not found").value, Arc::new(ObjectValue::String(StringValue::new("value".to_string()))));
+    assert_eq!(removed_key_2.expect("Key not found").value, Arc::new(ObjectValue::String(StringValue::new("123".to_string()))));
+    assert_eq!(removed_key_4.expect("Key not found").value, Arc::new(ObjectValue::String(StringValue::new(String::from("12345678901234567890123456789012345678901234567890test12345")))));
     assert_eq!(not_existing_key.is_none(), true);
 }
+
+#[test]
+fn test_store_databases() {
+    // given
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), 10000, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.put("other", ObjectValue::String(StringValue::new("db0".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.select_db(1).unwrap();
+    store.put("other", ObjectValue::String(StringValue::new("db1".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.select_db(0).unwrap();
+
+    // when
+    let moved = store.move_key("key", 1).unwrap();
+    let not_moved = store.move_key("other", 1).unwrap();
+
+    // then
+    assert!(moved);
+    assert!(!not_moved);
+    assert!(store.get("key").is_none());
+    assert!(store.select_db(16).is_err());
+    assert!(store.move_key("other", 0).is_err());
+
+    store.select_db(1).unwrap();
+    assert!(store.get_expiry("key").is_some());
+    assert_eq!(store.get_data().len(), 2);
+
+    // when
+    store.swap_dbs(0, 1).unwrap();
+
+    // then
+    assert_eq!(store.get_data().len(), 1);
+    assert_eq!(store.get("other").unwrap().get_value_clone(), ObjectValue::String(StringValue::new("db0".to_string())));
+    store.select_db(0).unwrap();
+    assert_eq!(store.get_data().len(), 2);
+
+    // when
+    store.flush_db();
+
+    // then
+    assert!(store.get_data().is_empty());
+    store.select_db(1).unwrap();
+    assert_eq!(store.get_data().len(), 1);
+}
+
+#[test]
+fn test_store_scan() {
+    // given
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    for i in 0..100 {
+        store.insert(&format!("key:{}", i), StoreObject::new(ObjectValue::String(StringValue::new(i.to_string())), OBJ_TYPE_STRING | OBJ_ENCODING_INT), None);
+    }
+
+    // when
+    let mut returned_keys = Vec::new();
+    let (mut cursor, keys) = store.scan(0, 10);
+    returned_keys.extend(keys);
+    // the keyspace grows and keys are removed during the iteration
+    for i in 100..1000 {
+        store.insert(&format!("key:{}", i), StoreObject::new(ObjectValue::String(StringValue::new(i.to_string())), OBJ_TYPE_STRING | OBJ_ENCODING_INT), None);
+    }
+    // a key which was not returned yet, the keys of the first call depend on their buckets
+    let removed_key = (0..100).map(|i| format!("key:{}", i)).rev().find(|key| !returned_keys.contains(key)).unwrap();
+    store.remove(&removed_key);
+    while cursor != 0 {
+        let (next_cursor, keys) = store.scan(cursor, 10);
+        returned_keys.extend(keys);
+        cursor = next_cursor;
+    }
+
+    // then
+    for key in (0..100).map(|i| format!("key:{}", i)).filter(|key| *key != removed_key) {
+        assert_eq!(returned_keys.iter().filter(|returned_key| **returned_key == key).count(), 1, "{} not returned once", key);
+    }
+    assert!(!returned_keys.contains(&removed_key));
+    assert_eq!(store.get_keys("key:99?").len(), 10);
+}
+
+#[test]
+fn test_store_rename_and_copy() {
+    // given
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), 10000, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+    store.put("other", ObjectValue::String(StringValue::new("other".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_RAW);
+
+    // when
+    let renamed = store.rename("key", "renamed", false).unwrap();
+    let not_renamed = store.rename("renamed", "other", false).unwrap();
+    let copied = store.copy("renamed", "other", 0, true).unwrap();
+    let copied_to_db = store.copy("renamed", "renamed", 1, false).unwrap();
+
+    // then
+    assert!(renamed && !not_renamed && copied && copied_to_db);
+    assert!(!store.contains_key("key"));
+    assert!(store.rename("key", "new", true).is_err());
+    assert!(store.get_expiry("renamed").is_some());
+    assert_eq!(store.get("other").unwrap().get_value_clone(), ObjectValue::String(StringValue::new("value".to_string())));
+    assert!(store.get_expiry("other").is_some());
+    assert!(["renamed", "other"].contains(&store.random_key().unwrap().as_str()));
+
+    store.select_db(1).unwrap();
+    assert_eq!(store.get("renamed").unwrap().get_value_clone(), ObjectValue::String(StringValue::new("value".to_string())));
+    store.select_db(2).unwrap();
+    assert_eq!(store.random_key(), None);
+}
+
+#[test]
+fn test_store_eviction_policies() {
+    // given
+    // the process always allocates more than one byte, so the store is always above the limit
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    store.put("volatile", ObjectValue::String(StringValue::new("value".to_string())), 10000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    store.set_maxmemory(1);
+
+    // when
+    let below_limit_noeviction = store.perform_evictions();
+    store.set_eviction_policy(EvictionPolicy::VolatileTTL);
+    let below_limit_volatile = store.perform_evictions();
+
+    // then
+    assert!(!below_limit_noeviction && !below_limit_volatile);
+    assert_eq!(store.get_maxmemory(), 1);
+    assert_eq!(store.get_eviction_policy(), EvictionPolicy::VolatileTTL);
+    assert!(store.contains_key("key"));
+    assert!(!store.contains_key("volatile"));
+
+    store.set_eviction_policy(EvictionPolicy::AllKeysRandom);
+    store.put("other", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    assert!(!store.contains_key("key"));
+    assert!(store.contains_key("other"));
+}
+
+#[test]
+fn test_store_lfu_access_counter() {
+    // given
+    // with a log factor of 0 every access increments the counter
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.set_eviction_policy(EvictionPolicy::AllKeysLFU);
+    store.set_lfu_configuration(LFUConfiguration { log_factor: 0, decay_time: 1 });
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+
+    // when
+    for _ in 0..3 {
+        store.get("key");
+    }
+
+    // then
+    let counter = lfu_eviction_strategy::get_decayed_counter(store.get_without_touch("key").unwrap().last_accessed_at, &store.get_lfu_configuration());
+    assert_eq!(counter, lfu_eviction_strategy::LFU_INIT_VAL + 3);
+
+    // a copy is a new key and starts with the initial counter
+    store.copy("key", "copy", 0, false).unwrap();
+    let copy_counter = lfu_eviction_strategy::get_decayed_counter(store.get_without_touch("copy").unwrap().last_accessed_at, &store.get_lfu_configuration());
+    assert_eq!(copy_counter, lfu_eviction_strategy::LFU_INIT_VAL);
+}
+
+#[test]
+fn test_store_sample_keys() {
+    // given
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    store.put("volatile", ObjectValue::String(StringValue::new("value".to_string())), 10000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+
+    // when
+    let samples = store.sample_keys(10, false);
+    let volatile_samples = store.sample_keys(10, true);
+
+    // then
+    assert_eq!(samples.len(), 10);
+    assert!(volatile_samples.iter().all(|key| key == "volatile"));
+    assert!(store.is_evictable("key", false));
+    assert!(!store.is_evictable("key", true));
+    assert!(!store.is_evictable("missing", false));
+}
+
+#[test]
+fn test_store_put_clears_expiration() {
+    // given
+    let mut store = Store::new(crate::config::DEFAULT_DATABASES);
+    store.put("key", ObjectValue::String(StringValue::new("value".to_string())), 10000, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+
+    // when
+    store.put("key", ObjectValue::String(StringValue::new("new".to_string())), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+
+    // then
+    assert_eq!(store.get_expiry("key"), None);
+    assert!(store.get_expiration_data().is_empty());
+}
+
+#[test]
+fn test_object_value_size() {
+    // given
+    let string_size = std::mem::size_of::<StringValue>();
+
+    // when
+    let object_value_size = std::mem::size_of::<ObjectValue>();
+
+    // then
+    // a string only pays for the enum tag
+    assert!(object_value_size <= string_size + 8, "ObjectValue has {} bytes", object_value_size);
+}
+
+#[test]
+fn test_store_search_skips_expired_keys() {
+    // given
+    let mut store = Store::new(1);
+    let args: Vec<String> = "idx ON HASH PREFIX 1 doc: SCHEMA vec VECTOR FLAT 6 TYPE FLOAT32 DIM 2 DISTANCE_METRIC L2"
+        .split_whitespace().map(|arg| arg.to_string()).collect();
+    store.create_search_index(IndexDefinition::parse(&args).unwrap()).unwrap();
+    for (key, vector, expiration_ms) in [("doc:1", "0,0", 1), ("doc:2", "1,1", -1), ("doc:3", "2,2", -1), ("doc:4", "3,3", -1)] {
+        let hash = HashMap::from([(String::from("vec"), String::from(vector))]);
+        store.put(key, ObjectValue::Hash(hash), expiration_ms, OBJ_TYPE_HASH | OBJ_ENCODING_HT);
+    }
+    std::thread::sleep(Duration::from_millis(5));
+
+    // when
+    let all = store.search("idx", &Query::parse("*", &HashMap::new()).unwrap()).unwrap();
+    store.put("doc:5", ObjectValue::Hash(HashMap::from([(String::from("vec"), String::from("0,0"))])), 1, OBJ_TYPE_HASH | OBJ_ENCODING_HT);
+    std::thread::sleep(Duration::from_millis(5));
+    let nearest = store.search("idx", &Query::parse("*=>[KNN 2 @vec [0,0]]", &HashMap::new()).unwrap()).unwrap();
+
+    // then
+    let keys = |results: Vec<SearchResult>| results.into_iter().map(|result| result.key).collect::<Vec<String>>();
+    assert_eq!(keys(all).len(), 3);
+    // the expired nearest neighbour is replaced by the next live key
+    assert_eq!(keys(nearest), vec!["doc:2", "doc:3"]);
+    assert!(!store.contains_key("doc:1") && !store.contains_key("doc:5"));
+}
+
+#[test]
+fn test_time_series_keys_are_tracked() {
+    // given
+    let mut store = Store::new(2);
+    let series = || ObjectValue::TimeSeries(Box::new(TimeSeries::new(0, DuplicatePolicy::Block, Vec::new())));
+    store.put("a", series(), -1, OBJ_TYPE_TIMESERIES | OBJ_ENCODING_RAW);
+    store.put("b", series(), -1, OBJ_TYPE_TIMESERIES | OBJ_ENCODING_RAW);
+    store.put("c", series(), -1, OBJ_TYPE_TIMESERIES | OBJ_ENCODING_RAW);
+    store.put("string", ObjectValue::String(StringValue::new(String::from("value"))), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+
+    // when
+    store.rename("a", "renamed", true).unwrap();
+    store.put("b", ObjectValue::String(StringValue::new(String::from("value"))), -1, OBJ_TYPE_STRING | OBJ_ENCODING_EMBSTR);
+    store.move_key("c", 1).unwrap();
+
+    // then
+    assert_eq!(store.get_time_series_keys(), &HashSet::from([String::from("renamed")]));
+    store.select_db(1).unwrap();
+    assert_eq!(store.get_time_series_keys(), &HashSet::from([String::from("c")]));
+    store.flush_db();
+    assert!(store.get_time_series_keys().is_empty());
+}
@@ -0,0 +1,113 @@
+/// Longest common subsequence of two strings, see https://redis.io/commands/lcs/
+///
+/// The lengths of the common subsequences of all prefixes are computed by dynamic programming in O(n * m) time and memory.
+/// Walking back from the end of both strings yields the subsequence and the ranges of both strings which match,
+/// so the matches are ordered from the end to the start of the strings, as Redis returns them.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LcsMatch {
+    // inclusive byte ranges of the match in the first and the second string
+    pub first: (usize, usize),
+    pub second: (usize, usize),
+}
+
+impl LcsMatch {
+    pub fn len(&self) -> usize {
+        self.first.1 - self.first.0 + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lcs {
+    pub subsequence: Vec<u8>,
+    // matches with at least the minimal match length
+    pub matches: Vec<LcsMatch>,
+}
+
+/// Number of bytes of the table of prefix lengths, used to reject strings which would need too much memory
+pub fn get_lcs_memory_usage(first: &[u8], second: &[u8]) -> u64 {
+    (first.len() as u64 + 1) * (second.len() as u64 + 1) * std::mem::size_of::<u32>() as u64
+}
+
+pub fn lcs(first: &[u8], second: &[u8], min_match_len: usize) -> Lcs {
+    // lengths[i][j] is the length of the longest common subsequence of first[..i] and second[..j]
+    let columns = second.len() + 1;
+    let mut lengths = vec![0u32; (first.len() + 1) * columns];
+    for i in 1..=first.len() {
+        for j in 1..=second.len() {
+            lengths[i * columns + j] = if first[i - 1] == second[j - 1] {
+                lengths[(i - 1) * columns + j - 1] + 1
+            } else {
+                lengths[(i - 1) * columns + j].max(lengths[i * columns + j - 1])
+            };
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(lengths[first.len() * columns + second.len()] as usize);
+    let mut matches = Vec::new();
+    // the current match, which grows towards the start of the strings
+    let mut current: Option<LcsMatch> = None;
+
+    let (mut i, mut j) = (first.len(), second.len());
+    while i > 0 && j > 0 {
+        if first[i - 1] == second[j - 1] {
+            subsequence.push(first[i - 1]);
+            current = match current.take() {
+                Some(range) if range.first.0 == i && range.second.0 == j => Some(LcsMatch { first: (i - 1, range.first.1), second: (j - 1, range.second.1) }),
+                Some(range) => {
+                    add_match(&mut matches, range, min_match_len);
+                    Some(LcsMatch { first: (i - 1, i - 1), second: (j - 1, j - 1) })
+                }
+                None => Some(LcsMatch { first: (i - 1, i - 1), second: (j - 1, j - 1) }),
+            };
+            i -= 1;
+            j -= 1;
+        } else {
+            if lengths[(i - 1) * columns + j] > lengths[i * columns + j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+            if let Some(range) = current.take() {
+                add_match(&mut matches, range, min_match_len);
+            }
+        }
+    }
+    if let Some(range) = current.take() {
+        add_match(&mut matches, range, min_match_len);
+    }
+
+    subsequence.reverse();
+    Lcs { subsequence, matches }
+}
+
+fn add_match(matches: &mut Vec<LcsMatch>, range: LcsMatch, min_match_len: usize) {
+    if range.len() >= min_match_len {
+        matches.push(range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcs() {
+        // given
+        let first = b"ohmytext";
+        let second = b"mynewtext";
+
+        // when
+        let result = lcs(first, second, 0);
+        let long_matches = lcs(first, second, 4);
+
+        // then
+        assert_eq!(result.subsequence, b"mytext".to_vec());
+        assert_eq!(result.matches, vec![
+            LcsMatch { first: (4, 7), second: (5, 8) },
+            LcsMatch { first: (2, 3), second: (0, 1) },
+        ]);
+        assert_eq!(long_matches.matches.len(), 1);
+        assert_eq!(lcs(b"", b"abc", 0).subsequence, Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod string_helper;
+pub mod lcs;
@@ -0,0 +1,162 @@
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::object_type_encoding::OBJ_TYPE_STRING;
+use crate::resp::DataType;
+use crate::resp::DataType::{BulkString, Error, Integer, Null};
+use crate::store::{ObjectValue, Store};
+use crate::string_value::StringValue;
+
+/// Shared functions of the string commands
+
+/// Looks up the string stored at key. Returns an error if the key holds a value of another type
+pub fn get_string(store: &mut Store, key: &str) -> Result<Option<String>, DataType> {
+    match store.get(key) {
+        Some(store_object) => match store_object.value.as_ref() {
+            ObjectValue::String(string) => Ok(Some(string.as_str().into_owned())),
+            _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Stores the string with the most compact encoding, an existing value and its expiration are replaced
+pub fn put_string(store: &mut Store, key: &str, value: String, expires_at: Option<i64>) {
+    let value = StringValue::new(value);
+    let string_encoding = value.get_encoding();
+    store.put_with_expiry(key, ObjectValue::String(value), expires_at, OBJ_TYPE_STRING | string_encoding);
+}
+
+/// Bulk string of the value or null if there is no value
+pub fn to_bulk_string(value: Option<String>) -> DataType {
+    match value {
+        Some(value) => BulkString(value),
+        None => Null,
+    }
+}
+
+/// Expiration options of SET and GETEX
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expiration {
+    // unix time in milliseconds, set by EX, PX, EXAT or PXAT
+    At(i64),
+    KeepTTL,
+    Persist,
+}
+
+/// Parses the value of the EX, PX, EXAT or PXAT option into a unix time in milliseconds.
+/// Returns None if the option is not an expiration option
+pub fn parse_expiration(option: &str, value: Option<&String>, command_name: &str) -> Option<Result<i64, DataType>> {
+    let (multiplier, absolute) = match option.to_uppercase().as_str() {
+        "EX" => (1000, false),
+        "PX" => (1, false),
+        "EXAT" => (1000, true),
+        "PXAT" => (1, true),
+        _ => return None,
+    };
+
+    let time = match value.map(|value| value.parse::<i64>()) {
+        Some(Ok(time)) => time,
+        Some(Err(_)) => return Some(Err(Error(String::from("ERR value is not an integer or out of range")))),
+        None => return Some(Err(Error(String::from("ERR syntax error")))),
+    };
+
+    let now = if absolute { 0 } else { chrono::Utc::now().timestamp_millis() };
+    let expires_at = time.checked_mul(multiplier).and_then(|time| time.checked_add(now)).filter(|_| time > 0);
+    Some(expires_at.ok_or_else(|| Error(format!("ERR invalid expire time in '{}' command", command_name))))
+}
+
+/// Adds the increment to the integer stored at key, a missing key counts as 0. Shared by INCR, INCRBY, DECR and DECRBY
+pub fn increment(store: &mut Store, key: &str, increment: i64) -> DataType {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            let value = match store_object.value.as_ref() {
+                ObjectValue::String(string) => match string.to_int() {
+                    Some(value) => value,
+                    None => return Error(String::from("ERR value is not an integer or out of range")),
+                },
+                _ => return Error(String::from(WRONG_TYPE_ERROR)),
+            };
+            let new_value = match value.checked_add(increment) {
+                Some(new_value) => new_value,
+                None => return Error(String::from("ERR increment or decrement would overflow")),
+            };
+            // results in the range of the shared integers reference the shared object again, the expiration is kept
+            store_object.set_value(ObjectValue::String(StringValue::Int(new_value)));
+            Integer(new_value)
+        }
+        None => {
+            put_string(store, key, increment.to_string(), None);
+            Integer(increment)
+        }
+    }
+}
+
+/// Returns the bytes between start and end (both inclusive), negative offsets count from the end of the string.
+/// See GETRANGE
+pub fn get_range(value: &str, start: i64, end: i64) -> String {
+    let length = value.len() as i64;
+    let start = if start < 0 { (length + start).max(0) } else { start };
+    let end = if end < 0 { (length + end).max(0) } else { end.min(length - 1) };
+    if length == 0 || start > end {
+        return String::new();
+    }
+
+    // a range can cut a multi-byte character, like SETRANGE invalid UTF-8 is replaced
+    String::from_utf8_lossy(&value.as_bytes()[start as usize..=end as usize]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+
+    #[test]
+    fn test_increment_modified_strings() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET k 1");
+        execute(&mut handler, &mut store, "APPEND k 0");
+        execute(&mut handler, &mut store, "SETRANGE s 0 5");
+        execute(&mut handler, &mut store, "SET padded 01");
+
+        // when
+        let appended = execute(&mut handler, &mut store, "INCR k");
+        let set_range = execute(&mut handler, &mut store, "INCR s");
+        let padded = execute(&mut handler, &mut store, "INCR padded");
+
+        // then
+        assert_eq!(appended, Integer(11));
+        assert_eq!(set_range, Integer(6));
+        assert_eq!(padded, Error(String::from("ERR value is not an integer or out of range")));
+    }
+
+    #[test]
+    fn test_get_range() {
+        assert_eq!(get_range("This is a string", 0, 3), "This");
+        assert_eq!(get_range("This is a string", -3, -1), "ing");
+        assert_eq!(get_range("This is a string", 0, -1), "This is a string");
+        assert_eq!(get_range("This is a string", 10, 100), "string");
+        assert_eq!(get_range("This is a string", 5, 3), "");
+        assert_eq!(get_range("", 0, -1), "");
+    }
+
+    #[test]
+    fn test_parse_expiration() {
+        // given
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // when
+        let ex = parse_expiration("ex", Some(&String::from("10")), "set").unwrap().unwrap_or(0);
+        let pxat = parse_expiration("PXAT", Some(&String::from("1000")), "set").unwrap().unwrap_or(0);
+
+        // then
+        assert!(ex >= now + 10000 && ex < now + 11000);
+        assert_eq!(pxat, 1000);
+        assert!(parse_expiration("NX", None, "set").is_none());
+        assert!(parse_expiration("EX", Some(&String::from("0")), "set").unwrap().is_err());
+        assert!(parse_expiration("EX", Some(&String::from("abc")), "set").unwrap().is_err());
+        assert!(parse_expiration("EX", Some(&i64::MAX.to_string()), "set").unwrap().is_err());
+        assert!(parse_expiration("EX", None, "set").unwrap().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+
+use crate::object_type_encoding::{OBJ_ENCODING_EMBSTR, OBJ_ENCODING_INT, OBJ_ENCODING_RAW};
+
+/// Value of a string object, the variant is the actual encoding reported by OBJECT ENCODING
+///
+/// int      integers which can be represented as i64 without losing their text form (e.g. "42" but not "042")
+/// embstr   strings shorter than 44 bytes, stored inline in the object without a second allocation
+/// raw      all other strings, stored on the heap
+///
+/// Modifying a string (APPEND, SETRANGE) converts it to raw, as Redis does, so repeated appends do not copy the value
+/// between encodings.
+
+pub const EMBSTR_SIZE_LIMIT: usize = 44;
+
+// maximum size of a string created by SETRANGE, same as proto-max-bulk-len in Redis
+pub const STRING_MAX_SIZE: usize = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringValue {
+    Int(i64),
+    Embedded { len: u8, bytes: [u8; EMBSTR_SIZE_LIMIT - 1] },
+    Raw(String),
+}
+
+impl StringValue {
+    /// Chooses the most compact encoding for the value
+    pub fn new(value: String) -> Self {
+        if let Some(int) = parse_int(&value) {
+            return StringValue::Int(int);
+        }
+
+        if value.len() < EMBSTR_SIZE_LIMIT {
+            let mut bytes = [0; EMBSTR_SIZE_LIMIT - 1];
+            bytes[..value.len()].copy_from_slice(value.as_bytes());
+            return StringValue::Embedded { len: value.len() as u8, bytes };
+        }
+
+        StringValue::Raw(value)
+    }
+
+    pub fn get_encoding(&self) -> u8 {
+        match self {
+            StringValue::Int(_) => OBJ_ENCODING_INT,
+            StringValue::Embedded { .. } => OBJ_ENCODING_EMBSTR,
+            StringValue::Raw(_) => OBJ_ENCODING_RAW,
+        }
+    }
+
+    pub fn as_str(&self) -> Cow<'_, str> {
+        match self {
+            StringValue::Int(int) => Cow::Owned(int.to_string()),
+            StringValue::Embedded { len, bytes } => {
+                Cow::Borrowed(std::str::from_utf8(&bytes[..*len as usize]).expect("Embedded string is not valid UTF-8"))
+            }
+            StringValue::Raw(string) => Cow::Borrowed(string),
+        }
+    }
+
+    /// The value as integer, strings modified by APPEND or SETRANGE are raw but can still hold an integer
+    pub fn to_int(&self) -> Option<i64> {
+        match self {
+            StringValue::Int(int) => Some(*int),
+            _ => parse_int(&self.as_str()),
+        }
+    }
+
+    pub fn get_length(&self) -> usize {
+        match self {
+            StringValue::Int(int) => int.to_string().len(),
+            StringValue::Embedded { len, .. } => *len as usize,
+            StringValue::Raw(string) => string.len(),
+        }
+    }
+
+    /// Heap memory used by the value, int and embstr values are stored inline
+    pub fn get_memory_usage(&self) -> u64 {
+        match self {
+            StringValue::Raw(string) => string.capacity() as u64,
+            _ => 0,
+        }
+    }
+
+    /// Appends the value and returns the new length, see APPEND
+    pub fn append(&mut self, value: &str) -> usize {
+        let string = self.make_raw();
+        string.push_str(value);
+        string.len()
+    }
+
+    /// Overwrites the string at the byte offset and returns the new length, missing bytes are padded with zero bytes.
+    /// See SETRANGE
+    pub fn set_range(&mut self, offset: usize, value: &str) -> Result<usize, String> {
+        if offset + value.len() > STRING_MAX_SIZE {
+            return Err(String::from("string exceeds maximum allowed size (proto-max-bulk-len)"));
+        }
+        if value.is_empty() {
+            return Ok(self.get_length());
+        }
+
+        let string = self.make_raw();
+        let mut bytes = std::mem::take(string).into_bytes();
+        if bytes.len() < offset + value.len() {
+            bytes.resize(offset + value.len(), 0);
+        }
+        bytes[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+
+        // overwriting part of a multi-byte character can leave invalid UTF-8 behind
+        *string = match String::from_utf8(bytes) {
+            Ok(string) => string,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        };
+        Ok(string.len())
+    }
+
+    /// Converts the value to the raw encoding and returns the heap string
+    fn make_raw(&mut self) -> &mut String {
+        if !matches!(self, StringValue::Raw(_)) {
+            *self = StringValue::Raw(self.as_str().into_owned());
+        }
+
+        match self {
+            StringValue::Raw(string) => string,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Parses the value as integer if it keeps its exact text form, e.g. "+1" or "01" stay strings
+fn parse_int(value: &str) -> Option<i64> {
+    if value.is_empty() || value.len() > 20 {
+        return None;
+    }
+
+    let int = value.parse::<i64>().ok()?;
+    if int.to_string() != value {
+        return None;
+    }
+    Some(int)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding() {
+        // given
+        let int = StringValue::new(String::from("-123"));
+        let not_int = StringValue::new(String::from("0123"));
+        let embedded = StringValue::new(String::from("value"));
+        let raw = StringValue::new("a".repeat(EMBSTR_SIZE_LIMIT));
+
+        // then
+        assert_eq!(int, StringValue::Int(-123));
+        assert_eq!(int.as_str(), "-123");
+        assert_eq!(not_int.get_encoding(), OBJ_ENCODING_EMBSTR);
+        assert_eq!(embedded.get_encoding(), OBJ_ENCODING_EMBSTR);
+        assert_eq!(embedded.as_str(), "value");
+        assert_eq!(embedded.get_memory_usage(), 0);
+        assert_eq!(raw.get_encoding(), OBJ_ENCODING_RAW);
+        assert_eq!(raw.get_length(), EMBSTR_SIZE_LIMIT);
+    }
+
+    #[test]
+    fn test_append_converts_to_raw() {
+        // given
+        let mut value = StringValue::new(String::from("12"));
+
+        // when
+        let len = value.append("3");
+
+        // then
+        assert_eq!(len, 3);
+        assert_eq!(value, StringValue::Raw(String::from("123")));
+    }
+
+    #[test]
+    fn test_set_range() {
+        // given
+        let mut value = StringValue::new(String::from("Hello World"));
+
+        // when
+        let len = value.set_range(6, "Redis").unwrap();
+        let padded_len = StringValue::new(String::new()).set_range(2, "a").unwrap();
+
+        // then
+        assert_eq!(len, 11);
+        assert_eq!(value.as_str(), "Hello Redis");
+        assert_eq!(value.get_encoding(), OBJ_ENCODING_RAW);
+        assert_eq!(padded_len, 3);
+        assert!(value.set_range(STRING_MAX_SIZE, "a").is_err());
+    }
+}
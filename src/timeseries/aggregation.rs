@@ -0,0 +1,109 @@
+/// Aggregation functions of TS.RANGE, TS.MRANGE and compaction rules
+/// see: https://redis.io/commands/ts.range/
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    Count,
+    First,
+    Last,
+}
+
+impl Aggregation {
+    pub fn parse(name: &str) -> Option<Aggregation> {
+        match name.to_lowercase().as_str() {
+            "avg" => Some(Aggregation::Avg),
+            "sum" => Some(Aggregation::Sum),
+            "min" => Some(Aggregation::Min),
+            "max" => Some(Aggregation::Max),
+            "count" => Some(Aggregation::Count),
+            "first" => Some(Aggregation::First),
+            "last" => Some(Aggregation::Last),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregation::Avg => "avg",
+            Aggregation::Sum => "sum",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::Count => "count",
+            Aggregation::First => "first",
+            Aggregation::Last => "last",
+        }
+    }
+
+    /// Aggregates the values of one bucket, the values are ordered by timestamp
+    pub fn aggregate(&self, values: &[f64]) -> f64 {
+        match self {
+            Aggregation::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Sum => values.iter().sum(),
+            Aggregation::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::Count => values.len() as f64,
+            Aggregation::First => values.first().cloned().unwrap_or(f64::NAN),
+            Aggregation::Last => values.last().cloned().unwrap_or(f64::NAN),
+        }
+    }
+}
+
+/// Groups the samples into buckets of `bucket_duration` milliseconds (aligned to timestamp 0)
+/// and returns the start timestamp and aggregated value of every non-empty bucket
+pub fn aggregate_samples<'a, I>(samples: I, aggregation: Aggregation, bucket_duration: u64) -> Vec<(i64, f64)>
+where
+    I: Iterator<Item=(&'a i64, &'a f64)>,
+{
+    let mut result = Vec::new();
+    let mut current_bucket: Option<i64> = None;
+    let mut values = Vec::new();
+
+    for (timestamp, value) in samples {
+        let bucket = get_bucket_start(*timestamp, bucket_duration);
+
+        if current_bucket != Some(bucket) {
+            if let Some(start) = current_bucket {
+                result.push((start, aggregation.aggregate(&values)));
+                values.clear();
+            }
+            current_bucket = Some(bucket);
+        }
+        values.push(*value);
+    }
+
+    if let Some(start) = current_bucket {
+        result.push((start, aggregation.aggregate(&values)));
+    }
+
+    result
+}
+
+pub fn get_bucket_start(timestamp: i64, bucket_duration: u64) -> i64 {
+    timestamp - timestamp.rem_euclid(bucket_duration as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_samples() {
+        // given
+        let samples = [(0, 1.0), (5, 3.0), (10, 2.0), (25, 4.0), (29, 6.0)];
+
+        // when
+        let avg = aggregate_samples(samples.iter().map(|(t, v)| (t, v)), Aggregation::Avg, 10);
+        let count = aggregate_samples(samples.iter().map(|(t, v)| (t, v)), Aggregation::Count, 10);
+        let first = aggregate_samples(samples.iter().map(|(t, v)| (t, v)), Aggregation::First, 100);
+
+        // then
+        assert_eq!(avg, vec![(0, 2.0), (10, 2.0), (20, 5.0)]);
+        assert_eq!(count, vec![(0, 2.0), (10, 1.0), (20, 2.0)]);
+        assert_eq!(first, vec![(0, 1.0)]);
+        assert_eq!(get_bucket_start(-5, 10), -10);
+    }
+}
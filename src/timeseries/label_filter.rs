@@ -0,0 +1,95 @@
+use crate::timeseries::time_series::TimeSeries;
+
+/// Label filters of TS.MRANGE
+/// see: https://redis.io/commands/ts.mrange/
+///
+/// label=value         label equals value
+/// label!=value        label does not equal value
+/// label=              the series does not have the label
+/// label!=             the series has the label
+/// label=(v1,v2,...)   label equals one of the values
+/// label!=(v1,v2,...)  label equals none of the values
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelFilter {
+    label: String,
+    // an empty list matches series without the label
+    values: Vec<String>,
+    negated: bool,
+}
+
+impl LabelFilter {
+    pub fn parse(filter: &str) -> Result<LabelFilter, String> {
+        let (label, value, negated) = match filter.find("!=") {
+            Some(position) => (&filter[..position], &filter[position + 2..], true),
+            None => match filter.find('=') {
+                Some(position) => (&filter[..position], &filter[position + 1..], false),
+                None => return Err(String::from("ERR TSDB: failed parsing labels")),
+            },
+        };
+
+        if label.is_empty() {
+            return Err(String::from("ERR TSDB: failed parsing labels"));
+        }
+
+        let values = if value.starts_with('(') && value.ends_with(')') && value.len() >= 2 {
+            value[1..value.len() - 1].split(',').map(|value| value.trim().to_string()).filter(|value| !value.is_empty()).collect()
+        } else if value.is_empty() {
+            Vec::new()
+        } else {
+            vec![value.to_string()]
+        };
+
+        Ok(LabelFilter { label: label.to_string(), values, negated })
+    }
+
+    /// Positive filters (label=value) select series, a query needs at least one of them
+    pub fn is_positive(&self) -> bool {
+        !self.negated && !self.values.is_empty()
+    }
+
+    pub fn matches(&self, series: &TimeSeries) -> bool {
+        let matches = match series.get_label(&self.label) {
+            Some(value) => self.values.iter().any(|expected| expected == value),
+            None => self.values.is_empty(),
+        };
+        matches != self.negated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::time_series::DuplicatePolicy;
+
+    fn series_with_labels(labels: &[(&str, &str)]) -> TimeSeries {
+        let labels = labels.iter().map(|(label, value)| (label.to_string(), value.to_string())).collect();
+        TimeSeries::new(0, DuplicatePolicy::Block, labels)
+    }
+
+    #[test]
+    fn test_matches() {
+        // given
+        let series = series_with_labels(&[("sensor", "temperature"), ("area", "32")]);
+
+        // then
+        assert!(LabelFilter::parse("sensor=temperature").unwrap().matches(&series));
+        assert!(!LabelFilter::parse("sensor!=temperature").unwrap().matches(&series));
+        assert!(LabelFilter::parse("area=(31,32)").unwrap().matches(&series));
+        assert!(!LabelFilter::parse("area!=(31,32)").unwrap().matches(&series));
+        assert!(LabelFilter::parse("room=").unwrap().matches(&series));
+        assert!(!LabelFilter::parse("area=").unwrap().matches(&series));
+        assert!(LabelFilter::parse("area!=").unwrap().matches(&series));
+        assert!(LabelFilter::parse("room!=kitchen").unwrap().matches(&series));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(LabelFilter::parse("sensor=temperature").unwrap().is_positive());
+        assert!(LabelFilter::parse("area=(31,32)").unwrap().is_positive());
+        assert!(!LabelFilter::parse("sensor!=temperature").unwrap().is_positive());
+        assert!(!LabelFilter::parse("sensor=").unwrap().is_positive());
+        assert!(LabelFilter::parse("sensor").is_err());
+        assert!(LabelFilter::parse("=value").is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod aggregation;
+pub mod time_series;
+pub mod label_filter;
+pub mod retention;
+pub mod timeseries_helper;
@@ -0,0 +1,48 @@
+use crate::store::{ObjectValue, Store};
+
+/// Removes the samples of all time series of the selected database which are older than their retention period.
+/// Called by the active expiration cycle, returns the number of removed samples
+pub fn enforce_retention(store: &mut Store) -> usize {
+    let mut removed_samples = 0;
+
+    // only the keys holding a time series are visited, not the whole keyspace
+    let keys: Vec<String> = store.get_time_series_keys().iter().cloned().collect();
+    for key in keys {
+        let store_object = store.get_data_mut().get_mut(&key);
+        if let Some(ObjectValue::TimeSeries(series)) = store_object.and_then(|store_object| store_object.get_value_mut_if(|value| matches!(value, ObjectValue::TimeSeries(_)))) {
+            removed_samples += series.trim();
+        }
+    }
+
+    removed_samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::handler::CommandHandler;
+    use crate::cmd::test_helper::execute;
+    use crate::resp::DataType;
+
+    #[test]
+    fn test_enforce_retention_keeps_shared_objects() {
+        // given
+        let mut handler = CommandHandler::new();
+        let mut store = Store::new(1);
+        execute(&mut handler, &mut store, "SET counter 12");
+        execute(&mut handler, &mut store, "TS.CREATE series RETENTION 100");
+        execute(&mut handler, &mut store, "TS.ADD series 1000 1");
+        execute(&mut handler, &mut store, "TS.ADD series 2000 2");
+        let ref_count = execute(&mut handler, &mut store, "OBJECT REFCOUNT counter");
+
+        // when
+        enforce_retention(&mut store);
+
+        // then
+        match execute(&mut handler, &mut store, "TS.RANGE series - +") {
+            DataType::Array(samples) => assert_eq!(samples.len(), 1),
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert_eq!(execute(&mut handler, &mut store, "OBJECT REFCOUNT counter"), ref_count);
+    }
+}
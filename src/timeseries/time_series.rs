@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::timeseries::aggregation::{get_bucket_start, Aggregation};
+
+/// Time series implementation
+/// see: https://redis.io/docs/data-types/timeseries/
+///
+/// Samples (timestamp in milliseconds, value) are stored ordered by timestamp in a BTreeMap,
+/// so that range queries and inserting out-of-order samples are O(log n).
+///
+/// Retention: samples older than `retention_ms` relative to the newest sample are removed by the active expiration cycle.
+///
+/// Compaction: a rule aggregates the samples of the source series into buckets and adds the result to a destination series.
+/// A bucket is written to the destination once the first sample of a newer bucket arrives.
+/// Samples which are added to an already closed bucket update the aggregated value of the bucket in the destination.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    Block,
+    First,
+    Last,
+    Min,
+    Max,
+    Sum,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactionRule {
+    pub destination_key: String,
+    pub aggregation: Aggregation,
+    pub bucket_duration: u64,
+    // start timestamp of the bucket which is not yet written to the destination
+    current_bucket: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    samples: BTreeMap<i64, f64>,
+    pub retention_ms: u64,
+    pub duplicate_policy: DuplicatePolicy,
+    pub labels: Vec<(String, String)>,
+    pub rules: Vec<CompactionRule>,
+    // key of the series this series is the compaction destination of
+    pub source_key: Option<String>,
+}
+
+/// A sample which needs to be added to the destination of a compaction rule
+#[derive(Debug, PartialEq)]
+pub struct CompactedSample {
+    pub destination_key: String,
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+impl DuplicatePolicy {
+    pub fn parse(name: &str) -> Option<DuplicatePolicy> {
+        match name.to_uppercase().as_str() {
+            "BLOCK" => Some(DuplicatePolicy::Block),
+            "FIRST" => Some(DuplicatePolicy::First),
+            "LAST" => Some(DuplicatePolicy::Last),
+            "MIN" => Some(DuplicatePolicy::Min),
+            "MAX" => Some(DuplicatePolicy::Max),
+            "SUM" => Some(DuplicatePolicy::Sum),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DuplicatePolicy::Block => "BLOCK",
+            DuplicatePolicy::First => "FIRST",
+            DuplicatePolicy::Last => "LAST",
+            DuplicatePolicy::Min => "MIN",
+            DuplicatePolicy::Max => "MAX",
+            DuplicatePolicy::Sum => "SUM",
+        }
+    }
+
+    fn resolve(&self, existing: f64, new: f64) -> Result<f64, String> {
+        match self {
+            DuplicatePolicy::Block => Err(String::from("ERR TSDB: Error at upsert, update is not supported when DUPLICATE_POLICY is set to BLOCK mode")),
+            DuplicatePolicy::First => Ok(existing),
+            DuplicatePolicy::Last => Ok(new),
+            DuplicatePolicy::Min => Ok(existing.min(new)),
+            DuplicatePolicy::Max => Ok(existing.max(new)),
+            DuplicatePolicy::Sum => Ok(existing + new),
+        }
+    }
+}
+
+impl CompactionRule {
+    pub fn new(destination_key: &str, aggregation: Aggregation, bucket_duration: u64) -> Self {
+        CompactionRule {
+            destination_key: destination_key.to_string(),
+            aggregation,
+            bucket_duration,
+            current_bucket: None,
+        }
+    }
+}
+
+impl TimeSeries {
+    pub fn new(retention_ms: u64, duplicate_policy: DuplicatePolicy, labels: Vec<(String, String)>) -> Self {
+        TimeSeries {
+            samples: BTreeMap::new(),
+            retention_ms,
+            duplicate_policy,
+            labels,
+            rules: Vec::new(),
+            source_key: None,
+        }
+    }
+
+    /// Adds the sample and returns the samples which need to be added to the destinations of the compaction rules.
+    /// The policy overrides the duplicate policy of the series (ON_DUPLICATE option of TS.ADD)
+    pub fn add(&mut self, timestamp: i64, value: f64, policy: Option<DuplicatePolicy>) -> Result<Vec<CompactedSample>, String> {
+        if let Some(last_timestamp) = self.get_last_timestamp() {
+            if self.retention_ms > 0 && timestamp < last_timestamp.saturating_sub(self.retention_ms as i64) {
+                return Err(String::from("ERR TSDB: Timestamp is older than retention"));
+            }
+        }
+
+        let value = match self.samples.get(&timestamp) {
+            Some(existing) => policy.unwrap_or(self.duplicate_policy).resolve(*existing, value)?,
+            None => value,
+        };
+        self.samples.insert(timestamp, value);
+
+        Ok(self.compact(timestamp))
+    }
+
+    fn compact(&mut self, timestamp: i64) -> Vec<CompactedSample> {
+        let mut compacted = Vec::new();
+
+        for i in 0..self.rules.len() {
+            let rule = &self.rules[i];
+            let bucket = get_bucket_start(timestamp, rule.bucket_duration);
+
+            let closed_bucket = match rule.current_bucket {
+                // a new bucket started, so the previous bucket is complete
+                Some(current) if bucket > current => {
+                    self.rules[i].current_bucket = Some(bucket);
+                    Some(current)
+                }
+                // the sample belongs to a bucket which was already written, so we update it
+                Some(current) if bucket < current => Some(bucket),
+                Some(_) => None,
+                None => {
+                    self.rules[i].current_bucket = Some(bucket);
+                    None
+                }
+            };
+
+            if let Some(start) = closed_bucket {
+                let rule = &self.rules[i];
+                let end = start.saturating_add(rule.bucket_duration as i64 - 1);
+                let values: Vec<f64> = self.samples.range(start..=end).map(|(_, value)| *value).collect();
+                if !values.is_empty() {
+                    compacted.push(CompactedSample {
+                        destination_key: rule.destination_key.clone(),
+                        timestamp: start,
+                        value: rule.aggregation.aggregate(&values),
+                    });
+                }
+            }
+        }
+
+        compacted
+    }
+
+    /// Removes all samples which are older than the retention period. Returns the number of removed samples
+    pub fn trim(&mut self) -> usize {
+        let last_timestamp = match self.get_last_timestamp() {
+            Some(last_timestamp) if self.retention_ms > 0 => last_timestamp,
+            _ => return 0,
+        };
+
+        let min_timestamp = last_timestamp.saturating_sub(self.retention_ms as i64);
+        let retained = self.samples.split_off(&min_timestamp);
+        let removed = self.samples.len();
+        self.samples = retained;
+        removed
+    }
+
+    pub fn range(&self, range: RangeInclusive<i64>) -> impl DoubleEndedIterator<Item=(&i64, &f64)> {
+        self.samples.range(range)
+    }
+
+    pub fn get_number_of_samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn get_last_sample(&self) -> Option<(i64, f64)> {
+        self.samples.iter().next_back().map(|(timestamp, value)| (*timestamp, *value))
+    }
+
+    pub fn get_last_timestamp(&self) -> Option<i64> {
+        self.samples.keys().next_back().cloned()
+    }
+
+    pub fn get_label(&self, name: &str) -> Option<&str> {
+        self.labels.iter().find(|(label, _)| label == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Estimated number of bytes used by the time series
+    pub fn get_memory_usage(&self) -> u64 {
+        let sample_size = std::mem::size_of::<(i64, f64)>();
+        let labels_size: usize = self.labels.iter().map(|(label, value)| label.capacity() + value.capacity() + std::mem::size_of::<(String, String)>()).sum();
+        let rules_size: usize = self.rules.iter().map(|rule| rule.destination_key.capacity() + std::mem::size_of::<CompactionRule>()).sum();
+        (self.samples.len() * sample_size + labels_size + rules_size + std::mem::size_of::<TimeSeries>()) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_with_duplicate_policy() {
+        // given
+        let mut series = TimeSeries::new(0, DuplicatePolicy::Block, Vec::new());
+        series.add(10, 1.0, None).unwrap();
+
+        // when
+        let blocked = series.add(10, 2.0, None);
+        let summed = series.add(10, 5.0, Some(DuplicatePolicy::Sum));
+
+        // then
+        assert!(blocked.is_err());
+        assert!(summed.is_ok());
+        assert_eq!(series.get_last_sample(), Some((10, 6.0)));
+    }
+
+    #[test]
+    fn test_retention() {
+        // given
+        let mut series = TimeSeries::new(100, DuplicatePolicy::Last, Vec::new());
+        for timestamp in (0..=300).step_by(50) {
+            series.add(timestamp, 1.0, None).unwrap();
+        }
+
+        // when
+        let too_old = series.add(150, 1.0, None);
+        let removed = series.trim();
+
+        // then
+        assert!(too_old.is_err());
+        assert_eq!(removed, 4);
+        assert_eq!(series.range(i64::MIN..=i64::MAX).map(|(timestamp, _)| *timestamp).collect::<Vec<_>>(), vec![200, 250, 300]);
+    }
+
+    #[test]
+    fn test_compaction() {
+        // given
+        let mut series = TimeSeries::new(0, DuplicatePolicy::Last, Vec::new());
+        series.rules.push(CompactionRule::new("dest", Aggregation::Sum, 10));
+
+        // when
+        let first = series.add(1, 1.0, None).unwrap();
+        let second = series.add(5, 2.0, None).unwrap();
+        let third = series.add(12, 4.0, None).unwrap();
+        let late = series.add(7, 3.0, None).unwrap();
+
+        // then
+        assert!(first.is_empty());
+        assert!(second.is_empty());
+        assert_eq!(third, vec![CompactedSample { destination_key: String::from("dest"), timestamp: 0, value: 3.0 }]);
+        assert_eq!(late, vec![CompactedSample { destination_key: String::from("dest"), timestamp: 0, value: 6.0 }]);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::cmd::command::WRONG_TYPE_ERROR;
+use crate::object_type_encoding::{OBJ_ENCODING_RAW, OBJ_TYPE_TIMESERIES};
+use crate::resp::DataType;
+use crate::resp::DataType::{Array, BulkString, Error, Integer};
+use crate::store::{ObjectValue, Store};
+use crate::timeseries::aggregation::{aggregate_samples, Aggregation};
+use crate::timeseries::label_filter::LabelFilter;
+use crate::timeseries::time_series::{DuplicatePolicy, TimeSeries};
+
+/// Shared lookup, parsing and response functions of the time series (TS.*) commands
+
+pub const KEY_NOT_FOUND_ERROR: &str = "ERR TSDB: the key does not exist";
+pub const KEY_EXISTS_ERROR: &str = "ERR TSDB: key already exists";
+
+/// Options of TS.CREATE which can also be passed to TS.ADD and TS.INCRBY to create a missing series
+pub struct CreateOptions {
+    pub retention_ms: u64,
+    pub duplicate_policy: DuplicatePolicy,
+    pub labels: Vec<(String, String)>,
+    // ON_DUPLICATE option of TS.ADD
+    pub on_duplicate: Option<DuplicatePolicy>,
+}
+
+/// Options of TS.RANGE, TS.REVRANGE and TS.MRANGE
+pub struct RangeOptions {
+    pub count: Option<usize>,
+    pub aggregation: Option<(Aggregation, u64)>,
+    // only supported by TS.MRANGE
+    pub with_labels: bool,
+    pub filters: Vec<LabelFilter>,
+}
+
+/// Looks up the time series stored at key. Returns an error if the key holds a value of another type
+pub fn get_time_series<'a>(store: &'a mut Store, key: &str) -> Result<Option<&'a mut TimeSeries>, DataType> {
+    match store.get_mut(key) {
+        Some(store_object) => {
+            match store_object.get_value_mut_if(|value| matches!(value, ObjectValue::TimeSeries(_))) {
+                Some(ObjectValue::TimeSeries(series)) => Ok(Some(series)),
+                _ => Err(Error(String::from(WRONG_TYPE_ERROR))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn create_time_series(store: &mut Store, key: &str, options: &CreateOptions) {
+    let series = TimeSeries::new(options.retention_ms, options.duplicate_policy, options.labels.clone());
+    store.put(key, ObjectValue::TimeSeries(Box::new(series)), -1, OBJ_TYPE_TIMESERIES | OBJ_ENCODING_RAW);
+}
+
+/// Adds the sample to the series and the resulting compacted samples to the destination series of the compaction rules
+pub fn add_sample(store: &mut Store, key: &str, timestamp: i64, value: f64, policy: Option<DuplicatePolicy>) -> Result<(), DataType> {
+    let series = match get_time_series(store, key)? {
+        Some(series) => series,
+        None => return Err(Error(String::from(KEY_NOT_FOUND_ERROR))),
+    };
+
+    let compacted_samples = series.add(timestamp, value, policy).map_err(Error)?;
+
+    // compacted values of a bucket are replaced when late samples arrive, so the destination always takes the last value.
+    // Destinations which were deleted in the meantime are skipped
+    for sample in compacted_samples {
+        match add_sample(store, &sample.destination_key, sample.timestamp, sample.value, Some(DuplicatePolicy::Last)) {
+            Err(Error(message)) if message == KEY_NOT_FOUND_ERROR => {}
+            result => result?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses RETENTION, DUPLICATE_POLICY, LABELS and (if allowed) ON_DUPLICATE options.
+/// LABELS consumes all remaining arguments, so it has to be the last option
+pub fn parse_create_options(args: &[String], allow_on_duplicate: bool) -> Result<CreateOptions, DataType> {
+    let mut options = CreateOptions {
+        retention_ms: 0,
+        duplicate_policy: DuplicatePolicy::Block,
+        labels: Vec::new(),
+        on_duplicate: None,
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        let option = args[i].to_uppercase();
+
+        if option == "LABELS" {
+            let labels = &args[i + 1..];
+            if labels.is_empty() || labels.len() % 2 != 0 {
+                return Err(Error(String::from("ERR TSDB: wrong number of labels")));
+            }
+            options.labels = labels.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect();
+            break;
+        }
+
+        let value = match args.get(i + 1) {
+            Some(value) => value,
+            None => return Err(Error(String::from("ERR syntax error"))),
+        };
+
+        match option.as_str() {
+            "RETENTION" => {
+                options.retention_ms = value.parse::<u64>().map_err(|_| Error(String::from("ERR TSDB: invalid retention value")))?;
+            }
+            "DUPLICATE_POLICY" => {
+                options.duplicate_policy = parse_duplicate_policy(value)?;
+            }
+            "ON_DUPLICATE" if allow_on_duplicate => {
+                options.on_duplicate = Some(parse_duplicate_policy(value)?);
+            }
+            _ => return Err(Error(String::from("ERR syntax error"))),
+        }
+        i += 2;
+    }
+
+    Ok(options)
+}
+
+fn parse_duplicate_policy(value: &str) -> Result<DuplicatePolicy, DataType> {
+    DuplicatePolicy::parse(value).ok_or_else(|| Error(String::from("ERR TSDB: Unknown DUPLICATE_POLICY")))
+}
+
+/// Parses the timestamp of a new sample, `*` is the current time
+pub fn parse_timestamp(value: &str) -> Result<i64, DataType> {
+    if value == "*" {
+        return Ok(chrono::Utc::now().timestamp_millis());
+    }
+
+    match value.parse::<i64>() {
+        Ok(timestamp) if timestamp >= 0 => Ok(timestamp),
+        _ => Err(Error(String::from("ERR TSDB: invalid timestamp"))),
+    }
+}
+
+/// Parses the start or end of a range, `-` is the earliest and `+` the latest possible timestamp
+pub fn parse_range_timestamp(value: &str) -> Result<i64, DataType> {
+    match value {
+        "-" => Ok(0),
+        "+" => Ok(i64::MAX),
+        _ => value.parse::<i64>().map_err(|_| Error(String::from("ERR TSDB: invalid timestamp"))),
+    }
+}
+
+pub fn parse_value(value: &str) -> Result<f64, DataType> {
+    match value.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(Error(String::from("ERR TSDB: invalid value"))),
+    }
+}
+
+pub fn parse_aggregation(aggregation: &str, bucket_duration: &str) -> Result<(Aggregation, u64), DataType> {
+    let aggregation = Aggregation::parse(aggregation).ok_or_else(|| Error(String::from("ERR TSDB: Unknown aggregation type")))?;
+
+    match bucket_duration.parse::<u64>() {
+        Ok(bucket_duration) if bucket_duration > 0 && bucket_duration <= i64::MAX as u64 => Ok((aggregation, bucket_duration)),
+        _ => Err(Error(String::from("ERR TSDB: bucketDuration must be greater than zero"))),
+    }
+}
+
+/// Parses COUNT, AGGREGATION and for TS.MRANGE also WITHLABELS and FILTER (which consumes all remaining arguments)
+pub fn parse_range_options(args: &[String], is_multi_range: bool) -> Result<RangeOptions, DataType> {
+    let mut options = RangeOptions {
+        count: None,
+        aggregation: None,
+        with_labels: false,
+        filters: Vec::new(),
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].to_uppercase().as_str() {
+            "COUNT" if i + 1 < args.len() => {
+                let count = args[i + 1].parse::<usize>().map_err(|_| Error(String::from("ERR TSDB: Couldn't parse COUNT")))?;
+                options.count = Some(count);
+                i += 2;
+            }
+            "AGGREGATION" if i + 2 < args.len() => {
+                options.aggregation = Some(parse_aggregation(&args[i + 1], &args[i + 2])?);
+                i += 3;
+            }
+            "WITHLABELS" if is_multi_range => {
+                options.with_labels = true;
+                i += 1;
+            }
+            "FILTER" if is_multi_range => {
+                for filter in args[i + 1..].iter() {
+                    options.filters.push(LabelFilter::parse(filter).map_err(Error)?);
+                }
+                break;
+            }
+            _ => return Err(Error(String::from("ERR syntax error"))),
+        }
+    }
+
+    if is_multi_range && !options.filters.iter().any(|filter| filter.is_positive()) {
+        return Err(Error(String::from("ERR TSDB: please provide at least one matcher")));
+    }
+
+    Ok(options)
+}
+
+/// Returns the (aggregated) samples within the range, limited by the COUNT option
+pub fn query_range(series: &TimeSeries, from: i64, to: i64, options: &RangeOptions, reverse: bool) -> Vec<(i64, f64)> {
+    if from > to {
+        return Vec::new();
+    }
+
+    let mut samples = match options.aggregation {
+        Some((aggregation, bucket_duration)) => aggregate_samples(series.range(from..=to), aggregation, bucket_duration),
+        None => series.range(from..=to).map(|(timestamp, value)| (*timestamp, *value)).collect(),
+    };
+
+    if reverse {
+        samples.reverse();
+    }
+    if let Some(count) = options.count {
+        samples.truncate(count);
+    }
+    samples
+}
+
+pub fn samples_to_response(samples: Vec<(i64, f64)>) -> DataType {
+    Array(samples.into_iter().map(|(timestamp, value)| sample_to_response(timestamp, value)).collect())
+}
+
+pub fn sample_to_response(timestamp: i64, value: f64) -> DataType {
+    Array(vec![Integer(timestamp), BulkString(value.to_string())])
+}